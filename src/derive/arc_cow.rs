@@ -0,0 +1,214 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::deref_expr;
+use crate::utils::deref_self_arguments;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::is_pointer_receiver;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    for item in trait_.items.iter() {
+        if let syn::TraitItem::Method(ref m) = item {
+            if let Some(constness) = m.sig.constness {
+                let msg = "cannot derive `ArcCow` for a trait declaring `const fn` methods";
+                return Err(syn::Error::new(constness.span(), msg));
+            }
+
+            let mut call = signature_to_method_call(&m.sig)?;
+            match m.sig.receiver() {
+                // fn()
+                None => {
+                    let msg = "cannot derive `ArcCow` for a trait declaring associated functions";
+                    return Err(syn::Error::new(m.sig.ident.span(), msg));
+                }
+                // `fn(self: Arc<Self>)`: the receiver is an `Arc` of the
+                // wrapper type, so the wrapper itself is cloned out of it
+                Some(receiver) if is_pointer_receiver(receiver, "Arc") => {
+                    *call.receiver = parse_quote!(std::sync::Arc::clone(&*self));
+                }
+                // `fn(self: Type)`
+                Some(syn::FnArg::Typed(pat)) => {
+                    let msg = "cannot derive `ArcCow` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                // `fn(&mut self)`: the shared value is cloned before being
+                // mutated, unless this is the only reference to it
+                Some(syn::FnArg::Receiver(r)) if r.mutability.is_some() => {
+                    *call.receiver = parse_quote!(std::sync::Arc::make_mut(self));
+                }
+                // `fn(&self)`
+                Some(syn::FnArg::Receiver(r)) if r.reference.is_some() => {
+                    *call.receiver = deref_expr(deref_expr(*call.receiver));
+                }
+                // `fn(self)`
+                Some(syn::FnArg::Receiver(r)) => {
+                    let msg = "cannot derive `ArcCow` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+            }
+            // pass the wrapped values of the arguments typed after `Self`
+            deref_self_arguments(&m.sig, &mut call, "ArcCow", false)?;
+
+            let signature = &m.sig;
+            let attrs = forwarded_attributes(&m.attrs);
+            let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+            let mut body = forwarding_body(signature, call);
+            // wrap the value returned by methods returning `Self`, possibly
+            // in a `Result` or an `Option`
+            if returns_self(signature) {
+                body = parse_quote!(std::convert::From::from(#body));
+            } else if returns_wrapped_self(signature) {
+                body = parse_quote!((#body).map(std::convert::From::from));
+            }
+            let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+            methods.push(item)
+        }
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation, and which can be cloned by `Arc::make_mut`
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let impl_generics = impl_generics(
+        trait_,
+        Some(parse_quote!(#generic_type: #trait_ident #trait_generic_names + std::clone::Clone)),
+    );
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for std::sync::Arc<#generic_type> #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + std::clone::Clone> Trait for std::sync::Arc<T> {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_mix() {
+            let trait_ = parse_quote!(
+                trait Buffer {
+                    fn len(&self) -> usize;
+                    fn push(&mut self, byte: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<B: Buffer + std::clone::Clone> Buffer for std::sync::Arc<B> {
+                        #[inline]
+                        fn len(&self) -> usize {
+                            (*(*self)).len()
+                        }
+                        #[inline]
+                        fn push(&mut self, byte: u8) {
+                            std::sync::Arc::make_mut(self).push(byte)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `ArcCow` for a trait declaring `self` methods"
+            );
+        }
+
+        #[test]
+        fn receiver_arc() {
+            let trait_ = parse_quote!(
+                trait Task {
+                    fn spawn(self: std::sync::Arc<Self>);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Task + std::clone::Clone> Task for std::sync::Arc<T> {
+                        #[inline]
+                        fn spawn(self: std::sync::Arc<Self>) {
+                            std::sync::Arc::clone(&*self).spawn()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_arbitrary() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self: std::pin::Pin<&mut Self>);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn method_const() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    const fn my_method(&self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::borrowed_return;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_result;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    derive_with(trait_, generic, ufcs, false)
+}
+
+pub fn derive_try(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    derive_with(trait_, generic, ufcs, true)
+}
+
+fn derive_with(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+    fallible: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    let derive = if fallible { "ArcMutexTry" } else { "ArcMutex" };
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(constness) = m.sig.constness {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring `const fn` methods",
+                derive
+            );
+            return Err(syn::Error::new(constness.span(), msg));
+        }
+
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = format!(
+                        "cannot derive `{}` for a trait declaring `self` methods",
+                        derive
+                    );
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Typed(pat) => {
+                    let msg = format!("cannot derive `{}` for a trait declaring methods with arbitrary receiver types", derive);
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
+            }
+        }
+
+        // the lock is only held as long as the forwarding call, so
+        // neither `Self` nor a borrow can be returned from it
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring methods returning `Self`",
+                derive
+            );
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+        if let Some(span) = borrowed_return(&m.sig) {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring methods returning references borrowed from `self`, which cannot outlive the lock of the `Mutex` in the forwarding method",
+                derive
+            );
+            return Err(syn::Error::new(span, msg));
+        }
+
+        // the fallible flavor reports a failed lock through the error
+        // type of the method, so every method must return a `Result`
+        if fallible && !returns_result(&m.sig) {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring methods not returning `Result`",
+                derive
+            );
+            let span = match m.sig.output {
+                syn::ReturnType::Type(_, ref ty) => ty.span(),
+                syn::ReturnType::Default => m.sig.ident.span(),
+            };
+            return Err(syn::Error::new(span, msg));
+        }
+
+        // every method goes through a lock of the mutex, and `Mutex::lock`
+        // is called explicitly so that it is not shadowed by a trait
+        // method of the same name
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = match (fallible, cfg!(feature = "parking_lot")) {
+            (false, true) => parse_quote!(parking_lot::Mutex::lock(self)),
+            (false, false) => parse_quote!(std::sync::Mutex::lock(self).unwrap()),
+            (true, true) => parse_quote!(parking_lot::Mutex::try_lock(self)
+                .ok_or(std::sync::TryLockError::<()>::WouldBlock)?),
+            // the guard cannot outlive the method, so a poisoned lock
+            // is reported without it
+            (true, false) => {
+                parse_quote!(std::sync::Mutex::try_lock(self).map_err(|e| match e {
+                    std::sync::TryLockError::Poisoned(_) => {
+                        std::sync::TryLockError::Poisoned(std::sync::PoisonError::new(()))
+                    }
+                    std::sync::TryLockError::WouldBlock => std::sync::TryLockError::WouldBlock,
+                })?)
+            }
+        };
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    // use the `parking_lot` mutex if the feature is enabled, which cannot
+    // be poisoned
+    let doc = match (fallible, cfg!(feature = "parking_lot")) {
+        (false, true) => " Every method locks the mutex.",
+        (false, false) => " Every method locks the mutex, and panics if the mutex is poisoned.",
+        (true, true) => " Every method tries to lock the mutex, and returns an error if it is already locked.",
+        (true, false) => " Every method tries to lock the mutex, and returns an error if it is poisoned or already locked.",
+    };
+    if cfg!(feature = "parking_lot") {
+        Ok(parse_quote!(
+            #[automatically_derived]
+            #[doc = #doc]
+            impl #impl_generics #trait_ident #trait_generic_names for std::sync::Arc<parking_lot::Mutex<#generic_type>> #where_clause {
+                #(#types)*
+                #(#methods)*
+            }
+        ))
+    } else {
+        Ok(parse_quote!(
+            #[automatically_derived]
+            #[doc = #doc]
+            impl #impl_generics #trait_ident #trait_generic_names for std::sync::Arc<std::sync::Mutex<#generic_type>> #where_clause {
+                #(#types)*
+                #(#methods)*
+            }
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every method locks the mutex, and panics if the mutex is poisoned."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<std::sync::Mutex<T>> {}
+                )
+            );
+        }
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn receiver_ref_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> u8;
+                    fn set(&mut self, value: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every method locks the mutex, and panics if the mutex is poisoned."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<std::sync::Mutex<T>> {
+                        #[inline]
+                        fn get(&self) -> u8 {
+                            std::sync::Mutex::lock(self).unwrap().get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u8) {
+                            std::sync::Mutex::lock(self).unwrap().set(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn return_reference() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn name(&self) -> &str;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `ArcMutex` for a trait declaring methods returning references borrowed from `self`, which cannot outlive the lock of the `Mutex` in the forwarding method"
+            );
+
+            let trait_ = parse_quote!(
+                trait Trait<'a> {
+                    fn name(&self) -> Option<&str>;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+
+            let trait_ = parse_quote!(
+                trait Trait<'a> {
+                    fn name(&self) -> &'static str;
+                    fn label(&self) -> &'a str;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_ok());
+        }
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait MyTrait<T> {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every method locks the mutex, and panics if the mutex is poisoned."]
+                    impl<T, MT: MyTrait<T> + ?Sized> MyTrait<T> for std::sync::Arc<std::sync::Mutex<MT>> {}
+                )
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "parking_lot")]
+        fn parking_lot() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> u8;
+                    fn set(&mut self, value: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every method locks the mutex."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<parking_lot::Mutex<T>> {
+                        #[inline]
+                        fn get(&self) -> u8 {
+                            parking_lot::Mutex::lock(self).get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u8) {
+                            parking_lot::Mutex::lock(self).set(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn try_result() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> Result<u8, Error>;
+                }
+            );
+            let derived = super::super::derive_try(&trait_, None, false).unwrap();
+            let expected: syn::Type = parse_quote!(std::sync::Arc<std::sync::Mutex<T>>);
+            assert_eq!(*derived.self_ty, expected);
+            let items = &derived.items;
+            let tokens = quote::quote!(#(#items)*).to_string();
+            assert!(tokens.contains("std :: sync :: Mutex :: try_lock (self)"));
+            assert!(tokens.contains("std :: sync :: PoisonError :: new (())"));
+        }
+
+        #[test]
+        #[cfg(feature = "parking_lot")]
+        fn try_parking_lot() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> Result<u8, Error>;
+                }
+            );
+            assert_eq!(
+                super::super::derive_try(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every method tries to lock the mutex, and returns an error if it is already locked."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<parking_lot::Mutex<T>> {
+                        #[inline]
+                        fn get(&self) -> Result<u8, Error> {
+                            parking_lot::Mutex::try_lock(self)
+                                .ok_or(std::sync::TryLockError::<()>::WouldBlock)?
+                                .get()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn try_not_result() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> u8;
+                }
+            );
+            let err = super::super::derive_try(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `ArcMutexTry` for a trait declaring methods not returning `Result`"
+            );
+        }
+    }
+}
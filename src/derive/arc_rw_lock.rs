@@ -0,0 +1,283 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::borrowed_return;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::lock_kind;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(constness) = m.sig.constness {
+            let msg = "cannot derive `ArcRwLock` for a trait declaring `const fn` methods";
+            return Err(syn::Error::new(constness.span(), msg));
+        }
+
+        // `&self` methods only need a read lock, while `&mut self` methods
+        // need a write lock, unless another lock is given explicitly
+        let mutable = match m.sig.receiver() {
+            Some(syn::FnArg::Receiver(r)) if r.reference.is_none() => {
+                let msg = "cannot derive `ArcRwLock` for a trait declaring `self` methods";
+                return Err(syn::Error::new(r.span(), msg));
+            }
+            Some(syn::FnArg::Typed(pat)) => {
+                let msg = "cannot derive `ArcRwLock` for a trait declaring methods with arbitrary receiver types";
+                return Err(syn::Error::new(pat.span(), msg));
+            }
+            Some(syn::FnArg::Receiver(r)) => r.mutability.is_some(),
+            None => {
+                let msg = "cannot derive `ArcRwLock` for a trait declaring associated functions";
+                return Err(syn::Error::new(m.sig.ident.span(), msg));
+            }
+        };
+        let write = match lock_kind(&m.attrs)? {
+            Some(false) if mutable => {
+                let msg = "cannot derive `ArcRwLock` for a `&mut self` method forwarded through a read lock";
+                return Err(syn::Error::new(m.sig.ident.span(), msg));
+            }
+            Some(write) => write,
+            None => mutable,
+        };
+
+        // the lock is only held as long as the forwarding call, so
+        // neither `Self` nor a borrow can be returned from it
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `ArcRwLock` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+        if let Some(span) = borrowed_return(&m.sig) {
+            let msg = "cannot derive `ArcRwLock` for a trait declaring methods returning references borrowed from `self`, which cannot outlive the lock of the `RwLock` in the forwarding method";
+            return Err(syn::Error::new(span, msg));
+        }
+
+        // `RwLock::read` and `RwLock::write` are called explicitly so that
+        // they are not shadowed by a trait method of the same name
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = match (write, cfg!(feature = "parking_lot")) {
+            (false, true) => parse_quote!(parking_lot::RwLock::read(self)),
+            (true, true) => parse_quote!(parking_lot::RwLock::write(self)),
+            (false, false) => parse_quote!(std::sync::RwLock::read(self).unwrap()),
+            (true, false) => parse_quote!(std::sync::RwLock::write(self).unwrap()),
+        };
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    // use the `parking_lot` lock if the feature is enabled, which cannot
+    // be poisoned
+    if cfg!(feature = "parking_lot") {
+        Ok(parse_quote!(
+            #[automatically_derived]
+            #[doc = " Every `&self` method takes a read lock, and every `&mut self` method a write lock."]
+            impl #impl_generics #trait_ident #trait_generic_names for std::sync::Arc<parking_lot::RwLock<#generic_type>> #where_clause {
+                #(#types)*
+                #(#methods)*
+            }
+        ))
+    } else {
+        Ok(parse_quote!(
+            #[automatically_derived]
+            #[doc = " Every `&self` method takes a read lock, and every `&mut self` method a write lock, and panics if the lock is poisoned."]
+            impl #impl_generics #trait_ident #trait_generic_names for std::sync::Arc<std::sync::RwLock<#generic_type>> #where_clause {
+                #(#types)*
+                #(#methods)*
+            }
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every `&self` method takes a read lock, and every `&mut self` method a write lock, and panics if the lock is poisoned."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<std::sync::RwLock<T>> {}
+                )
+            );
+        }
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn receiver_ref_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> u8;
+                    fn set(&mut self, value: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every `&self` method takes a read lock, and every `&mut self` method a write lock, and panics if the lock is poisoned."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<std::sync::RwLock<T>> {
+                        #[inline]
+                        fn get(&self) -> u8 {
+                            std::sync::RwLock::read(self).unwrap().get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u8) {
+                            std::sync::RwLock::write(self).unwrap().set(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "parking_lot")]
+        fn receiver_ref_mut_parking_lot() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> u8;
+                    fn set(&mut self, value: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every `&self` method takes a read lock, and every `&mut self` method a write lock."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<parking_lot::RwLock<T>> {
+                        #[inline]
+                        fn get(&self) -> u8 {
+                            parking_lot::RwLock::read(self).get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u8) {
+                            parking_lot::RwLock::write(self).set(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        #[cfg(not(feature = "parking_lot"))]
+        fn lock_write() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    #[blanket(lock = "write")]
+                    fn next_id(&self) -> u8;
+                    fn get(&self) -> u8;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    #[doc = " Every `&self` method takes a read lock, and every `&mut self` method a write lock, and panics if the lock is poisoned."]
+                    impl<T: Trait + ?Sized> Trait for std::sync::Arc<std::sync::RwLock<T>> {
+                        #[inline]
+                        fn next_id(&self) -> u8 {
+                            std::sync::RwLock::write(self).unwrap().next_id()
+                        }
+                        #[inline]
+                        fn get(&self) -> u8 {
+                            std::sync::RwLock::read(self).unwrap().get()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn lock_read_receiver_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    #[blanket(lock = "read")]
+                    fn set(&mut self, value: u8);
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `ArcRwLock` for a `&mut self` method forwarded through a read lock"
+            );
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn into_value(self) -> u8;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `ArcRwLock` for a trait declaring `self` methods"
+            );
+        }
+
+        #[test]
+        fn return_reference() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn name(&self) -> &str;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `ArcRwLock` for a trait declaring methods returning references borrowed from `self`, which cannot outlive the lock of the `RwLock` in the forwarding method"
+            );
+        }
+    }
+}
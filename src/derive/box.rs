@@ -2,65 +2,141 @@ use syn::parse_quote;
 use syn::spanned::Spanned;
 
 use crate::utils::deref_expr;
-use crate::utils::generics_declaration_to_generics;
+use crate::utils::deref_self_arguments;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::is_pin_mut_receiver;
+use crate::utils::is_pin_ref_receiver;
+use crate::utils::is_pointer_receiver;
+use crate::utils::returns_pin_mut_self;
 use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
 use crate::utils::trait_to_generic_ident;
+use crate::utils::type_to_string;
+use crate::utils::via_adapter;
+use crate::utils::wrap_returned_self;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
 
-pub fn derive(trait_: &syn::ItemTrait) -> syn::Result<syn::ItemImpl> {
     // build the methods
     let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let mut unpin = false;
     for item in trait_.items.iter() {
         if let syn::TraitItem::Method(ref m) = item {
+            if let Some(constness) = m.sig.constness {
+                let msg = "cannot derive `Box` for a trait declaring `const fn` methods";
+                return Err(syn::Error::new(constness.span(), msg));
+            }
+
             let signature = &m.sig;
             let mut call = signature_to_method_call(signature)?;
+            // methods returning the pinned receiver return the pinned box,
+            // which is borrowed again once the forwarding call is done
+            let pin_return = returns_pin_mut_self(signature)
+                && signature.receiver().map(is_pin_mut_receiver) == Some(true);
 
             match signature.receiver() {
                 // fn()
                 None => unimplemented!(),
+                // `fn(self: Box<Self>)`: the receiver is a box of the wrapper
+                // type, so the wrapper itself is moved out of it
+                Some(receiver) if is_pointer_receiver(receiver, "Box") => {
+                    *call.receiver = deref_expr(*call.receiver);
+                }
+                // `fn(self: Pin<&mut Self>)` and `fn(self: Pin<&Self>)`: the
+                // box is pinned, but not its content, which can only be pinned
+                // again if the wrapped type is `Unpin`
+                Some(receiver) if is_pin_mut_receiver(receiver) => {
+                    *call.receiver = if pin_return {
+                        parse_quote!(std::pin::Pin::new(&mut **__this))
+                    } else {
+                        parse_quote!(std::pin::Pin::new(&mut **std::pin::Pin::get_mut(self)))
+                    };
+                    unpin = true;
+                }
+                Some(receiver) if is_pin_ref_receiver(receiver) => {
+                    *call.receiver = parse_quote!(std::pin::Pin::new(&**std::pin::Pin::get_ref(self)));
+                    unpin = true;
+                }
                 // `fn(self: Type)`
                 Some(syn::FnArg::Typed(pat)) => {
-                    let msg = "cannot derive `Box` for a trait declaring methods with arbitrary receiver types";
-                    return Err(syn::Error::new(pat.span(), msg));
+                    let msg = format!(
+                        "cannot derive `Box` for receiver type `{}`",
+                        type_to_string(&pat.ty)
+                    );
+                    return Err(syn::Error::new(pat.pat.span(), msg));
                 }
                 // `fn(&self)` and `fn(&mut self)`
                 Some(syn::FnArg::Receiver(r)) if r.reference.is_some() => {
-                    call.receiver = Box::new(deref_expr(deref_expr(*call.receiver)));
+                    *call.receiver = deref_expr(deref_expr(*call.receiver));
                 }
                 // `fn(self)`
                 Some(syn::FnArg::Receiver(_)) => {
-                    call.receiver = Box::new(deref_expr(*call.receiver));
+                    *call.receiver = deref_expr(*call.receiver);
                 }
             }
+            // pass the wrapped values of the arguments typed after `Self`
+            deref_self_arguments(signature, &mut call, "Box", true)?;
 
-            let item = parse_quote!(#[inline] #signature { #call });
+            let attrs = forwarded_attributes(&m.attrs);
+            // forward the method through the adapter given with `via = "..."`
+            if let Some(via) = via_adapter(&m.attrs, &m.sig)? {
+                *call.receiver = parse_quote!(self.#via());
+            }
+            let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+            // wrap the values returned by methods returning `Self`, possibly
+            // in a `Result`, an `Option` or a tuple; the pinned value returned
+            // as `Pin<&mut Self>` is expected to be the receiver, and is
+            // replaced with the pinned box
+            let item = if pin_return {
+                let call = forwarding_body(signature, call);
+                parse_quote!(#(#attrs)* #[inline] #signature {
+                    let __this = std::pin::Pin::get_mut(self);
+                    #call;
+                    std::pin::Pin::new(__this)
+                })
+            } else {
+                let body = wrap_returned_self(signature, forwarding_body(signature, call), "Box")?;
+                parse_quote!(#(#attrs)* #[inline] #signature { #body })
+            };
             methods.push(item)
         }
     }
 
-    // build an identifier for the generic type used for the implementation
     let trait_ident = &trait_.ident;
-    let generic_type = trait_to_generic_ident(&trait_);
 
     // build the generics for the impl block:
     // we use the same generics as the trait itself, plus
     // a generic type that implements the trait for which we provide the
     // blanket implementation
-    let trait_generics = &trait_.generics;
-    let where_clause = &trait_.generics.where_clause;
-    let mut impl_generics = trait_generics.clone();
-
-    // we must however remove the generic type bounds, to avoid repeating them
-    let mut trait_generic_names = trait_generics.clone();
-    trait_generic_names.params = generics_declaration_to_generics(&trait_generics.params)?;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unpin {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + std::marker::Unpin)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
 
-    impl_generics.params.push(syn::GenericParam::Type(
-        parse_quote!(#generic_type: #trait_ident #trait_generic_names),
-    ));
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
 
     // generate the impl block
     Ok(parse_quote!(
         #[automatically_derived]
-        impl #impl_generics #trait_ident #trait_generic_names for Box<#generic_type> #where_clause {
+        impl #impl_generics #trait_ident #trait_generic_names for std::boxed::Box<#generic_type> #where_clause {
+            #(#types)*
             #(#methods)*
         }
     ))
@@ -77,12 +153,12 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
             assert_eq!(
                 derived,
                 parse_quote!(
                     #[automatically_derived]
-                    impl<MT: MyTrait> MyTrait for Box<MT> {}
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {}
                 )
             );
         }
@@ -95,10 +171,10 @@ mod tests {
                 }
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
-                    impl<MT: MyTrait> MyTrait for Box<MT> {
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
                         #[inline]
                         fn my_method(&self) {
                             (*(*self)).my_method()
@@ -108,6 +184,27 @@ mod tests {
             );
         }
 
+        #[test]
+        fn method_const_generic_array() {
+            let trait_ = parse_quote!(
+                trait Snapshot {
+                    fn snapshot<const N: usize>(&self) -> [u8; N];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<S: Snapshot> Snapshot for std::boxed::Box<S> {
+                        #[inline]
+                        fn snapshot<const N: usize>(&self) -> [u8; N] {
+                            (*(*self)).snapshot::<N>()
+                        }
+                    }
+                )
+            );
+        }
+
         #[test]
         fn receiver_mut() {
             let trait_ = parse_quote!(
@@ -116,10 +213,10 @@ mod tests {
                 }
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
-                    impl<MT: MyTrait> MyTrait for Box<MT> {
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
                         #[inline]
                         fn my_method(&mut self) {
                             (*(*self)).my_method()
@@ -137,10 +234,10 @@ mod tests {
                 }
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
-                    impl<MT: MyTrait> MyTrait for Box<MT> {
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
                         #[inline]
                         fn my_method(self) {
                             (*self).my_method()
@@ -151,59 +248,1113 @@ mod tests {
         }
 
         #[test]
-        fn receiver_arbitrary() {
+        fn receiver_self_arguments() {
             let trait_ = parse_quote!(
                 trait MyTrait {
-                    fn my_method(self: Box<Self>);
+                    fn into_parts(self, separator: char, limit: usize) -> (String, Vec<u8>);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn into_parts(self, separator: char, limit: usize) -> (String, Vec<u8>) {
+                            (*self).into_parts(separator, limit)
+                        }
+                    }
+                )
+            );
         }
 
         #[test]
-        fn generics() {
+        fn argument_self() {
             let trait_ = parse_quote!(
-                trait MyTrait<T> {}
+                trait MyTrait {
+                    fn merge(&mut self, other: &Self);
+                    fn absorb(&mut self, other: Self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn merge(&mut self, other: &Self) {
+                            (*(*self)).merge(&**other)
+                        }
+                        #[inline]
+                        fn absorb(&mut self, other: Self) {
+                            (*(*self)).absorb(*other)
+                        }
+                    }
+                )
             );
-            let derived = super::super::derive(&trait_).unwrap();
+        }
 
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn with_flag(self) -> Self;
+                    fn duplicate(&self) -> Self;
+                }
+            );
             assert_eq!(
-                derived,
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
-                    impl<T, MT: MyTrait<T>> MyTrait<T> for Box<MT> {}
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn with_flag(self) -> Self {
+                            std::convert::From::from((*self).with_flag())
+                        }
+                        #[inline]
+                        fn duplicate(&self) -> Self {
+                            std::convert::From::from((*(*self)).duplicate())
+                        }
+                    }
                 )
             );
         }
 
         #[test]
-        fn generics_bounded() {
+        fn return_tuple_self() {
             let trait_ = parse_quote!(
-                trait MyTrait<T: 'static + Send> {}
+                trait MyTrait {
+                    fn split(self) -> (Self, Self);
+                    fn pop(&mut self) -> Option<(Self, u8)>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn split(self) -> (Self, Self) {
+                            {
+                                let (__0, __1) = (*self).split();
+                                (std::convert::From::from(__0), std::convert::From::from(__1))
+                            }
+                        }
+                        #[inline]
+                        fn pop(&mut self) -> Option<(Self, u8)> {
+                            ((*(*self)).pop()).map(|__value| {
+                                let (__0, __1) = __value;
+                                (std::convert::From::from(__0), __1)
+                            })
+                        }
+                    }
+                )
             );
-            let derived = super::super::derive(&trait_).unwrap();
 
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn children(&self) -> Vec<Self>;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
             assert_eq!(
-                derived,
+                err.to_string(),
+                "cannot derive `Box` for method `children` returning `Self` in `Vec<Self>`"
+            );
+        }
+
+        #[test]
+        fn return_box_dyn() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn name(&self) -> Box<dyn Display>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
-                    impl<T: 'static + Send, MT: MyTrait<T>> MyTrait<T> for Box<MT> {}
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn name(&self) -> Box<dyn Display> {
+                            (*(*self)).name()
+                        }
+                    }
                 )
             );
         }
 
         #[test]
-        fn generics_lifetime() {
+        fn return_wrapped_self() {
             let trait_ = parse_quote!(
-                trait MyTrait<'a, 'b: 'a, T: 'static + Send> {}
+                trait MyTrait {
+                    fn build(self) -> Result<Self, Error>;
+                    fn next(&self) -> Option<Self>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn build(self) -> Result<Self, Error> {
+                            ((*self).build()).map(std::convert::From::from)
+                        }
+                        #[inline]
+                        fn next(&self) -> Option<Self> {
+                            ((*(*self)).next()).map(std::convert::From::from)
+                        }
+                    }
+                )
             );
-            let derived = super::super::derive(&trait_).unwrap();
+        }
 
+        #[test]
+        fn method_async() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    async fn get(&self, url: &str) -> Vec<u8>;
+                }
+            );
             assert_eq!(
-                derived,
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        async fn get(&self, url: &str) -> Vec<u8> {
+                            (*(*self)).get(url).await
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_unsafe() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    unsafe fn raw(&self) -> *const u8;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        unsafe fn raw(&self) -> *const u8 {
+                            unsafe { (*(*self)).raw() }
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_impl_trait() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn items(&self) -> impl Iterator<Item = u32> + '_;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn items(&self) -> impl Iterator<Item = u32> + '_ {
+                            (*(*self)).items()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_self_impl_trait() {
+            let trait_ = parse_quote!(
+                trait Builder {
+                    fn build(self) -> impl std::fmt::Display;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<B: Builder> Builder for std::boxed::Box<B> {
+                        #[inline]
+                        fn build(self) -> impl std::fmt::Display {
+                            (*self).build()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn argument_impl_trait() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn push(&mut self, item: impl Into<String>);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn push(&mut self, item: impl Into<String>) {
+                            (*(*self)).push(item)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_const_generic() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn fill<const N: usize>(&self) -> [u8; N];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn fill<const N: usize>(&self) -> [u8; N] {
+                            (*(*self)).fill::<N>()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_sized() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn my_sized_method(&self)
+                    where
+                        Self: Sized,
+                    {
+                    }
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[inline]
+                        fn my_sized_method(&self)
+                        where
+                            Self: Sized,
+                        {
+                            (*(*self)).my_sized_method()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_self_sized() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn finish(self) -> u64
+                    where
+                        Self: Sized;
+                }
+            );
+            assert_eq!(
+                crate::utils::pretty_print(&super::super::derive(&trait_, None, false).unwrap()),
+                r#"#[automatically_derived]
+impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+    #[inline]
+    fn finish(self) -> u64
+    where
+        Self: Sized,
+    {
+        (*self).finish()
+    }
+}
+"#
+            );
+        }
+
+        #[test]
+        fn associated_type() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    type Item;
+                    type Iter<'a>: Iterator<Item = &'a Self::Item>
+                    where
+                        Self: 'a;
+                    fn iter<'a>(&'a self) -> Self::Iter<'a>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        type Item = <MT as MyTrait>::Item;
+                        type Iter<'a>
+                            = <MT as MyTrait>::Iter<'a>
+                        where
+                            Self: 'a;
+                        #[inline]
+                        fn iter<'a>(&'a self) -> Self::Iter<'a> {
+                            (*(*self)).iter()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn associated_type_default() {
+            let trait_ = parse_quote!(
+                trait Codec {
+                    type Err = std::io::Error;
+                    type Item;
+                    fn decode(&self) -> Result<Self::Item, Self::Err>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<C: Codec> Codec for std::boxed::Box<C> {
+                        type Item = <C as Codec>::Item;
+                        #[inline]
+                        fn decode(&self) -> Result<Self::Item, Self::Err> {
+                            (*(*self)).decode()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn associated_const() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    const MASK: [u8; 4];
+                    const NAME: &'static str = "name";
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
-                    impl<'a, 'b: 'a, T: 'static + Send, MT: MyTrait<'a, 'b, T>> MyTrait<'a, 'b, T> for Box<MT> {}
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        const MASK: [u8; 4] = <MT as MyTrait>::MASK;
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn associated_type_method() {
+            let trait_ = parse_quote!(
+                trait Graph {
+                    type Node;
+                    fn neighbors(&self, n: &Self::Node) -> Vec<Self::Node>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<G: Graph> Graph for std::boxed::Box<G> {
+                        type Node = <G as Graph>::Node;
+                        #[inline]
+                        fn neighbors(&self, n: &Self::Node) -> Vec<Self::Node> {
+                            (*(*self)).neighbors(n)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_allow() {
+            let trait_ = parse_quote!(
+                trait Canvas {
+                    #[allow(clippy::too_many_arguments)]
+                    fn blit(&mut self, x: u32, y: u32, w: u32, h: u32, r: u8, g: u8, b: u8, a: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<C: Canvas> Canvas for std::boxed::Box<C> {
+                        #[allow(clippy::too_many_arguments)]
+                        #[inline]
+                        fn blit(&mut self, x: u32, y: u32, w: u32, h: u32, r: u8, g: u8, b: u8, a: u8) {
+                            (*(*self)).blit(x, y, w, h, r, g, b, a)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_cfg() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    #[cfg(feature = "extra")]
+                    fn extra(&self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait> MyTrait for std::boxed::Box<MT> {
+                        #[cfg(feature = "extra")]
+                        #[inline]
+                        fn extra(&self) {
+                            (*(*self)).extra()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_const() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    const fn my_method(&self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn method_abi() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    extern "C" fn callback(&self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn method_variadic() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    unsafe fn printf(&self, format: *const u8, ...);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn receiver_arbitrary() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn my_method(self: Pin<Rc<Self>>);
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Box` for receiver type `Pin<Rc<Self>>`"
+            );
+        }
+
+        #[test]
+        fn receiver_pin() {
+            let trait_ = parse_quote!(
+                trait Builder {
+                    fn as_pin(self: Pin<&mut Self>) -> Pin<&mut Self>;
+                    fn poll(self: Pin<&mut Self>) -> bool;
+                    fn peek(self: Pin<&Self>) -> u8;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<B: Builder + std::marker::Unpin> Builder for std::boxed::Box<B> {
+                        #[inline]
+                        fn as_pin(self: Pin<&mut Self>) -> Pin<&mut Self> {
+                            let __this = std::pin::Pin::get_mut(self);
+                            std::pin::Pin::new(&mut **__this).as_pin();
+                            std::pin::Pin::new(__this)
+                        }
+                        #[inline]
+                        fn poll(self: Pin<&mut Self>) -> bool {
+                            std::pin::Pin::new(&mut **std::pin::Pin::get_mut(self)).poll()
+                        }
+                        #[inline]
+                        fn peek(self: Pin<&Self>) -> u8 {
+                            std::pin::Pin::new(&**std::pin::Pin::get_ref(self)).peek()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_box() {
+            let trait_ = parse_quote!(
+                trait Task {
+                    fn run(self: Box<Self>) -> u8;
+                    fn spawn(self: std::boxed::Box<Self>, delay: u64);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Task> Task for std::boxed::Box<T> {
+                        #[inline]
+                        fn run(self: Box<Self>) -> u8 {
+                            (*self).run()
+                        }
+                        #[inline]
+                        fn spawn(self: std::boxed::Box<Self>, delay: u64) {
+                            (*self).spawn(delay)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_boxed_future() {
+            let trait_ = parse_quote!(
+                trait Fetcher {
+                    fn get(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = u8> + '_>>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<F: Fetcher> Fetcher for std::boxed::Box<F> {
+                        #[inline]
+                        fn get(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = u8> + '_>> {
+                            (*(*self)).get()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn supertraits_method_name() {
+            let trait_ = parse_quote!(
+                trait Named: Base {
+                    fn name(&self) -> String;
+                    fn into_name(self) -> String;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<N: Named> Named for std::boxed::Box<N> {
+                        #[inline]
+                        fn name(&self) -> String {
+                            <N as Named>::name(&(*(*self)))
+                        }
+                        #[inline]
+                        fn into_name(self) -> String {
+                            <N as Named>::into_name((*self))
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn supertraits() {
+            let trait_ = parse_quote!(
+                trait Service: Handler + Clone {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<S: Service> Service for std::boxed::Box<S> {}
+                )
+            );
+        }
+
+        #[test]
+        fn supertraits_lifetime() {
+            let trait_ = parse_quote!(
+                trait Event: 'static {
+                    fn id(&self) -> u32;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<E: Event + 'static> Event for std::boxed::Box<E> {
+                        #[inline]
+                        fn id(&self) -> u32 {
+                            (*(*self)).id()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait MyTrait<T> {}
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T, MT: MyTrait<T>> MyTrait<T> for std::boxed::Box<MT> {}
+                )
+            );
+        }
+
+        #[test]
+        fn generics_const_default() {
+            let trait_ = parse_quote!(
+                trait Buf<const N: usize = 64> {}
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<const N: usize, B: Buf<N>> Buf<N> for std::boxed::Box<B> {}
+                )
+            );
+        }
+
+        #[test]
+        fn generics_const_associated_const() {
+            let trait_ = parse_quote!(
+                trait Packed<const N: usize> {
+                    const BYTES: usize;
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<const N: usize, P: Packed<N>> Packed<N> for std::boxed::Box<P> {
+                        const BYTES: usize = <P as Packed<N>>::BYTES;
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_method_argument() {
+            let trait_ = parse_quote!(
+                trait Handler<M> {
+                    fn handle(&self, msg: M);
+                    fn handle_all(&mut self, msgs: Vec<M>) -> usize;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<M, H: Handler<M>> Handler<M> for std::boxed::Box<H> {
+                        #[inline]
+                        fn handle(&self, msg: M) {
+                            (*(*self)).handle(msg)
+                        }
+                        #[inline]
+                        fn handle_all(&mut self, msgs: Vec<M>) -> usize {
+                            (*(*self)).handle_all(msgs)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_unsized() {
+            let trait_ = parse_quote!(
+                trait Holder<T: ?Sized> {
+                    fn get(&self) -> &T;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: ?Sized, H: Holder<T>> Holder<T> for std::boxed::Box<H> {
+                        #[inline]
+                        fn get(&self) -> &T {
+                            (*(*self)).get()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_method_return() {
+            let trait_ = parse_quote!(
+                trait Bytes {
+                    fn collect<C: FromIterator<u8>>(&self) -> C;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<B: Bytes> Bytes for std::boxed::Box<B> {
+                        #[inline]
+                        fn collect<C: FromIterator<u8>>(&self) -> C {
+                            (*(*self)).collect::<C>()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_method_default() {
+            let trait_ = parse_quote!(
+                trait Parser {
+                    fn parse<T: std::str::FromStr = i32>(&self) -> T;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<P: Parser> Parser for std::boxed::Box<P> {
+                        #[inline]
+                        fn parse<T: std::str::FromStr = i32>(&self) -> T {
+                            (*(*self)).parse::<T>()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_method_arguments() {
+            let trait_ = parse_quote!(
+                trait Store<K: Hash + Eq, V> {
+                    fn get(&self, key: &K) -> Option<&V>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<K: Hash + Eq, V, S: Store<K, V>> Store<K, V> for std::boxed::Box<S> {
+                        #[inline]
+                        fn get(&self, key: &K) -> Option<&V> {
+                            (*(*self)).get(key)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_via() {
+            let trait_ = parse_quote!(
+                trait Bytes {
+                    #[blanket(via = "as_ref")]
+                    fn len(&self) -> usize;
+                    fn clear(&mut self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<B: Bytes> Bytes for std::boxed::Box<B> {
+                        #[inline]
+                        fn len(&self) -> usize {
+                            self.as_ref().len()
+                        }
+                        #[inline]
+                        fn clear(&mut self) {
+                            (*(*self)).clear()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_method_higher_ranked() {
+            let trait_ = parse_quote!(
+                trait Store {
+                    fn with<F: for<'a> FnMut(&'a mut State)>(&self, f: F);
+                    fn with_where<F>(&mut self, f: F)
+                    where
+                        F: for<'a> FnOnce(&'a State) -> &'a str;
+                }
+            );
+            assert_eq!(
+                crate::utils::pretty_print(&super::super::derive(&trait_, None, false).unwrap()),
+                r#"#[automatically_derived]
+impl<S: Store> Store for std::boxed::Box<S> {
+    #[inline]
+    fn with<F: for<'a> FnMut(&'a mut State)>(&self, f: F) {
+        (*(*self)).with::<F>(f)
+    }
+    #[inline]
+    fn with_where<F>(&mut self, f: F)
+    where
+        F: for<'a> FnOnce(&'a State) -> &'a str,
+    {
+        (*(*self)).with_where::<F>(f)
+    }
+}
+"#
+            );
+        }
+
+        #[test]
+        fn generics_inter_parameter_bounds() {
+            let trait_ = parse_quote!(
+                trait Pair<A, B: Into<A>> {
+                    fn merge(&self, b: B) -> A;
+                    fn convert<C: Into<B>>(&self, c: C) -> A;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<A, B: Into<A>, P: Pair<A, B>> Pair<A, B> for std::boxed::Box<P> {
+                        #[inline]
+                        fn merge(&self, b: B) -> A {
+                            (*(*self)).merge(b)
+                        }
+                        #[inline]
+                        fn convert<C: Into<B>>(&self, c: C) -> A {
+                            (*(*self)).convert::<C>(c)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn where_clause_associated_type() {
+            let trait_ = parse_quote!(
+                trait Producer
+                where
+                    Self::Item: Send,
+                {
+                    type Item;
+                    fn produce(&mut self) -> Self::Item;
+                }
+            );
+            assert_eq!(
+                crate::utils::pretty_print(&super::super::derive(&trait_, None, false).unwrap()),
+                r#"#[automatically_derived]
+impl<P: Producer> Producer for std::boxed::Box<P>
+where
+    <P as Producer>::Item: Send,
+{
+    type Item = <P as Producer>::Item;
+    #[inline]
+    fn produce(&mut self) -> Self::Item {
+        (*(*self)).produce()
+    }
+}
+"#
+            );
+        }
+
+        #[test]
+        fn generics_bounded() {
+            let trait_ = parse_quote!(
+                trait MyTrait<T: 'static + Send> {}
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: 'static + Send, MT: MyTrait<T>> MyTrait<T> for std::boxed::Box<MT> {}
+                )
+            );
+        }
+
+        #[test]
+        fn generics_where_self() {
+            let trait_ = parse_quote!(
+                trait MyTrait<T>
+                where
+                    Self: Clone,
+                    T: Into<Self>,
+                {
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T, MT: MyTrait<T>> MyTrait<T> for std::boxed::Box<MT>
+                    where
+                        Self: Clone,
+                        T: Into<Self>,
+                        MT: Clone,
+                        T: Into<MT>,
+                    {
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_higher_ranked() {
+            let trait_ = parse_quote!(
+                trait MyTrait<F, G: for<'a> Fn(&'a u8)>
+                where
+                    F: for<'a> Fn(&'a str),
+                {
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            let expected: syn::Generics =
+                parse_quote!(<F, G: for<'a> Fn(&'a u8), MT: MyTrait<F, G>>);
+            assert_eq!(derived.generics.params, expected.params);
+            let expected: syn::WhereClause = parse_quote!(where F: for<'a> Fn(&'a str),);
+            assert_eq!(derived.generics.where_clause, Some(expected));
+        }
+
+        #[test]
+        fn generics_lifetime_return() {
+            let trait_ = parse_quote!(
+                trait Cursor<'a> {
+                    fn current(&self) -> &'a [u8];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, C: Cursor<'a>> Cursor<'a> for std::boxed::Box<C> {
+                        #[inline]
+                        fn current(&self) -> &'a [u8] {
+                            (*(*self)).current()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn lifetime_elided_parameter() {
+            // the elided output lifetime is tied to `&self` and not to the
+            // parameter, which only holds if no lifetime is added to the signature
+            let trait_ = parse_quote!(
+                trait Finder {
+                    fn find(&self, needle: &str) -> Option<&str>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<F: Finder> Finder for std::boxed::Box<F> {
+                        #[inline]
+                        fn find(&self, needle: &str) -> Option<&str> {
+                            (*(*self)).find(needle)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_lifetime() {
+            let trait_ = parse_quote!(
+                trait MyTrait<'a, 'b: 'a, T: 'static + Send> {}
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, 'b: 'a, T: 'static + Send, MT: MyTrait<'a, 'b, T>> MyTrait<'a, 'b, T>
+                        for std::boxed::Box<MT>
+                    {
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_associated_binding() {
+            let trait_ = parse_quote!(
+                trait Sink<T: Iterator<Item = u8> + Send, U: Into<Vec<u8>>> {
+                    fn send(&mut self, items: T, extra: U);
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Iterator<Item = u8> + Send, U: Into<Vec<u8>>, S: Sink<T, U>> Sink<T, U>
+                        for std::boxed::Box<S>
+                    {
+                        #[inline]
+                        fn send(&mut self, items: T, extra: U) {
+                            (*(*self)).send(items, extra)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics_const() {
+            let trait_ = parse_quote!(
+                trait MyTrait<'a, T: 'a, const N: usize> {
+                    type Chunk;
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+
+            assert_eq!(
+                derived,
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, T: 'a, const N: usize, MT: MyTrait<'a, T, N>> MyTrait<'a, T, N> for std::boxed::Box<MT> {
+                        type Chunk = <MT as MyTrait<'a, T, N>>::Chunk;
+                    }
                 )
             );
         }
@@ -0,0 +1,250 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::deref_expr;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarding_body;
+use crate::utils::is_object_safe;
+use crate::utils::is_sized_method;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+
+pub fn derive(trait_: &syn::ItemTrait, ufcs: bool) -> syn::Result<syn::ItemImpl> {
+    derive_with(trait_, ufcs, false)
+}
+
+pub fn derive_send_sync(trait_: &syn::ItemTrait, ufcs: bool) -> syn::Result<syn::ItemImpl> {
+    derive_with(trait_, ufcs, true)
+}
+
+fn derive_with(
+    trait_: &syn::ItemTrait,
+    ufcs: bool,
+    send_sync: bool,
+) -> syn::Result<syn::ItemImpl> {
+    let derive = if send_sync { "BoxDynSendSync" } else { "BoxDyn" };
+
+    // check the trait can be made into an object, reporting every item
+    // preventing it at once
+    is_object_safe(trait_, &format!("derive `{}`", derive))?;
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    for item in trait_.items.iter() {
+        if let syn::TraitItem::Method(ref m) = item {
+            // methods requiring `Self: Sized` are not part of the trait
+            // object, so they inherit their default implementation
+            if is_sized_method(&m.sig) {
+                continue;
+            }
+
+            // methods of an object safe trait may still take a receiver the
+            // trait object cannot be passed as
+            match m.sig.receiver() {
+                Some(syn::FnArg::Receiver(r)) if r.reference.is_none() => {
+                    let msg = format!(
+                        "cannot derive `{}` for a trait declaring `self` methods: cannot move out of `dyn {}`",
+                        derive, trait_.ident
+                    );
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                Some(syn::FnArg::Typed(pat)) => {
+                    let msg = format!(
+                        "cannot derive `{}` for a trait declaring methods with arbitrary receiver types",
+                        derive
+                    );
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
+            }
+
+            let mut call = signature_to_method_call(&m.sig)?;
+            *call.receiver = deref_expr(deref_expr(*call.receiver));
+
+            let signature = &m.sig;
+            let attrs = forwarded_attributes(&m.attrs);
+            let call = disambiguate_method_call(trait_, &m.sig, call, None, ufcs)?;
+            let body = forwarding_body(signature, call);
+            let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+            methods.push(item)
+        }
+    }
+
+    // build the generics for the impl block: since the implementation is
+    // for a trait object, we use the same generics as the trait itself
+    let trait_ident = &trait_.ident;
+    let trait_generics = &trait_.generics;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = &trait_.generics.where_clause;
+
+    // the thread-safe flavor requires the trait object to be `Send + Sync`
+    let bounds = if send_sync {
+        Some(quote::quote!(+ std::marker::Send + std::marker::Sync))
+    } else {
+        None
+    };
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #trait_generics #trait_ident #trait_generic_names for std::boxed::Box<dyn #trait_ident #trait_generic_names #bounds + '_> #where_clause {
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Trait for std::boxed::Box<dyn Trait + '_> {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_ref_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&self);
+                    fn my_method_mut(&mut self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Trait for std::boxed::Box<dyn Trait + '_> {
+                        #[inline]
+                        fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                        #[inline]
+                        fn my_method_mut(&mut self) {
+                            (*(*self)).my_method_mut()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn send_sync() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&self);
+                }
+            );
+            assert_eq!(
+                super::super::derive_send_sync(&trait_, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Trait for std::boxed::Box<dyn Trait + std::marker::Send + std::marker::Sync + '_> {
+                        #[inline]
+                        fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                    }
+                )
+            );
+
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            let err = super::super::derive_send_sync(&trait_, false).unwrap_err();
+            assert!(err.to_string().contains("`BoxDynSendSync`"));
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            assert!(super::super::derive(&trait_, false).is_err());
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            assert!(super::super::derive(&trait_, false).is_err());
+        }
+
+        #[test]
+        fn method_generic() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method<T>(&self, t: T);
+                }
+            );
+            assert!(super::super::derive(&trait_, false).is_err());
+        }
+
+        #[test]
+        fn method_sized() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_sized_method<T>(&self, t: T)
+                    where
+                        Self: Sized,
+                    {
+                    }
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Trait for std::boxed::Box<dyn Trait + '_> {}
+                )
+            );
+        }
+
+        #[test]
+        fn associated_const() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    const NAME: &'static str;
+                    fn my_method(&self);
+                }
+            );
+            let err = super::super::derive(&trait_, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `BoxDyn` for a trait declaring associated constants: `NAME` prevents the trait from being made into an object"
+            );
+        }
+
+        #[test]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait Trait<'a, T: 'static + Send> {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, T: 'static + Send> Trait<'a, T> for std::boxed::Box<dyn Trait<'a, T> + '_> {}
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::take_attribute;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let mut default = false;
+    for item in trait_.items.iter() {
+        if let syn::TraitItem::Method(ref m) = item {
+            if let Some(constness) = m.sig.constness {
+                let msg = "cannot derive `Cell` for a trait declaring `const fn` methods";
+                return Err(syn::Error::new(constness.span(), msg));
+            }
+
+            // the value is copied out of the cell for `&self` methods, so
+            // a reference returned by the forwarding call would not outlive it
+            if let syn::ReturnType::Type(_, ref ty) = m.sig.output {
+                if let syn::Type::Reference(_) = **ty {
+                    let msg =
+                        "cannot derive `Cell` for a trait declaring methods returning references";
+                    return Err(syn::Error::new(ty.span(), msg));
+                }
+            }
+
+            // `Cell` methods are called explicitly so that they are not
+            // shadowed by a trait method of the same name
+            let mut call = signature_to_method_call(&m.sig)?;
+            let taken = take_attribute(&m.attrs).is_some();
+            match m.sig.receiver() {
+                // fn()
+                None => {
+                    let msg = "cannot derive `Cell` for a trait declaring associated functions";
+                    return Err(syn::Error::new(m.sig.ident.span(), msg));
+                }
+                // `fn(&self)` with `#[blanket(as = "take")]`: the value is
+                // taken out of the cell, leaving the default value in its place
+                Some(syn::FnArg::Receiver(r))
+                    if taken && r.reference.is_some() && r.mutability.is_none() =>
+                {
+                    *call.receiver = parse_quote!(std::cell::Cell::take(self));
+                    default = true;
+                }
+                Some(receiver) if taken => {
+                    let msg = "cannot derive `Cell` for a trait declaring methods taken out of the cell without a `&self` receiver";
+                    return Err(syn::Error::new(receiver.span(), msg));
+                }
+                // `fn(self: Type)`
+                Some(syn::FnArg::Typed(pat)) => {
+                    let msg = "cannot derive `Cell` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                // `fn(&mut self)`
+                Some(syn::FnArg::Receiver(r)) if r.mutability.is_some() => {
+                    *call.receiver = parse_quote!(std::cell::Cell::get_mut(self));
+                }
+                // `fn(&self)`
+                Some(syn::FnArg::Receiver(r)) if r.reference.is_some() => {
+                    *call.receiver = parse_quote!(std::cell::Cell::get(self));
+                }
+                // `fn(self)`
+                Some(syn::FnArg::Receiver(_)) => {
+                    *call.receiver = parse_quote!(std::cell::Cell::into_inner(self));
+                }
+            }
+
+            let signature = &m.sig;
+            let attrs = forwarded_attributes(&m.attrs);
+            let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+            let mut body = forwarding_body(signature, call);
+            // wrap the value returned by methods returning `Self`, possibly
+            // in a `Result` or an `Option`
+            if returns_self(signature) {
+                body = parse_quote!(std::convert::From::from(#body));
+            } else if returns_wrapped_self(signature) {
+                body = parse_quote!((#body).map(std::convert::From::from));
+            }
+            let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+            methods.push(item)
+        }
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation, and which can be copied out of the cell, or
+    // taken out of it if needed
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if default {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + std::marker::Copy + std::default::Default)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + std::marker::Copy)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for std::cell::Cell<#generic_type> #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + std::marker::Copy> Trait for std::cell::Cell<T> {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_mix() {
+            let trait_ = parse_quote!(
+                trait Value {
+                    fn get(&self) -> u32;
+                    fn set(&mut self, value: u32);
+                    fn take(self) -> u32;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<V: Value + std::marker::Copy> Value for std::cell::Cell<V> {
+                        #[inline]
+                        fn get(&self) -> u32 {
+                            std::cell::Cell::get(self).get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u32) {
+                            std::cell::Cell::get_mut(self).set(value)
+                        }
+                        #[inline]
+                        fn take(self) -> u32 {
+                            std::cell::Cell::into_inner(self).take()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_take() {
+            let trait_ = parse_quote!(
+                trait Counter {
+                    fn count(&self) -> u32;
+                    #[blanket(as = "take")]
+                    fn flush(&self) -> u32;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<C: Counter + std::marker::Copy + std::default::Default> Counter
+                        for std::cell::Cell<C>
+                    {
+                        #[inline]
+                        fn count(&self) -> u32 {
+                            std::cell::Cell::get(self).count()
+                        }
+                        #[inline]
+                        fn flush(&self) -> u32 {
+                            std::cell::Cell::take(self).flush()
+                        }
+                    }
+                )
+            );
+
+            let trait_ = parse_quote!(
+                trait Counter {
+                    #[blanket(as = "take")]
+                    fn flush(&mut self) -> u32;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Value {
+                    fn doubled(&self) -> Self;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<V: Value + std::marker::Copy> Value for std::cell::Cell<V> {
+                        #[inline]
+                        fn doubled(&self) -> Self {
+                            std::convert::From::from(std::cell::Cell::get(self).doubled())
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_reference() {
+            let trait_ = parse_quote!(
+                trait Value {
+                    fn get(&self) -> &u32;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Cell` for a trait declaring methods returning references"
+            );
+        }
+
+        #[test]
+        fn receiver_arbitrary() {
+            let trait_ = parse_quote!(
+                trait Value {
+                    fn get(self: std::rc::Rc<Self>) -> u32;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+    }
+}
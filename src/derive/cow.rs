@@ -0,0 +1,196 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::deref_expr;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.mutability.is_some() => {
+                    let msg = "cannot derive `Cow` for a trait declaring `&mut self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = "cannot derive `Cow` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Typed(pat) => {
+                    let msg = "cannot derive `Cow` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
+            }
+        }
+
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `Cow` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+
+        // the borrowed view of the value is used whether it is borrowed
+        // or owned by the `Cow`
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = deref_expr(deref_expr(*call.receiver));
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation; `ToOwned` is required by `Cow` itself, but the
+    // owned type is never used since the methods only borrow the value
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized + std::borrow::ToOwned)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + std::borrow::ToOwned)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for std::borrow::Cow<'_, #generic_type> #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized + std::borrow::ToOwned> Trait for std::borrow::Cow<'_, T> {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_ref() {
+            let trait_ = parse_quote!(
+                trait Name {
+                    fn name(&self) -> &str;
+                    fn len(&self) -> usize;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<N: Name + ?Sized + std::borrow::ToOwned> Name for std::borrow::Cow<'_, N> {
+                        #[inline]
+                        fn name(&self) -> &str {
+                            (*(*self)).name()
+                        }
+                        #[inline]
+                        fn len(&self) -> usize {
+                            (*(*self)).len()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&mut self);
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Cow` for a trait declaring `&mut self` methods"
+            );
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Cow` for a trait declaring `self` methods"
+            );
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait Trait<'a, T> {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, T, T_: Trait<'a, T> + ?Sized + std::borrow::ToOwned> Trait<'a, T>
+                        for std::borrow::Cow<'_, T_>
+                    {
+                    }
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::deref_expr;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+use crate::utils::trait_to_pointer_ident;
+use crate::utils::via_adapter;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    let mut mutable = false;
+    for m in forwarded {
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = "cannot derive `Deref` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Receiver(r) if r.mutability.is_some() => {
+                    mutable = true;
+                }
+                syn::FnArg::Typed(pat) => {
+                    let msg = "cannot derive `Deref` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
+            }
+        }
+
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `Deref` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = deref_expr(deref_expr(*call.receiver));
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        // forward the method through the adapter given with `via = "..."`
+        if let Some(via) = via_adapter(&m.attrs, &m.sig)? {
+            *call.receiver = parse_quote!(self.#via());
+        }
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    // build an identifier for the pointer type dereferencing to the generic type
+    let trait_ident = &trait_.ident;
+    let pointer_type = trait_to_pointer_ident(trait_, &generic_type);
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation, and a pointer type dereferencing to it
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+
+    // `&mut self` methods can only be forwarded through a mutable dereference
+    let pointer_param = if mutable {
+        parse_quote!(#pointer_type: std::ops::Deref<Target = #generic_type> + std::ops::DerefMut)
+    } else {
+        parse_quote!(#pointer_type: std::ops::Deref<Target = #generic_type>)
+    };
+    let impl_generics = impl_generics(trait_, vec![generic_param, pointer_param]);
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for #pointer_type #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized, P: std::ops::Deref<Target = T>> Trait for P {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_ref() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized, P: std::ops::Deref<Target = T>> Trait for P {
+                        #[inline]
+                        fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&self);
+                    fn my_method_mut(&mut self);
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+            let expected: syn::Generics = parse_quote!(
+                <T: Trait + ?Sized, P: std::ops::Deref<Target = T> + std::ops::DerefMut>
+            );
+            assert_eq!(derived.generics, expected);
+            assert_eq!(derived.items.len(), 2);
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn receiver_arbitrary() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self: Box<Self>);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait Pointer<T> {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T, P: Pointer<T> + ?Sized, P_: std::ops::Deref<Target = P>> Pointer<T> for P_ {}
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::is_self_type;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::via_adapter;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    ty: &syn::Path,
+    field: &syn::Member,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    for item in trait_.items.iter() {
+        match item {
+            // the type of the field is unknown, so the associated items cannot
+            // be forwarded to the ones of its implementation
+            syn::TraitItem::Type(ref t) if t.default.is_none() => {
+                let msg = "cannot forward associated types to a field";
+                return Err(syn::Error::new(t.ident.span(), msg));
+            }
+            syn::TraitItem::Const(ref c) if c.default.is_none() => {
+                let msg = "cannot forward associated constants to a field";
+                return Err(syn::Error::new(c.ident.span(), msg));
+            }
+            // associated functions have no receiver to forward the call through,
+            // so only the ones with a default implementation are inherited
+            syn::TraitItem::Method(ref m) if m.sig.receiver().is_none() && m.default.is_none() => {
+                let msg = "cannot forward associated functions without a default implementation to a field";
+                return Err(syn::Error::new(m.sig.ident.span(), msg));
+            }
+            syn::TraitItem::Method(ref m) if m.sig.receiver().is_none() => (),
+            syn::TraitItem::Method(ref m) => {
+                if let Some(syn::FnArg::Typed(pat)) = m.sig.receiver() {
+                    let msg = "cannot forward methods with arbitrary receiver types to a field";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+                    let msg = "cannot forward methods returning `Self` to a field";
+                    return Err(syn::Error::new(m.sig.output.span(), msg));
+                }
+
+                // the field is borrowed or moved out of the receiver as needed
+                // by the method call, and out of the arguments typed after `Self`
+                let mut call = signature_to_method_call(&m.sig)?;
+                *call.receiver = parse_quote!(self.#field);
+                let inputs = m.sig.inputs.iter().skip(1);
+                for (input, arg) in inputs.zip(call.args.iter_mut()) {
+                    let ty = match input {
+                        syn::FnArg::Typed(pat) => &*pat.ty,
+                        syn::FnArg::Receiver(_) => continue,
+                    };
+                    match ty {
+                        syn::Type::Reference(r) if is_self_type(&r.elem) => {
+                            let mutability = &r.mutability;
+                            *arg = parse_quote!(&#mutability #arg.#field);
+                        }
+                        ty if is_self_type(ty) => *arg = parse_quote!(#arg.#field),
+                        _ => (),
+                    }
+                }
+
+                let signature = &m.sig;
+                let attrs = forwarded_attributes(&m.attrs);
+                // forward the method through the adapter given with `via = "..."`
+                if let Some(via) = via_adapter(&m.attrs, &m.sig)? {
+                    *call.receiver = parse_quote!(self.#via());
+                }
+                let call = disambiguate_method_call(trait_, &m.sig, call, None, ufcs)?;
+                let body = forwarding_body(signature, call);
+                let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+                methods.push(item)
+            }
+            _ => (),
+        }
+    }
+
+    // build the generics for the impl block: the implementing type is given,
+    // so only the generics of the trait itself are declared, along with its
+    // where clause which must hold for the implementing type as it is
+    let trait_ident = &trait_.ident;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let impl_generics = impl_generics(trait_, None);
+    let where_clause = &trait_.generics.where_clause;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for #ty #where_clause {
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(inner), false)
+                    .unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Trait for Wrapper {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_mix() {
+            let trait_ = parse_quote!(
+                trait Counter {
+                    fn get(&self) -> u32;
+                    fn increment(&mut self);
+                    fn into_count(self) -> u32;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(inner), false)
+                    .unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Counter for Wrapper {
+                        #[inline]
+                        fn get(&self) -> u32 {
+                            self.inner.get()
+                        }
+                        #[inline]
+                        fn increment(&mut self) {
+                            self.inner.increment()
+                        }
+                        #[inline]
+                        fn into_count(self) -> u32 {
+                            self.inner.into_count()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn argument_self() {
+            let trait_ = parse_quote!(
+                trait Merge {
+                    fn merge(&mut self, a: &Self, b: &mut Self, c: Self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(0), false)
+                    .unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl Merge for Wrapper {
+                        #[inline]
+                        fn merge(&mut self, a: &Self, b: &mut Self, c: Self) {
+                            self.0.merge(&a.0, &mut b.0, c.0)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait Sink<'a, T: 'a>
+                where
+                    T: Send,
+                {
+                    fn push(&mut self, item: &'a T);
+                }
+            );
+            let derived =
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(inner), false)
+                    .unwrap();
+            let expected: syn::ItemImpl = parse_quote!(
+                #[automatically_derived]
+                impl<'a, T: 'a> Sink<'a, T> for Wrapper
+                where
+                    T: Send,
+                {
+                    #[inline]
+                    fn push(&mut self, item: &'a T) {
+                        self.inner.push(item)
+                    }
+                }
+            );
+            assert_eq!(
+                crate::utils::pretty_print(&derived),
+                crate::utils::pretty_print(&expected)
+            );
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            let err =
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(inner), false)
+                    .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot forward methods returning `Self` to a field"
+            );
+        }
+
+        #[test]
+        fn associated_type() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    type Item;
+                }
+            );
+            let derived =
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(inner), false);
+            assert!(derived.is_err());
+        }
+
+        #[test]
+        fn receiver_arbitrary() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self: Box<Self>);
+                }
+            );
+            let derived =
+                super::super::derive(&trait_, &parse_quote!(Wrapper), &parse_quote!(inner), false);
+            assert!(derived.is_err());
+        }
+    }
+}
@@ -1,25 +1,103 @@
+mod arc_cow;
+mod arc_mutex;
+mod arc_rw_lock;
 mod r#box;
+mod box_dyn;
+mod cell;
+mod cow;
+mod deref;
+mod field;
 mod r#mut;
+mod pin_arc;
+mod pin_mut;
 mod rc;
+mod rc_ref_cell;
 mod r#ref;
+mod ref_box;
+mod ref_dyn;
+mod weak;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Derive {
     Box,
+    BoxDyn,
     Ref,
     Mut,
     Rc,
+    Deref,
+    RefDyn,
+    RcRefCell,
+    ArcMutex,
+    RcRefCellTry,
+    ArcMutexTry,
+    PinMut,
+    PinArc,
+    Cell,
+    ArcCow,
+    Weak,
+    BoxDynSendSync,
+    RefBox,
+    Cow,
+    ArcRwLock,
 }
 
 impl Derive {
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "Box" => Some(Derive::Box),
-            "Ref" => Some(Derive::Ref),
-            "Mut" => Some(Derive::Mut),
-            "Rc" => Some(Derive::Rc),
-            _ => None,
+    /// The name of each derive, in the order they are documented.
+    ///
+    /// This table is the only place where the derives are named, so that
+    /// parsing a derive with [`Derive::from_str`] and naming it with
+    /// [`Derive::as_str`] cannot get out of sync.
+    const NAMES: [(&'static str, Derive); 20] = [
+        ("Ref", Derive::Ref),
+        ("Rc", Derive::Rc),
+        ("Mut", Derive::Mut),
+        ("Box", Derive::Box),
+        ("Deref", Derive::Deref),
+        ("BoxDyn", Derive::BoxDyn),
+        ("BoxDynSendSync", Derive::BoxDynSendSync),
+        ("RefDyn", Derive::RefDyn),
+        ("RcRefCell", Derive::RcRefCell),
+        ("ArcMutex", Derive::ArcMutex),
+        ("RcRefCellTry", Derive::RcRefCellTry),
+        ("ArcMutexTry", Derive::ArcMutexTry),
+        ("PinMut", Derive::PinMut),
+        ("PinArc", Derive::PinArc),
+        ("Cell", Derive::Cell),
+        ("ArcCow", Derive::ArcCow),
+        ("Weak", Derive::Weak),
+        ("RefBox", Derive::RefBox),
+        ("Cow", Derive::Cow),
+        ("ArcRwLock", Derive::ArcRwLock),
+    ];
+
+    /// All the available derives, in the order they are documented.
+    pub const ALL: [Derive; 20] = {
+        let mut all = [Derive::Box; 20];
+        let mut i = 0;
+        while i < all.len() {
+            all[i] = Self::NAMES[i].1;
+            i += 1;
         }
+        all
+    };
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, d)| *d)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find(|(_, d)| d == self)
+            .map(|(name, _)| *name)
+            .expect("every derive is named in `Derive::NAMES`")
     }
 
     pub fn from_path(p: &syn::Path) -> Option<Self> {
@@ -28,12 +106,435 @@ impl Derive {
             .and_then(|s| Self::from_str(&s.ident.to_string()))
     }
 
+    /// Get the derive with the name closest to the given unknown name, if any.
+    pub fn suggest(s: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .map(|d| (crate::utils::edit_distance(s, d.as_str()), *d))
+            .filter(|(distance, d)| *distance <= std::cmp::max(1, d.as_str().len() / 3))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, d)| d)
+    }
+
+    /// Derive the implementation of a trait for this derive alone.
+    #[cfg(test)]
     pub fn defer_trait_methods(&self, trait_: &syn::ItemTrait) -> syn::Result<syn::ItemImpl> {
+        Self::defer_many(std::iter::once(self), trait_, None, false).remove(0)
+    }
+
+    /// Derive the implementations of a trait for each of the given derives.
+    ///
+    /// The method signatures are normalized once and shared by all derives,
+    /// rather than once per derive. The generic type of the implementations
+    /// is the one given with `generic = "..."`, if any, and the `ufcs` flag
+    /// forwards every method with a fully qualified call.
+    pub fn defer_many<'d, I>(
+        derives: I,
+        trait_: &syn::ItemTrait,
+        generic: Option<&syn::Ident>,
+        ufcs: bool,
+    ) -> Vec<syn::Result<syn::ItemImpl>>
+    where
+        I: IntoIterator<Item = &'d Derive>,
+    {
+        let trait_ = Self::normalize_trait_methods(trait_);
+        derives
+            .into_iter()
+            .map(|d| d.defer_normalized_trait_methods(&trait_, generic, ufcs))
+            .collect()
+    }
+
+    /// Derive the implementation of a trait for a type forwarding the trait
+    /// methods to one of its fields.
+    pub fn defer_field(
+        trait_: &syn::ItemTrait,
+        ty: &syn::Path,
+        field: &syn::Member,
+        ufcs: bool,
+    ) -> syn::Result<syn::ItemImpl> {
+        self::field::derive(&Self::normalize_trait_methods(trait_), ty, field, ufcs)
+    }
+
+    /// Rewrite the typed receivers equivalent to shorthand receivers, and
+    /// remove the `mut` bindings the forwarding methods do not need.
+    fn normalize_trait_methods(trait_: &syn::ItemTrait) -> syn::ItemTrait {
+        let mut trait_ = trait_.clone();
+        for item in trait_.items.iter_mut() {
+            if let syn::TraitItem::Method(ref mut m) = item {
+                crate::utils::normalize_receiver(&mut m.sig);
+                crate::utils::strip_argument_mutability(&mut m.sig);
+            }
+        }
+        trait_
+    }
+
+    fn defer_normalized_trait_methods(
+        &self,
+        trait_: &syn::ItemTrait,
+        generic: Option<&syn::Ident>,
+        ufcs: bool,
+    ) -> syn::Result<syn::ItemImpl> {
+        self.derive(trait_, generic, ufcs)
+            .map_err(|err| self.item_errors(trait_, generic, ufcs).unwrap_or(err))
+            .map_err(|err| self.annotate_errors(err))
+    }
+
+    /// Name the derive in the errors which do not already.
+    ///
+    /// The errors reported by the helpers shared by all derives, such as for
+    /// methods with an explicit ABI, would otherwise not say which of the
+    /// derives given to the attribute rejected the trait.
+    fn annotate_errors(&self, error: syn::Error) -> syn::Error {
+        let prefix = format!("cannot derive `{}`", self);
+        error
+            .into_iter()
+            .map(|err| {
+                let msg = err.to_string();
+                if msg.starts_with(&prefix) {
+                    err
+                } else {
+                    syn::Error::new(err.span(), format!("{} (in `derive({})`)", msg, self))
+                }
+            })
+            .reduce(|mut error, err| {
+                error.combine(err);
+                error
+            })
+            .expect("an error holds at least one message")
+    }
+
+    /// Collect the errors of the derive for each trait item separately.
+    ///
+    /// The derives stop at the first item they reject, so every item is
+    /// derived on its own to report each rejected item with its own span.
+    /// Errors which do not depend on the items of the trait are reported
+    /// only once.
+    fn item_errors(
+        &self,
+        trait_: &syn::ItemTrait,
+        generic: Option<&syn::Ident>,
+        ufcs: bool,
+    ) -> Option<syn::Error> {
+        let mut empty = trait_.clone();
+        empty.items.clear();
+        if let Err(err) = self.derive(&empty, generic, ufcs) {
+            return Some(err);
+        }
+
+        let mut errors = trait_.items.iter().filter_map(|item| {
+            let mut single = empty.clone();
+            single.items.push(item.clone());
+            self.derive(&single, generic, ufcs).err()
+        });
+        let mut error = errors.next()?;
+        errors.for_each(|err| error.combine(err));
+        Some(error)
+    }
+
+    fn derive(
+        &self,
+        trait_: &syn::ItemTrait,
+        generic: Option<&syn::Ident>,
+        ufcs: bool,
+    ) -> syn::Result<syn::ItemImpl> {
+        // associated functions have no receiver to forward the call through,
+        // so the derived implementations inherit the default implementation
+        // of those which have one; the trait object derives check them on
+        // their own, since they make the trait not object-safe
+        let trait_ = &match self {
+            Derive::BoxDyn | Derive::BoxDynSendSync | Derive::RefDyn => trait_.clone(),
+            _ => self.strip_associated_functions(trait_)?,
+        };
         match self {
-            Derive::Box => self::r#box::derive(trait_),
-            Derive::Ref => self::r#ref::derive(trait_),
-            Derive::Mut => self::r#mut::derive(trait_),
-            Derive::Rc => self::rc::derive(trait_),
+            Derive::Box => self::r#box::derive(trait_, generic, ufcs),
+            Derive::BoxDyn => self::box_dyn::derive(trait_, ufcs),
+            Derive::Ref => self::r#ref::derive(trait_, generic, ufcs),
+            Derive::Mut => self::r#mut::derive(trait_, generic, ufcs),
+            Derive::Rc => self::rc::derive(trait_, generic, ufcs),
+            Derive::Deref => self::deref::derive(trait_, generic, ufcs),
+            Derive::RefDyn => self::ref_dyn::derive(trait_, ufcs),
+            Derive::RcRefCell => self::rc_ref_cell::derive(trait_, generic, ufcs),
+            Derive::ArcMutex => self::arc_mutex::derive(trait_, generic, ufcs),
+            Derive::RcRefCellTry => self::rc_ref_cell::derive_try(trait_, generic, ufcs),
+            Derive::ArcMutexTry => self::arc_mutex::derive_try(trait_, generic, ufcs),
+            Derive::PinMut => self::pin_mut::derive(trait_, generic, ufcs),
+            Derive::PinArc => self::pin_arc::derive(trait_, generic, ufcs),
+            Derive::Cell => self::cell::derive(trait_, generic, ufcs),
+            Derive::ArcCow => self::arc_cow::derive(trait_, generic, ufcs),
+            Derive::Weak => self::weak::derive(trait_, generic, ufcs),
+            Derive::BoxDynSendSync => self::box_dyn::derive_send_sync(trait_, ufcs),
+            Derive::RefBox => self::ref_box::derive(trait_, generic, ufcs),
+            Derive::Cow => self::cow::derive(trait_, generic, ufcs),
+            Derive::ArcRwLock => self::arc_rw_lock::derive(trait_, generic, ufcs),
+        }
+    }
+}
+
+impl Derive {
+    /// Remove the associated functions with a default implementation from a
+    /// trait, and report the ones without one.
+    fn strip_associated_functions(&self, trait_: &syn::ItemTrait) -> syn::Result<syn::ItemTrait> {
+        let mut stripped = trait_.clone();
+        stripped.items.clear();
+        for item in trait_.items.iter() {
+            match item {
+                syn::TraitItem::Method(m) if m.sig.receiver().is_none() => {
+                    if m.default.is_none() {
+                        let msg = format!(
+                            "cannot derive `{}` for a trait declaring associated functions without a default implementation",
+                            self
+                        );
+                        return Err(syn::Error::new(m.sig.ident.span(), msg));
+                    }
+                }
+                _ => stripped.items.push(item.clone()),
+            }
+        }
+        Ok(stripped)
+    }
+}
+
+impl Display for Derive {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::Derive;
+
+    #[test]
+    fn as_str() {
+        for d in Derive::ALL.iter() {
+            assert_eq!(Derive::from_str(d.as_str()), Some(*d));
+            assert_eq!(d.to_string(), d.as_str());
+        }
+        assert_eq!(Derive::from_str("Arc"), None);
+        assert_eq!(Derive::from_str("box"), None);
+    }
+
+    #[test]
+    fn from_path() {
+        for d in Derive::ALL.iter() {
+            let path: syn::Path = syn::parse_str(d.as_str()).unwrap();
+            assert_eq!(Derive::from_path(&path), Some(*d));
+            // altered names must not be mistaken for another derive
+            for altered in [
+                d.as_str().to_lowercase(),
+                format!("{}s", d),
+                d.as_str()[1..].to_string(),
+            ]
+            .iter()
+            {
+                if let Ok(path) = syn::parse_str::<syn::Path>(altered) {
+                    assert_eq!(Derive::from_path(&path), Derive::from_str(altered));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn all() {
+        // the match fails to compile when a variant is added, as a reminder
+        // to name it in `Derive::NAMES` as well, which this test checks
+        let index = |d: &Derive| match d {
+            Derive::Box => 0,
+            Derive::BoxDyn => 1,
+            Derive::Ref => 2,
+            Derive::Mut => 3,
+            Derive::Rc => 4,
+            Derive::Deref => 5,
+            Derive::RefDyn => 6,
+            Derive::RcRefCell => 7,
+            Derive::ArcMutex => 8,
+            Derive::RcRefCellTry => 9,
+            Derive::ArcMutexTry => 10,
+            Derive::PinMut => 11,
+            Derive::PinArc => 12,
+            Derive::Cell => 13,
+            Derive::ArcCow => 14,
+            Derive::Weak => 15,
+            Derive::BoxDynSendSync => 16,
+            Derive::RefBox => 17,
+            Derive::Cow => 18,
+            Derive::ArcRwLock => 19,
+        };
+        let mut named = [false; 20];
+        for d in Derive::ALL.iter() {
+            assert!(!named[index(d)], "`{}` is named twice", d);
+            named[index(d)] = true;
+        }
+        assert!(named.iter().all(|named| *named));
+    }
+
+    #[test]
+    fn suggest() {
+        assert_eq!(Derive::suggest("Boxx"), Some(Derive::Box));
+        assert_eq!(Derive::suggest("box"), Some(Derive::Box));
+        assert_eq!(Derive::suggest("Deef"), Some(Derive::Deref));
+        assert_eq!(Derive::suggest("Arc"), None);
+        assert_eq!(Derive::suggest("Clone"), None);
+    }
+
+    #[test]
+    fn defer_trait_methods_associated_functions() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Named {
+                fn default_name() -> &'static str {
+                    "x"
+                }
+                fn name(&self) -> &str;
+            }
+        );
+        let expected: syn::ItemImpl = syn::parse_quote!(
+            #[automatically_derived]
+            impl<N: Named> Named for std::boxed::Box<N> {
+                #[inline]
+                fn name(&self) -> &str {
+                    (*(*self)).name()
+                }
+            }
+        );
+        assert_eq!(Derive::Box.defer_trait_methods(&trait_).unwrap(), expected);
+
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Make {
+                fn make() -> Self;
+            }
+        );
+        let err = Derive::Box.defer_trait_methods(&trait_).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot derive `Box` for a trait declaring associated functions without a default implementation"
+        );
+    }
+
+    #[test]
+    fn defer_trait_methods_errors() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Counter {
+                fn get(&self) -> u32;
+                fn increment(&mut self);
+                fn reset(self);
+            }
+        );
+        let err = Derive::Rc.defer_trait_methods(&trait_).unwrap_err();
+        let messages = err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "cannot derive `Rc` for a trait declaring `&mut self` methods",
+                "cannot derive `Rc` for a trait declaring `self` methods",
+            ]
+        );
+    }
+
+    #[test]
+    fn defer_many_errors() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Counter {
+                fn increment(&mut self);
+                extern "C" fn get(&self) -> u32;
+            }
+        );
+        let results = Derive::defer_many([Derive::Box, Derive::Rc].iter(), &trait_, None, false);
+        let messages = results
+            .into_iter()
+            .map(|result| {
+                let err = result.unwrap_err();
+                err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                vec!["cannot forward methods with an explicit ABI (in `derive(Box)`)"],
+                vec![
+                    "cannot derive `Rc` for a trait declaring `&mut self` methods",
+                    "cannot forward methods with an explicit ABI (in `derive(Rc)`)",
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn defer_trait_methods_pretty_print() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Counter {
+                type Count;
+                fn get(&self) -> Self::Count;
+                fn add<const N: usize>(&mut self, values: [u8; N]);
+            }
+        );
+        let derived = Derive::Box.defer_trait_methods(&trait_).unwrap();
+        assert_eq!(
+            crate::utils::pretty_print(&derived),
+            r#"#[automatically_derived]
+impl<C: Counter> Counter for std::boxed::Box<C> {
+    type Count = <C as Counter>::Count;
+    #[inline]
+    fn get(&self) -> Self::Count {
+        (*(*self)).get()
+    }
+    #[inline]
+    fn add<const N: usize>(&mut self, values: [u8; N]) {
+        (*(*self)).add::<N>(values)
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn defer_many() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Counter {
+                fn get(&self) -> u8;
+                fn add(&self, mut value: u8);
+            }
+        );
+        let derives = [Derive::Box, Derive::Rc, Derive::Ref];
+        let derived = Derive::defer_many(derives.iter(), &trait_, None, false);
+        assert_eq!(derived.len(), 3);
+        for (d, item) in derives.iter().zip(derived) {
+            assert_eq!(item.unwrap(), d.defer_trait_methods(&trait_).unwrap());
+        }
+        let self_types = Derive::defer_many(derives.iter(), &trait_, None, false)
+            .into_iter()
+            .map(|item| crate::utils::type_to_string(&item.unwrap().self_ty))
+            .collect::<Vec<_>>();
+        assert_eq!(self_types, ["std::boxed::Box<C>", "std::rc::Rc<C>", "&C"]);
+    }
+
+    #[test]
+    fn defer_trait_methods_typed_receivers() {
+        let typed: syn::ItemTrait = syn::parse_quote!(
+            trait Trait {
+                fn read(self: &Self) -> u8;
+                fn write(self: &mut Self, value: u8);
+                fn consume(self: Self);
+            }
+        );
+        let shorthand: syn::ItemTrait = syn::parse_quote!(
+            trait Trait {
+                fn read(&self) -> u8;
+                fn write(&mut self, value: u8);
+                fn consume(self);
+            }
+        );
+        for d in Derive::ALL.iter() {
+            match (
+                d.defer_trait_methods(&typed),
+                d.defer_trait_methods(&shorthand),
+            ) {
+                (Ok(typed), Ok(shorthand)) => assert_eq!(typed, shorthand),
+                (Err(typed), Err(shorthand)) => {
+                    assert_eq!(typed.to_string(), shorthand.to_string())
+                }
+                _ => panic!("different results for the `{}` derive", d),
+            }
         }
     }
 }
@@ -2,61 +2,89 @@ use syn::parse_quote;
 use syn::spanned::Spanned;
 
 use crate::utils::deref_expr;
-use crate::utils::generics_declaration_to_generics;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
 use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
 use crate::utils::trait_to_generic_ident;
+use crate::utils::via_adapter;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
 
-pub fn derive(trait_: &syn::ItemTrait) -> syn::Result<syn::ItemImpl> {
     // build the methods
     let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
-    for item in trait_.items.iter() {
-        if let syn::TraitItem::Method(ref m) = item {
-            if let Some(receiver) = m.sig.receiver() {
-                match receiver {
-                    syn::FnArg::Receiver(r) if r.reference.is_none() => {
-                        let msg = "cannot derive `Mut` for a trait declaring `self` methods";
-                        return Err(syn::Error::new(r.span(), msg));
-                    }
-                    syn::FnArg::Typed(pat) => {
-                        let msg = "cannot derive `Mut` for a trait declaring methods with arbitrary receiver types";
-                        return Err(syn::Error::new(pat.span(), msg));
-                    }
-                    _ => (),
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = "cannot derive `Mut` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Typed(pat) => {
+                    let msg = "cannot derive `Mut` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
                 }
+                _ => (),
             }
+        }
 
-            let mut call = signature_to_method_call(&m.sig)?;
-            call.receiver = Box::new(deref_expr(deref_expr(*call.receiver)));
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `Mut` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = deref_expr(deref_expr(*call.receiver));
 
-            let signature = &m.sig;
-            let item = parse_quote!(#[inline] #signature { #call });
-            methods.push(item)
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        // forward the method through the adapter given with `via = "..."`
+        if let Some(via) = via_adapter(&m.attrs, &m.sig)? {
+            *call.receiver = parse_quote!(self.#via());
         }
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
     }
 
-    // build an identifier for the generic type used for the implementation
     let trait_ident = &trait_.ident;
-    let generic_type = trait_to_generic_ident(&trait_);
 
     // build the generics for the impl block:
     // we use the same generics as the trait itself, plus
     // a generic type that implements the trait for which we provide the
     // blanket implementation
-    let trait_generics = &trait_.generics;
-    let where_clause = &trait_.generics.where_clause;
-    let mut impl_generics = trait_generics.clone();
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
 
-    // we must however remove the generic type bounds, to avoid repeating them
-    let mut trait_generic_names = trait_generics.clone();
-    trait_generic_names.params = generics_declaration_to_generics(&trait_generics.params)?;
-
-    impl_generics.params.push(syn::GenericParam::Type(
-        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized),
-    ));
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
 
     Ok(parse_quote!(
         #[automatically_derived]
         impl #impl_generics #trait_ident #trait_generic_names for &mut #generic_type #where_clause {
+            #(#types)*
             #(#methods)*
         }
     ))
@@ -73,7 +101,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
             assert_eq!(
                 derived,
                 parse_quote!(
@@ -83,6 +111,27 @@ mod tests {
             );
         }
 
+        #[test]
+        fn receiver_ref() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    fn my_method(&self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<MT: MyTrait + ?Sized> MyTrait for &mut MT {
+                        #[inline]
+                        fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                    }
+                )
+            );
+        }
+
         #[test]
         fn receiver_mut() {
             let trait_ = parse_quote!(
@@ -91,7 +140,7 @@ mod tests {
                 }
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
                     impl<MT: MyTrait + ?Sized> MyTrait for &mut MT {
@@ -104,6 +153,67 @@ mod tests {
             );
         }
 
+        #[test]
+        fn return_trait_lifetime() {
+            let trait_ = parse_quote!(
+                trait Writer<'a> {
+                    fn buffer(&mut self) -> &'a mut [u8];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, W: Writer<'a> + ?Sized> Writer<'a> for &mut W {
+                        #[inline]
+                        fn buffer(&mut self) -> &'a mut [u8] {
+                            (*(*self)).buffer()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_elided_lifetime() {
+            let trait_ = parse_quote!(
+                trait Named {
+                    fn name(&self) -> &str;
+                    fn as_bytes(&self) -> &[u8];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<N: Named + ?Sized> Named for &mut N {
+                        #[inline]
+                        fn name(&self) -> &str {
+                            (*(*self)).name()
+                        }
+                        #[inline]
+                        fn as_bytes(&self) -> &[u8] {
+                            (*(*self)).as_bytes()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Mut` for a trait declaring methods returning `Self`"
+            );
+        }
+
         #[test]
         fn receiver_self() {
             let trait_ = parse_quote!(
@@ -111,7 +221,32 @@ mod tests {
                     fn my_method(self);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Mut` for a trait declaring `self` methods"
+            );
+        }
+
+        #[test]
+        fn method_const() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    const fn my_method(&self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for &mut T {
+                        #[inline]
+                        const fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                    }
+                )
+            );
         }
 
         #[test]
@@ -121,7 +256,11 @@ mod tests {
                     fn my_method(self: Box<Self>);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Mut` for a trait declaring methods with arbitrary receiver types"
+            );
         }
 
         #[test]
@@ -129,7 +268,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait Trait<T> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -145,7 +284,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait Trait<T: 'static + Send> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -161,7 +300,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait Trait<'a, 'b: 'a, T: 'static + Send> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -0,0 +1,181 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::is_pin_mut_receiver;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(constness) = m.sig.constness {
+            let msg = "cannot derive `PinMut` for a trait declaring `const fn` methods";
+            return Err(syn::Error::new(constness.span(), msg));
+        }
+
+        // only pinned receivers can be projected through the pin, since
+        // the pinned value cannot be moved out nor borrowed without it
+        match m.sig.receiver() {
+            Some(receiver) if is_pin_mut_receiver(receiver) => (),
+            Some(syn::FnArg::Receiver(r)) => {
+                let msg = "cannot derive `PinMut` for a trait declaring `&self`, `&mut self` or `self` methods, which cannot be forwarded through a pin";
+                return Err(syn::Error::new(r.span(), msg));
+            }
+            Some(syn::FnArg::Typed(pat)) => {
+                let msg = "cannot derive `PinMut` for a trait declaring methods with arbitrary receiver types";
+                return Err(syn::Error::new(pat.span(), msg));
+            }
+            None => {
+                let msg = "cannot derive `PinMut` for a trait declaring associated functions";
+                return Err(syn::Error::new(m.sig.ident.span(), msg));
+            }
+        }
+
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `PinMut` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+
+        // the receiver is a `Pin<&mut Pin<&mut T>>`, which is reborrowed
+        // as a `Pin<&mut T>`: the functions are called explicitly so that
+        // they are not shadowed by a trait method of the same name
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = parse_quote!(std::pin::Pin::as_mut(std::pin::Pin::get_mut(self)));
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for std::pin::Pin<&mut #generic_type> #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::pin::Pin<&mut T> {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_pin_mut() {
+            let trait_ = parse_quote!(
+                trait Sink {
+                    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<S: Sink + ?Sized> Sink for std::pin::Pin<&mut S> {
+                        #[inline]
+                        fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+                            std::pin::Pin::as_mut(std::pin::Pin::get_mut(self)).poll_ready(cx)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_ref() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn receiver_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&mut self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn receiver_arbitrary() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self: Box<Self>);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+    }
+}
@@ -2,65 +2,115 @@ use syn::parse_quote;
 use syn::spanned::Spanned;
 
 use crate::utils::deref_expr;
-use crate::utils::generics_declaration_to_generics;
+use crate::utils::deref_self_arguments;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::is_pointer_receiver;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
 use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
 use crate::utils::trait_to_generic_ident;
+use crate::utils::via_adapter;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
 
-pub fn derive(trait_: &syn::ItemTrait) -> syn::Result<syn::ItemImpl> {
     // build the methods
     let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
-    for item in trait_.items.iter() {
-        if let syn::TraitItem::Method(ref m) = item {
-            if let Some(receiver) = m.sig.receiver() {
-                match receiver {
-                    syn::FnArg::Receiver(r) if r.mutability.is_some() => {
-                        let msg = "cannot derive `Rc` for a trait declaring `&mut self` methods";
-                        return Err(syn::Error::new(r.span(), msg));
-                    }
-                    syn::FnArg::Receiver(r) if r.reference.is_none() => {
-                        let msg = "cannot derive `Rc` for a trait declaring `self` methods";
-                        return Err(syn::Error::new(r.span(), msg));
-                    }
-                    syn::FnArg::Typed(pat) => {
-                        let msg = "cannot derive `Rc` for a trait declaring methods with arbitrary receiver types";
-                        return Err(syn::Error::new(pat.span(), msg));
-                    }
-                    _ => (),
+    let (forwarded, mut unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(constness) = m.sig.constness {
+            let msg = "cannot derive `Rc` for a trait declaring `const fn` methods";
+            return Err(syn::Error::new(constness.span(), msg));
+        }
+
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.mutability.is_some() => {
+                    let msg = "cannot derive `Rc` for a trait declaring `&mut self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
                 }
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = "cannot derive `Rc` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Typed(_) if is_pointer_receiver(receiver, "Rc") => (),
+                syn::FnArg::Typed(pat) => {
+                    let msg = "cannot derive `Rc` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
             }
+        }
 
-            let mut call = signature_to_method_call(&m.sig)?;
-            call.receiver = Box::new(deref_expr(deref_expr(*call.receiver)));
+        let mut call = signature_to_method_call(&m.sig)?;
+        match m.sig.receiver() {
+            // `fn(self: Rc<Self>)`: the receiver is an `Rc` of the wrapper
+            // type, so the wrapper itself is cloned out of it
+            Some(receiver) if is_pointer_receiver(receiver, "Rc") => {
+                *call.receiver = parse_quote!(std::rc::Rc::clone(&*self));
+            }
+            _ => *call.receiver = deref_expr(deref_expr(*call.receiver)),
+        }
+        // pass the wrapped values of the arguments typed after `Self`
+        deref_self_arguments(&m.sig, &mut call, "Rc", false)?;
 
-            let signature = &m.sig;
-            let item = parse_quote!(#[inline] #signature { #call });
-            methods.push(item)
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        // forward the method through the adapter given with `via = "..."`
+        if let Some(via) = via_adapter(&m.attrs, &m.sig)? {
+            *call.receiver = parse_quote!(self.#via());
+        }
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let mut body = forwarding_body(signature, call);
+        // wrap the value returned by methods returning `Self`, possibly
+        // in a `Result` or an `Option`, which can only be done for a sized
+        // generic type
+        if returns_self(signature) {
+            body = parse_quote!(std::convert::From::from(#body));
+            unsized_ = false;
+        } else if returns_wrapped_self(signature) {
+            body = parse_quote!((#body).map(std::convert::From::from));
+            unsized_ = false;
         }
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
     }
 
-    // build an identifier for the generic type used for the implementation
     let trait_ident = &trait_.ident;
-    let generic_type = trait_to_generic_ident(&trait_);
 
     // build the generics for the impl block:
     // we use the same generics as the trait itself, plus
     // a generic type that implements the trait for which we provide the
     // blanket implementation
-    let trait_generics = &trait_.generics;
-    let where_clause = &trait_.generics.where_clause;
-    let mut impl_generics = trait_generics.clone();
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
 
-    // we must however remove the generic type bounds, to avoid repeating them
-    let mut trait_generic_names = trait_generics.clone();
-    trait_generic_names.params = generics_declaration_to_generics(&trait_generics.params)?;
-
-    impl_generics.params.push(syn::GenericParam::Type(
-        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized),
-    ));
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
 
     Ok(parse_quote!(
         #[automatically_derived]
         impl #impl_generics #trait_ident #trait_generic_names for std::rc::Rc<#generic_type> #where_clause {
+            #(#types)*
             #(#methods)*
         }
     ))
@@ -78,7 +128,7 @@ mod tests {
                 trait Trait {}
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
                     impl<T: Trait + ?Sized> Trait for std::rc::Rc<T> {}
@@ -94,7 +144,7 @@ mod tests {
                 }
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
                     impl<T: Trait + ?Sized> Trait for std::rc::Rc<T> {
@@ -114,7 +164,7 @@ mod tests {
                     fn my_method(&mut self);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            assert!(super::super::derive(&trait_, None, false).is_err());
         }
 
         #[test]
@@ -124,7 +174,139 @@ mod tests {
                     fn my_method(self);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn method_const() {
+            let trait_ = parse_quote!(
+                trait MyTrait {
+                    const fn my_method(&self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn method_sized() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(&self);
+                    fn my_sized_method(&self)
+                    where
+                        Self: Sized,
+                    {
+                    }
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::rc::Rc<T> {
+                        #[inline]
+                        fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_sized_required() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_sized_method(&self)
+                    where
+                        Self: Sized;
+                }
+            );
+            let derived = super::super::derive(&trait_, None, false).unwrap();
+            assert_eq!(derived.generics, parse_quote!(<T: Trait>));
+            assert_eq!(derived.items.len(), 1);
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait> Trait for std::rc::Rc<T> {
+                        #[inline]
+                        fn duplicate(&self) -> Self {
+                            std::convert::From::from((*(*self)).duplicate())
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn method_generic() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn visit<V: Debug>(&self, value: V);
+                    fn visit_sized<V: Debug>(&self, value: V)
+                    where
+                        Self: Sized,
+                    {
+                    }
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::rc::Rc<T> {
+                        #[inline]
+                        fn visit<V: Debug>(&self, value: V) {
+                            (*(*self)).visit::<V>(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn argument_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn absorb(&self, other: Self);
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Rc` for a trait declaring methods with `Self` arguments"
+            );
+        }
+
+        #[test]
+        fn receiver_rc() {
+            let trait_ = parse_quote!(
+                trait Task {
+                    fn spawn(self: std::rc::Rc<Self>, delay: u32);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Task + ?Sized> Task for std::rc::Rc<T> {
+                        #[inline]
+                        fn spawn(self: std::rc::Rc<Self>, delay: u32) {
+                            std::rc::Rc::clone(&*self).spawn(delay)
+                        }
+                    }
+                )
+            );
         }
 
         #[test]
@@ -134,7 +316,7 @@ mod tests {
                     fn my_method(self: Box<Self>);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            assert!(super::super::derive(&trait_, None, false).is_err());
         }
 
         #[test]
@@ -142,7 +324,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait<T> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -158,7 +340,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait<T: 'static + Send> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -174,7 +356,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait<'a, 'b: 'a, T: 'static + Send> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
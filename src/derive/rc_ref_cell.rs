@@ -0,0 +1,296 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::borrowed_return;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_result;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    derive_with(trait_, generic, ufcs, false)
+}
+
+pub fn derive_try(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    derive_with(trait_, generic, ufcs, true)
+}
+
+fn derive_with(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+    fallible: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    let derive = if fallible {
+        "RcRefCellTry"
+    } else {
+        "RcRefCell"
+    };
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(constness) = m.sig.constness {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring `const fn` methods",
+                derive
+            );
+            return Err(syn::Error::new(constness.span(), msg));
+        }
+
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = format!(
+                        "cannot derive `{}` for a trait declaring `self` methods",
+                        derive
+                    );
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Typed(pat) => {
+                    let msg = format!("cannot derive `{}` for a trait declaring methods with arbitrary receiver types", derive);
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
+            }
+        }
+
+        // the runtime borrow only lives as long as the forwarding call,
+        // so neither `Self` nor a borrow can be returned from it
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring methods returning `Self`",
+                derive
+            );
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+        if let Some(span) = borrowed_return(&m.sig) {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring methods returning references borrowed from `self`, which cannot outlive the borrow of the `RefCell` in the forwarding method",
+                derive
+            );
+            return Err(syn::Error::new(span, msg));
+        }
+
+        // the fallible flavor reports a failed borrow through the error
+        // type of the method, so every method must return a `Result`
+        if fallible && !returns_result(&m.sig) {
+            let msg = format!(
+                "cannot derive `{}` for a trait declaring methods not returning `Result`",
+                derive
+            );
+            let span = match m.sig.output {
+                syn::ReturnType::Type(_, ref ty) => ty.span(),
+                syn::ReturnType::Default => m.sig.ident.span(),
+            };
+            return Err(syn::Error::new(span, msg));
+        }
+
+        // every method goes through a mutable borrow of the cell, and
+        // `RefCell::borrow_mut` is called explicitly so that it is not
+        // shadowed by a trait method of the same name
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = if fallible {
+            parse_quote!(std::cell::RefCell::try_borrow_mut(self)?)
+        } else {
+            parse_quote!(std::cell::RefCell::borrow_mut(self))
+        };
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for std::rc::Rc<std::cell::RefCell<#generic_type>> #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::rc::Rc<std::cell::RefCell<T>> {}
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_ref_mut() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> u8;
+                    fn set(&mut self, value: u8);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::rc::Rc<std::cell::RefCell<T>> {
+                        #[inline]
+                        fn get(&self) -> u8 {
+                            std::cell::RefCell::borrow_mut(self).get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u8) {
+                            std::cell::RefCell::borrow_mut(self).set(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn receiver_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn my_method(self);
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+
+        #[test]
+        fn return_reference() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn name(&self) -> &str;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `RcRefCell` for a trait declaring methods returning references borrowed from `self`, which cannot outlive the borrow of the `RefCell` in the forwarding method"
+            );
+
+            let trait_ = parse_quote!(
+                trait Trait<'a> {
+                    fn name(&self) -> Option<&str>;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+
+            let trait_ = parse_quote!(
+                trait Trait<'a> {
+                    fn name(&self) -> &'static str;
+                    fn label(&self) -> &'a str;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_ok());
+        }
+
+        #[test]
+        fn generics() {
+            let trait_ = parse_quote!(
+                trait MyTrait<T> {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T, MT: MyTrait<T> + ?Sized> MyTrait<T> for std::rc::Rc<std::cell::RefCell<MT>> {}
+                )
+            );
+        }
+
+        #[test]
+        fn try_result() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> Result<u8, Error>;
+                    fn set(&mut self, value: u8) -> Result<(), Error>;
+                }
+            );
+            assert_eq!(
+                super::super::derive_try(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::rc::Rc<std::cell::RefCell<T>> {
+                        #[inline]
+                        fn get(&self) -> Result<u8, Error> {
+                            std::cell::RefCell::try_borrow_mut(self)?.get()
+                        }
+                        #[inline]
+                        fn set(&mut self, value: u8) -> Result<(), Error> {
+                            std::cell::RefCell::try_borrow_mut(self)?.set(value)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn try_not_result() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn get(&self) -> Result<u8, Error>;
+                    fn set(&mut self, value: u8);
+                }
+            );
+            let err = super::super::derive_try(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `RcRefCellTry` for a trait declaring methods not returning `Result`"
+            );
+        }
+    }
+}
@@ -2,65 +2,93 @@ use syn::parse_quote;
 use syn::spanned::Spanned;
 
 use crate::utils::deref_expr;
-use crate::utils::generics_declaration_to_generics;
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_self;
+use crate::utils::returns_wrapped_self;
 use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
 use crate::utils::trait_to_generic_ident;
+use crate::utils::via_adapter;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
 
-pub fn derive(trait_: &syn::ItemTrait) -> syn::Result<syn::ItemImpl> {
     // build the methods
     let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
-    for item in trait_.items.iter() {
-        if let syn::TraitItem::Method(ref m) = item {
-            if let Some(receiver) = m.sig.receiver() {
-                match receiver {
-                    syn::FnArg::Receiver(r) if r.mutability.is_some() => {
-                        let msg = "cannot derive `Ref` for a trait declaring `&mut self` methods";
-                        return Err(syn::Error::new(r.span(), msg));
-                    }
-                    syn::FnArg::Receiver(r) if r.reference.is_none() => {
-                        let msg = "cannot derive `Ref` for a trait declaring `self` methods";
-                        return Err(syn::Error::new(r.span(), msg));
-                    }
-                    syn::FnArg::Typed(pat) => {
-                        let msg = "cannot derive `Ref` for a trait declaring methods with arbitrary receiver types";
-                        return Err(syn::Error::new(pat.span(), msg));
-                    }
-                    _ => (),
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.mutability.is_some() => {
+                    let msg = "cannot derive `Ref` for a trait declaring `&mut self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = "cannot derive `Ref` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
                 }
+                syn::FnArg::Typed(pat) => {
+                    let msg = "cannot derive `Ref` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
             }
+        }
 
-            let mut call = signature_to_method_call(&m.sig)?;
-            call.receiver = Box::new(deref_expr(deref_expr(*call.receiver)));
+        if returns_self(&m.sig) || returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `Ref` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = deref_expr(deref_expr(*call.receiver));
 
-            let signature = &m.sig;
-            let item = parse_quote!(#[inline] #signature { #call });
-            methods.push(item)
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        // forward the method through the adapter given with `via = "..."`
+        if let Some(via) = via_adapter(&m.attrs, &m.sig)? {
+            *call.receiver = parse_quote!(self.#via());
         }
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
     }
 
-    // build an identifier for the generic type used for the implementation
     let trait_ident = &trait_.ident;
-    let generic_type = trait_to_generic_ident(&trait_);
 
     // build the generics for the impl block:
     // we use the same generics as the trait itself, plus
     // a generic type that implements the trait for which we provide the
     // blanket implementation
-    let trait_generics = &trait_.generics;
-    let where_clause = &trait_.generics.where_clause;
-    let mut impl_generics = trait_generics.clone();
-
-    // we must however remove the generic type bounds, to avoid repeating them
-    let mut trait_generic_names = trait_generics.clone();
-    trait_generic_names.params = generics_declaration_to_generics(&trait_generics.params)?;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
 
-    impl_generics.params.push(syn::GenericParam::Type(
-        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized),
-    ));
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
 
     Ok(parse_quote!(
         #[automatically_derived]
         impl #impl_generics #trait_ident #trait_generic_names for &#generic_type #where_clause {
+            #(#types)*
             #(#methods)*
         }
     ))
@@ -78,7 +106,7 @@ mod tests {
                 trait Trait {}
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
                     impl<T: Trait + ?Sized> Trait for &T {}
@@ -94,7 +122,7 @@ mod tests {
                 }
             );
             assert_eq!(
-                super::super::derive(&trait_).unwrap(),
+                super::super::derive(&trait_, None, false).unwrap(),
                 parse_quote!(
                     #[automatically_derived]
                     impl<T: Trait + ?Sized> Trait for &T {
@@ -107,6 +135,48 @@ mod tests {
             );
         }
 
+        #[test]
+        fn method_const_generic_array() {
+            let trait_ = parse_quote!(
+                trait Snapshot {
+                    fn snapshot<const N: usize>(&self) -> [u8; N];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<S: Snapshot + ?Sized> Snapshot for &S {
+                        #[inline]
+                        fn snapshot<const N: usize>(&self) -> [u8; N] {
+                            (*(*self)).snapshot::<N>()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_ref_dyn() {
+            let trait_ = parse_quote!(
+                trait Service {
+                    fn as_logger(&self) -> &dyn Logger;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<S: Service + ?Sized> Service for &S {
+                        #[inline]
+                        fn as_logger(&self) -> &dyn Logger {
+                            (*(*self)).as_logger()
+                        }
+                    }
+                )
+            );
+        }
+
         #[test]
         fn receiver_mut() {
             let trait_ = parse_quote!(
@@ -114,7 +184,35 @@ mod tests {
                     fn my_method(&mut self);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Ref` for a trait declaring `&mut self` methods"
+            );
+        }
+
+        #[test]
+        fn return_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn duplicate(&self) -> Self;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Ref` for a trait declaring methods returning `Self`"
+            );
+        }
+
+        #[test]
+        fn return_wrapped_self() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    fn next(&self) -> Option<Self>;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
         }
 
         #[test]
@@ -124,7 +222,32 @@ mod tests {
                     fn my_method(self);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Ref` for a trait declaring `self` methods"
+            );
+        }
+
+        #[test]
+        fn method_const() {
+            let trait_ = parse_quote!(
+                trait Trait {
+                    const fn my_method(&self);
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for &T {
+                        #[inline]
+                        const fn my_method(&self) {
+                            (*(*self)).my_method()
+                        }
+                    }
+                )
+            );
         }
 
         #[test]
@@ -134,7 +257,11 @@ mod tests {
                     fn my_method(self: Box<Self>);
                 }
             );
-            assert!(super::super::derive(&trait_).is_err());
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Ref` for a trait declaring methods with arbitrary receiver types"
+            );
         }
 
         #[test]
@@ -142,7 +269,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait<T> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -158,7 +285,7 @@ mod tests {
             let trait_ = parse_quote!(
                 trait MyTrait<T: 'static + Send> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -169,12 +296,82 @@ mod tests {
             );
         }
 
+        #[test]
+        fn generics_lifetime_const() {
+            let trait_ = parse_quote!(
+                trait Frame<'a, const N: usize> {
+                    fn data(&self) -> &'a [u8; N];
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<'a, const N: usize, F: Frame<'a, N> + ?Sized> Frame<'a, N> for &F {
+                        #[inline]
+                        fn data(&self) -> &'a [u8; N] {
+                            (*(*self)).data()
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn lifetime_elided_parameter() {
+            // the elided output lifetime is tied to `&self` and not to the
+            // parameter, which only holds if no lifetime is added to the signature
+            let trait_ = parse_quote!(
+                trait Finder {
+                    fn find(&self, needle: &str) -> Option<&str>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<F: Finder + ?Sized> Finder for &F {
+                        #[inline]
+                        fn find(&self, needle: &str) -> Option<&str> {
+                            (*(*self)).find(needle)
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_impl_trait_captures() {
+            let trait_ = parse_quote!(
+                trait Source {
+                    fn stream(&self) -> impl Iterator<Item = &u8> + '_;
+                    fn windows<'s>(&'s self, size: usize) -> impl Iterator<Item = &'s [u8]> + 's;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<S: Source + ?Sized> Source for &S {
+                        #[inline]
+                        fn stream(&self) -> impl Iterator<Item = &u8> + '_ {
+                            (*(*self)).stream()
+                        }
+                        #[inline]
+                        fn windows<'s>(&'s self, size: usize) -> impl Iterator<Item = &'s [u8]> + 's {
+                            (*(*self)).windows(size)
+                        }
+                    }
+                )
+            );
+        }
+
         #[test]
         fn generics_lifetime() {
             let trait_ = parse_quote!(
                 trait MyTrait<'a, 'b: 'a, T: 'static + Send> {}
             );
-            let derived = super::super::derive(&trait_).unwrap();
+            let derived = super::super::derive(&trait_, None, false).unwrap();
 
             assert_eq!(
                 derived,
@@ -0,0 +1,207 @@
+use syn::parse_quote;
+use syn::spanned::Spanned;
+
+use crate::utils::disambiguate_method_call;
+use crate::utils::forwarded_associated_items;
+use crate::utils::forwarded_attributes;
+use crate::utils::forwarded_methods;
+use crate::utils::forwarding_body;
+use crate::utils::impl_generics;
+use crate::utils::impl_where_clause;
+use crate::utils::returns_option;
+use crate::utils::returns_wrapped_self;
+use crate::utils::signature_to_method_call;
+use crate::utils::trait_generic_names;
+use crate::utils::trait_to_generic_ident;
+
+pub fn derive(
+    trait_: &syn::ItemTrait,
+    generic: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::ItemImpl> {
+    // build an identifier for the generic type used for the implementation
+    let generic_type = trait_to_generic_ident(trait_, generic);
+
+    // build the methods
+    let mut methods: Vec<syn::ImplItemMethod> = Vec::new();
+    let (forwarded, unsized_) = forwarded_methods(trait_);
+    for m in forwarded {
+        if let Some(constness) = m.sig.constness {
+            let msg = "cannot derive `Weak` for a trait declaring `const fn` methods";
+            return Err(syn::Error::new(constness.span(), msg));
+        }
+        if let Some(asyncness) = m.sig.asyncness {
+            let msg = "cannot derive `Weak` for a trait declaring `async fn` methods";
+            return Err(syn::Error::new(asyncness.span(), msg));
+        }
+
+        if let Some(receiver) = m.sig.receiver() {
+            match receiver {
+                syn::FnArg::Receiver(r) if r.mutability.is_some() => {
+                    let msg = "cannot derive `Weak` for a trait declaring `&mut self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Receiver(r) if r.reference.is_none() => {
+                    let msg = "cannot derive `Weak` for a trait declaring `self` methods";
+                    return Err(syn::Error::new(r.span(), msg));
+                }
+                syn::FnArg::Typed(pat) => {
+                    let msg = "cannot derive `Weak` for a trait declaring methods with arbitrary receiver types";
+                    return Err(syn::Error::new(pat.span(), msg));
+                }
+                _ => (),
+            }
+        }
+
+        // a dangling reference is reported by returning `None`, so every
+        // method must return an `Option` of a value owned by the caller,
+        // since the upgraded pointer only lives as long as the call
+        if !returns_option(&m.sig) {
+            let msg = "cannot derive `Weak` for a trait declaring methods not returning `Option`";
+            let span = match m.sig.output {
+                syn::ReturnType::Type(_, ref ty) => ty.span(),
+                syn::ReturnType::Default => m.sig.ident.span(),
+            };
+            return Err(syn::Error::new(span, msg));
+        }
+        if let syn::ReturnType::Type(_, ref ty) = m.sig.output {
+            if let syn::Type::Path(ref path) = **ty {
+                let segment = path.path.segments.last().unwrap();
+                if let syn::PathArguments::AngleBracketed(ref args) = segment.arguments {
+                    if let Some(syn::GenericArgument::Type(syn::Type::Reference(r))) =
+                        args.args.first()
+                    {
+                        let msg = "cannot derive `Weak` for a trait declaring methods returning references";
+                        return Err(syn::Error::new(r.span(), msg));
+                    }
+                }
+            }
+        }
+        if returns_wrapped_self(&m.sig) {
+            let msg = "cannot derive `Weak` for a trait declaring methods returning `Self`";
+            return Err(syn::Error::new(m.sig.output.span(), msg));
+        }
+
+        // `Weak::upgrade` is called explicitly so that it is not shadowed
+        // by a trait method of the same name
+        let mut call = signature_to_method_call(&m.sig)?;
+        *call.receiver = parse_quote!((*upgraded));
+        let call = disambiguate_method_call(trait_, &m.sig, call, Some(&generic_type), ufcs)?;
+
+        let signature = &m.sig;
+        let attrs = forwarded_attributes(&m.attrs);
+        let call: syn::Expr =
+            parse_quote!(std::rc::Weak::upgrade(self).and_then(|upgraded| #call));
+        let body = forwarding_body(signature, call);
+        let item = parse_quote!(#(#attrs)* #[inline] #signature { #body });
+        methods.push(item)
+    }
+
+    let trait_ident = &trait_.ident;
+
+    // build the generics for the impl block:
+    // we use the same generics as the trait itself, plus
+    // a generic type that implements the trait for which we provide the
+    // blanket implementation
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let where_clause = impl_where_clause(trait_, &generic_type)?;
+    let generic_param = if unsized_ {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names + ?Sized)
+    } else {
+        parse_quote!(#generic_type: #trait_ident #trait_generic_names)
+    };
+    let impl_generics = impl_generics(trait_, Some(generic_param));
+
+    // forward the associated types and constants to the ones of the generic type
+    let trait_path = parse_quote!(#trait_ident #trait_generic_names);
+    let types = forwarded_associated_items(trait_, &generic_type, &trait_path)?;
+
+    Ok(parse_quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_ident #trait_generic_names for std::rc::Weak<#generic_type> #where_clause {
+            #(#types)*
+            #(#methods)*
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    mod derive {
+
+        use syn::parse_quote;
+
+        #[test]
+        fn empty() {
+            let trait_ = parse_quote!(
+                trait Trait {}
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<T: Trait + ?Sized> Trait for std::rc::Weak<T> {}
+                )
+            );
+        }
+
+        #[test]
+        fn return_option() {
+            let trait_ = parse_quote!(
+                trait Node {
+                    fn child(&self, index: usize) -> Option<u32>;
+                }
+            );
+            assert_eq!(
+                super::super::derive(&trait_, None, false).unwrap(),
+                parse_quote!(
+                    #[automatically_derived]
+                    impl<N: Node + ?Sized> Node for std::rc::Weak<N> {
+                        #[inline]
+                        fn child(&self, index: usize) -> Option<u32> {
+                            std::rc::Weak::upgrade(self).and_then(|upgraded| (*upgraded).child(index))
+                        }
+                    }
+                )
+            );
+        }
+
+        #[test]
+        fn return_value() {
+            let trait_ = parse_quote!(
+                trait Node {
+                    fn id(&self) -> u32;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Weak` for a trait declaring methods not returning `Option`"
+            );
+        }
+
+        #[test]
+        fn return_reference() {
+            let trait_ = parse_quote!(
+                trait Node {
+                    fn name(&self) -> Option<&str>;
+                }
+            );
+            let err = super::super::derive(&trait_, None, false).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Weak` for a trait declaring methods returning references"
+            );
+        }
+
+        #[test]
+        fn receiver_mut() {
+            let trait_ = parse_quote!(
+                trait Node {
+                    fn clear(&mut self) -> Option<()>;
+                }
+            );
+            assert!(super::super::derive(&trait_, None, false).is_err());
+        }
+    }
+}
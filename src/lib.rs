@@ -9,8 +9,11 @@ use std::collections::HashSet;
 
 use quote::quote;
 use quote::ToTokens;
-use syn::parse_macro_input;
+use syn::parse::Parser;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
+use syn::Token;
 
 // ---------------------------------------------------------------------------
 
@@ -23,12 +26,79 @@ mod utils;
 struct Args {
     default: Option<syn::Path>,
     derives: HashSet<derive::Derive>,
+    bounds: Vec<syn::WherePredicate>,
+    wrapper: Option<syn::Path>,
+    pointers: Vec<syn::Path>,
+    fields: Vec<(syn::Path, syn::Member)>,
+    clippy_allow: bool,
+    auto_derived: bool,
+    copy_docs: bool,
+    static_: bool,
+    unsized_: bool,
+    thread_safe: bool,
+    doc_hidden: bool,
+    ufcs: bool,
+    raw: bool,
+    generic: Option<syn::Ident>,
+    inner: Option<syn::Type>,
+    boxed_alias: Option<syn::Ident>,
+    only: Option<Vec<syn::Ident>>,
 }
 
 impl Args {
+    /// The names of the arguments of the `#[blanket(...)]` attribute.
+    const NAMES: [&'static str; 19] = [
+        "derive",
+        "default",
+        "bound",
+        "wrapper",
+        "for",
+        "for_field",
+        "generic",
+        "inner",
+        "boxed_alias",
+        "only",
+        "no_clippy_allow",
+        "no_auto_derived",
+        "copy_docs",
+        "static",
+        "unsized",
+        "thread_safe",
+        "doc_hidden",
+        "ufcs",
+        "raw",
+    ];
+
+    /// Get the argument closest to a misspelled one, if any is close enough.
+    fn suggest(name: &str) -> Option<&'static str> {
+        Self::NAMES
+            .iter()
+            .map(|n| (utils::edit_distance(name, n), *n))
+            .filter(|(distance, n)| *distance <= std::cmp::max(1, n.len() / 3))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, n)| n)
+    }
+
     fn from_args(args: &syn::AttributeArgs) -> syn::Result<Self> {
         let mut default = None;
         let mut derives = HashSet::new();
+        let mut bounds = Vec::new();
+        let mut wrapper = None;
+        let mut pointers = None;
+        let mut fields = Vec::new();
+        let mut clippy_allow = true;
+        let mut auto_derived = true;
+        let mut copy_docs = false;
+        let mut static_ = false;
+        let mut unsized_ = None;
+        let mut thread_safe = false;
+        let mut doc_hidden = false;
+        let mut ufcs = false;
+        let mut raw = false;
+        let mut generic = None;
+        let mut inner = None;
+        let mut boxed_alias = None;
+        let mut only = None;
 
         let meta = args
             .iter()
@@ -43,21 +113,152 @@ impl Args {
             // to avoid to compile `syn` with the `extra-traits` feature
             match arg {
                 syn::Meta::List(ref l) if l.path.to_token_stream().to_string() == "derive" => {
+                    let expected = derive::Derive::ALL
+                        .iter()
+                        .map(|d| format!("`{}`", d))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    if l.nested.is_empty() {
+                        let msg = format!(
+                            "expected at least one blanket derive (expected one of {})",
+                            expected
+                        );
+                        return Err(syn::Error::new(l.path.span(), msg));
+                    }
                     for elem in l.nested.iter() {
                         if let syn::NestedMeta::Meta(syn::Meta::Path(path)) = elem {
-                            if let Some(d) = derive::Derive::from_path(&path) {
+                            if let Some(d) = derive::Derive::from_path(path) {
                                 derives.insert(d);
+                                // the `Deref` blanket implementation covers every
+                                // pointer type, so it overlaps with any other derive
+                                if derives.len() > 1 && derives.contains(&derive::Derive::Deref) {
+                                    return Err(syn::Error::new(
+                                        path.span(),
+                                        "cannot combine `Deref` with other blanket derives",
+                                    ));
+                                }
+                                // `&dyn Trait` and `&Box<T>` are already covered by the
+                                // `Ref` derive, and the fallible flavors implement the
+                                // same types; the `Box` derive only covers sized types
+                                // unless given the `unsized` flag, checked below
+                                for (generic, object) in [
+                                    (derive::Derive::Ref, derive::Derive::RefDyn),
+                                    (derive::Derive::Ref, derive::Derive::RefBox),
+                                    (derive::Derive::RcRefCell, derive::Derive::RcRefCellTry),
+                                    (derive::Derive::ArcMutex, derive::Derive::ArcMutexTry),
+                                ]
+                                .iter()
+                                {
+                                    if derives.contains(generic) && derives.contains(object) {
+                                        let msg = format!(
+                                            "cannot combine `{}` with `{}`",
+                                            generic, object
+                                        );
+                                        return Err(syn::Error::new(path.span(), msg));
+                                    }
+                                }
                             } else {
-                                return Err(syn::Error::new(
-                                    path.span(),
-                                    "unknown blanket derive option",
-                                ));
+                                let name = path.to_token_stream().to_string();
+                                let msg = match derive::Derive::suggest(&name) {
+                                    Some(d) => format!(
+                                        "unknown blanket derive option `{}`, did you mean `{}`? (expected one of {})",
+                                        name, d, expected
+                                    ),
+                                    None => format!(
+                                        "unknown blanket derive option `{}` (expected one of {})",
+                                        name, expected
+                                    ),
+                                };
+                                return Err(syn::Error::new(path.span(), msg));
                             }
                         } else {
                             return Err(syn::Error::new(elem.span(), "expected identifier"));
                         }
                     }
                 }
+                syn::Meta::List(ref l) if l.path.to_token_stream().to_string() == "only" => {
+                    if only.is_some() {
+                        return Err(syn::Error::new(l.span(), "duplicate method list given"));
+                    }
+                    let mut methods = Vec::new();
+                    for elem in l.nested.iter() {
+                        match elem {
+                            syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                                match path.get_ident() {
+                                    Some(ident) => methods.push(ident.clone()),
+                                    None => {
+                                        return Err(syn::Error::new(
+                                            path.span(),
+                                            "expected method identifier",
+                                        ))
+                                    }
+                                }
+                            }
+                            _ => {
+                                return Err(syn::Error::new(
+                                    elem.span(),
+                                    "expected method identifier",
+                                ))
+                            }
+                        }
+                    }
+                    only = Some(methods);
+                }
+                syn::Meta::List(ref l) if l.path.to_token_stream().to_string() == "for" => {
+                    if pointers.is_some() {
+                        return Err(syn::Error::new(l.span(), "duplicate pointer type list given"));
+                    }
+                    if l.nested.is_empty() {
+                        return Err(syn::Error::new(
+                            l.path.span(),
+                            "expected at least one pointer type",
+                        ));
+                    }
+                    let mut paths = Vec::new();
+                    for elem in l.nested.iter() {
+                        match elem {
+                            syn::NestedMeta::Meta(syn::Meta::Path(path)) => paths.push(path.clone()),
+                            _ => return Err(syn::Error::new(elem.span(), "expected type path")),
+                        }
+                    }
+                    pointers = Some((paths, l.path.span()));
+                }
+                syn::Meta::List(ref l) if l.path.to_token_stream().to_string() == "for_field" => {
+                    if l.nested.is_empty() {
+                        return Err(syn::Error::new(
+                            l.path.span(),
+                            "expected at least one type with a field",
+                        ));
+                    }
+                    for elem in l.nested.iter() {
+                        match elem {
+                            syn::NestedMeta::Meta(syn::Meta::NameValue(ref n)) => {
+                                if let syn::Lit::Str(ref s) = n.lit {
+                                    match s.parse::<syn::Member>() {
+                                        Ok(member) => fields.push((n.path.clone(), member)),
+                                        Err(_) => {
+                                            return Err(syn::Error::new(
+                                                s.span(),
+                                                "expected field name",
+                                            ))
+                                        }
+                                    }
+                                } else {
+                                    return Err(syn::Error::new(
+                                        n.lit.span(),
+                                        "expected string literal",
+                                    ));
+                                }
+                            }
+                            _ => {
+                                return Err(syn::Error::new(
+                                    elem.span(),
+                                    "expected type path with a field name",
+                                ))
+                            }
+                        }
+                    }
+                }
                 syn::Meta::NameValue(ref n)
                     if n.path.to_token_stream().to_string() == "default" =>
                 {
@@ -80,11 +281,551 @@ impl Args {
                         return Err(syn::Error::new(n.lit.span(), "expected string literal"));
                     }
                 }
-                _ => return Err(syn::Error::new(arg.span(), "unexpected argument")),
+                syn::Meta::NameValue(ref n) if n.path.to_token_stream().to_string() == "bound" => {
+                    if let syn::Lit::Str(ref s) = n.lit {
+                        let parser = Punctuated::<syn::WherePredicate, Token![,]>::parse_terminated;
+                        match s.parse_with(parser) {
+                            Ok(predicates) => bounds.extend(predicates),
+                            Err(_) => {
+                                return Err(syn::Error::new(s.span(), "expected where predicates"))
+                            }
+                        }
+                    } else {
+                        return Err(syn::Error::new(n.lit.span(), "expected string literal"));
+                    }
+                }
+                syn::Meta::NameValue(ref n)
+                    if n.path.to_token_stream().to_string() == "wrapper" =>
+                {
+                    if let syn::Lit::Str(ref s) = n.lit {
+                        match s.parse::<syn::Path>() {
+                            Ok(path) if wrapper.is_none() => {
+                                wrapper = Some((path, s.span()));
+                            }
+                            Ok(_) => {
+                                return Err(syn::Error::new(
+                                    s.span(),
+                                    "duplicate wrapper type given",
+                                ))
+                            }
+                            Err(_) => return Err(syn::Error::new(s.span(), "expected type path")),
+                        }
+                    } else {
+                        return Err(syn::Error::new(n.lit.span(), "expected string literal"));
+                    }
+                }
+                syn::Meta::NameValue(ref n)
+                    if n.path.to_token_stream().to_string() == "generic" =>
+                {
+                    if let syn::Lit::Str(ref s) = n.lit {
+                        match s.parse::<syn::Ident>() {
+                            Ok(ident) if generic.is_none() => generic = Some(ident),
+                            Ok(_) => {
+                                return Err(syn::Error::new(
+                                    s.span(),
+                                    "duplicate generic type given",
+                                ))
+                            }
+                            Err(_) => return Err(syn::Error::new(s.span(), "expected identifier")),
+                        }
+                    } else {
+                        return Err(syn::Error::new(n.lit.span(), "expected string literal"));
+                    }
+                }
+                syn::Meta::NameValue(ref n)
+                    if n.path.to_token_stream().to_string() == "boxed_alias" =>
+                {
+                    if let syn::Lit::Str(ref s) = n.lit {
+                        match s.parse::<syn::Ident>() {
+                            Ok(ident) if boxed_alias.is_none() => boxed_alias = Some(ident),
+                            Ok(_) => {
+                                return Err(syn::Error::new(s.span(), "duplicate boxed alias given"))
+                            }
+                            Err(_) => return Err(syn::Error::new(s.span(), "expected identifier")),
+                        }
+                    } else {
+                        return Err(syn::Error::new(n.lit.span(), "expected string literal"));
+                    }
+                }
+                syn::Meta::NameValue(ref n) if n.path.to_token_stream().to_string() == "inner" => {
+                    if let syn::Lit::Str(ref s) = n.lit {
+                        match s.parse::<syn::Type>() {
+                            Ok(ty) if inner.is_none() => inner = Some((ty, s.span())),
+                            Ok(_) => {
+                                return Err(syn::Error::new(s.span(), "duplicate inner type given"))
+                            }
+                            Err(_) => return Err(syn::Error::new(s.span(), "expected type")),
+                        }
+                    } else {
+                        return Err(syn::Error::new(n.lit.span(), "expected string literal"));
+                    }
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "no_clippy_allow" => {
+                    clippy_allow = false;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "no_auto_derived" => {
+                    auto_derived = false;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "copy_docs" => {
+                    copy_docs = true;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "static" => {
+                    static_ = true;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "unsized" => {
+                    unsized_ = Some(p.span());
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "thread_safe" => {
+                    thread_safe = true;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "doc_hidden" => {
+                    doc_hidden = true;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "ufcs" => {
+                    ufcs = true;
+                }
+                syn::Meta::Path(ref p) if p.to_token_stream().to_string() == "raw" => {
+                    raw = true;
+                }
+                // the derives must be given as a list, even if there is only one
+                syn::Meta::Path(_) | syn::Meta::NameValue(_)
+                    if arg.path().to_token_stream().to_string() == "derive" =>
+                {
+                    let msg = "expected a list of blanket derives, as in `derive(Box)`";
+                    return Err(syn::Error::new(arg.span(), msg));
+                }
+                _ => {
+                    let name = arg.path().to_token_stream().to_string();
+                    let msg = match Self::suggest(&name) {
+                        Some(n) => format!("unexpected argument `{}`, did you mean `{}`?", name, n),
+                        None => format!("unexpected argument `{}`", name),
+                    };
+                    return Err(syn::Error::new(arg.span(), msg));
+                }
+            }
+        }
+
+        // a wrapper type replaces the smart pointer of a single derive, whose
+        // forwarding code relies on `Deref` only
+        let wrapper = match wrapper {
+            Some((path, span)) => {
+                let mut iter = derives.iter();
+                match (iter.next(), iter.next()) {
+                    (Some(derive::Derive::Box), None) | (Some(derive::Derive::Rc), None) => {
+                        Some(path)
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            span,
+                            "expected exactly one `Box` or `Rc` derive with a wrapper type",
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // the pointer types given with `for(...)` are implemented with the
+        // forwarding code of the `Deref` derive, which already covers them
+        let pointers = match pointers {
+            Some((_, span)) if derives.contains(&derive::Derive::Deref) => {
+                let msg = "cannot combine `Deref` with pointer types given with `for(...)`";
+                return Err(syn::Error::new(span, msg));
+            }
+            Some((paths, _)) => paths,
+            None => Vec::new(),
+        };
+
+        // the flag relaxes the bound of the `Box` derive alone, the other
+        // derives implementing the trait for unsized types whenever they can
+        let unsized_ = match unsized_ {
+            Some(span) if !derives.contains(&derive::Derive::Box) => {
+                let msg = "expected a `Box` derive with the `unsized` flag";
+                return Err(syn::Error::new(span, msg));
+            }
+            Some(span) => {
+                // `Box<dyn Trait>` is then covered by the `Box` derive
+                for object in [derive::Derive::BoxDyn, derive::Derive::BoxDynSendSync].iter() {
+                    if derives.contains(object) {
+                        let msg = format!("cannot combine `{}` with the `unsized` flag", object);
+                        return Err(syn::Error::new(span, msg));
+                    }
+                }
+                true
+            }
+            None => false,
+        };
+
+        // the inner type given with `inner = "..."` replaces the generic type,
+        // which the derives implemented for a trait object do not have
+        let inner = match inner {
+            Some((ty, span)) => {
+                let objects = [
+                    derive::Derive::BoxDyn,
+                    derive::Derive::BoxDynSendSync,
+                    derive::Derive::RefDyn,
+                ];
+                for object in objects.iter() {
+                    if derives.contains(object) {
+                        let msg = format!(
+                            "cannot combine `{}` with an inner type given with `inner = \"...\"`",
+                            object
+                        );
+                        return Err(syn::Error::new(span, msg));
+                    }
+                }
+                if generic.is_some() {
+                    let msg = "cannot combine a generic type with an inner type given with `inner = \"...\"`";
+                    return Err(syn::Error::new(span, msg));
+                }
+                Some(ty)
             }
+            None => None,
+        };
+
+        // the `raw` flag removes every attribute added to the derived
+        // implementations, including the ones removed by the other flags
+        if raw {
+            clippy_allow = false;
+            auto_derived = false;
         }
 
-        Ok(Self { default, derives })
+        Ok(Self {
+            default,
+            derives,
+            bounds,
+            wrapper,
+            pointers,
+            fields,
+            clippy_allow,
+            auto_derived,
+            copy_docs,
+            static_,
+            unsized_,
+            thread_safe,
+            doc_hidden,
+            ufcs,
+            raw,
+            generic,
+            inner,
+            boxed_alias,
+            only,
+        })
+    }
+
+    /// Get the trait with only the items to forward to the generic type.
+    ///
+    /// The methods not given with `only(...)` and the items marked with
+    /// `#[blanket(skip)]` are removed from the trait before deriving the
+    /// implementations, so they must have a default to inherit.
+    fn forwarded_trait(&self, trait_: &syn::ItemTrait) -> syn::Result<syn::ItemTrait> {
+        // the generic type given with `generic = "..."` must not collide with
+        // the trait, or with the pointer type of the `Deref` derive
+        if let Some(ref generic) = self.generic {
+            if utils::is_declared_by_trait(trait_, generic) {
+                let msg = format!(
+                    "generic type `{}` is already declared by trait `{}`",
+                    generic, trait_.ident
+                );
+                return Err(syn::Error::new(generic.span(), msg));
+            }
+            let generic_type = utils::trait_to_generic_ident(trait_, None);
+            let pointer_type = utils::trait_to_pointer_ident(trait_, &generic_type);
+            if self.derives.contains(&derive::Derive::Deref) && *generic == pointer_type {
+                let msg = format!(
+                    "generic type `{}` is already used by the `Deref` derive",
+                    generic
+                );
+                return Err(syn::Error::new(generic.span(), msg));
+            }
+        }
+        if let Some(ref only) = self.only {
+            for ident in only.iter() {
+                let declared = trait_.items.iter().any(|item| match item {
+                    syn::TraitItem::Method(m) => m.sig.ident == *ident,
+                    _ => false,
+                });
+                if !declared {
+                    let msg = format!("no method `{}` declared in trait `{}`", ident, trait_.ident);
+                    return Err(syn::Error::new(ident.span(), msg));
+                }
+            }
+        }
+
+        let mut forwarded = trait_.clone();
+        forwarded.items.clear();
+        for item in trait_.items.iter() {
+            let mut skipped = match item {
+                syn::TraitItem::Method(m) => match self.only {
+                    Some(ref only) => !only.contains(&m.sig.ident),
+                    None => false,
+                },
+                _ => false,
+            };
+            let mut kept = false;
+            if let Some(attrs) = utils::trait_item_attributes(item) {
+                skipped |= utils::is_skipped(attrs)?;
+                // methods taken out of a `Cell` only make sense for the `Cell`
+                // derive, which reads the attribute itself
+                if let Some(attr) = utils::take_attribute(attrs) {
+                    if !matches!(item, syn::TraitItem::Method(_)) {
+                        let msg = "`as` is only supported on trait methods";
+                        return Err(syn::Error::new(attr.span(), msg));
+                    }
+                    if !self.derives.contains(&derive::Derive::Cell) {
+                        let msg = "expected a `Cell` derive with `as = \"take\"`";
+                        return Err(syn::Error::new(attr.path.span(), msg));
+                    }
+                    kept = true;
+                }
+                // methods forwarded through an adapter are only supported by
+                // the derives dereferencing the receiver, which read the
+                // attribute themselves
+                if let Some(attr) = utils::via_attribute(attrs) {
+                    let m = match item {
+                        syn::TraitItem::Method(m) => m,
+                        _ => {
+                            let msg = "`via` is only supported on trait methods";
+                            return Err(syn::Error::new(attr.span(), msg));
+                        }
+                    };
+                    utils::via_adapter(attrs, &m.sig)?;
+                    let supported = [
+                        derive::Derive::Ref,
+                        derive::Derive::Rc,
+                        derive::Derive::Mut,
+                        derive::Derive::Box,
+                        derive::Derive::Deref,
+                        derive::Derive::RefBox,
+                    ];
+                    let mut unsupported = derive::Derive::ALL
+                        .iter()
+                        .filter(|d| self.derives.contains(d) && !supported.contains(d));
+                    if let Some(d) = unsupported.next() {
+                        let msg = format!("cannot combine `{}` with `via = \"...\"`", d);
+                        return Err(syn::Error::new(attr.path.span(), msg));
+                    }
+                    kept = true;
+                }
+                // the lock taken by a method can only be chosen for the
+                // `ArcRwLock` derive, which reads the attribute itself
+                if let Some(attr) = utils::lock_attribute(attrs) {
+                    if !matches!(item, syn::TraitItem::Method(_)) {
+                        let msg = "`lock` is only supported on trait methods";
+                        return Err(syn::Error::new(attr.span(), msg));
+                    }
+                    utils::lock_kind(attrs)?;
+                    let mut unsupported = derive::Derive::ALL
+                        .iter()
+                        .filter(|d| self.derives.contains(d) && **d != derive::Derive::ArcRwLock);
+                    if let Some(d) = unsupported.next() {
+                        let msg = format!("cannot combine `{}` with `lock = \"...\"`", d);
+                        return Err(syn::Error::new(attr.path.span(), msg));
+                    }
+                    kept = true;
+                }
+            }
+            if !skipped {
+                let mut item = item.clone();
+                if !kept {
+                    utils::strip_item_attributes(&mut item);
+                }
+                forwarded.items.push(item);
+                continue;
+            }
+            match item {
+                syn::TraitItem::Method(m) if m.default.is_none() => {
+                    let msg = format!(
+                        "cannot skip method `{}` without a default implementation",
+                        m.sig.ident
+                    );
+                    return Err(syn::Error::new(m.sig.ident.span(), msg));
+                }
+                syn::TraitItem::Const(c) if c.default.is_none() => {
+                    let msg = format!(
+                        "cannot skip associated const `{}` without a default value",
+                        c.ident
+                    );
+                    return Err(syn::Error::new(c.ident.span(), msg));
+                }
+                syn::TraitItem::Type(t) if t.default.is_none() => {
+                    let msg = format!(
+                        "cannot skip associated type `{}` without a default type",
+                        t.ident
+                    );
+                    return Err(syn::Error::new(t.ident.span(), msg));
+                }
+                _ => (),
+            }
+        }
+        // the methods of an unsized generic type cannot move it, or return it
+        if self.unsized_ {
+            for item in forwarded.items.iter() {
+                if let syn::TraitItem::Method(m) = item {
+                    let by_value = match m.sig.receiver() {
+                        Some(syn::FnArg::Receiver(r)) => r.reference.is_none(),
+                        _ => false,
+                    };
+                    if by_value
+                        || utils::is_sized_method(&m.sig)
+                        || utils::returns_self(&m.sig)
+                        || utils::returns_wrapped_self(&m.sig)
+                    {
+                        let msg = "cannot derive `Box` with the `unsized` flag for a trait declaring methods requiring `Self: Sized`";
+                        return Err(syn::Error::new(m.sig.ident.span(), msg));
+                    }
+                }
+            }
+        }
+        Ok(forwarded)
+    }
+
+    /// Update a derived implementation with the arguments common to all derives.
+    fn update_impl(&self, trait_: &syn::ItemTrait, item: &mut syn::ItemImpl) {
+        // gate the implementation with the `#[cfg]` attributes of the trait,
+        // so that it is only compiled when the trait itself is
+        let mut attrs = utils::cfg_attributes(&trait_.attrs);
+        attrs.append(&mut item.attrs);
+        item.attrs = attrs;
+        // rewrite the `async fn` methods of the implementation the same way
+        // as the ones of the trait when `#[async_trait]` is applied after
+        // `#[blanket]`, requiring the type to be `Send` or `Sync` as needed
+        for attr in trait_.attrs.iter().filter(|a| utils::is_async_trait_attribute(a)) {
+            let bounds = utils::async_trait_bounds(trait_, attr);
+            if !bounds.is_empty() {
+                let where_clause = item.generics.make_where_clause();
+                where_clause.predicates.extend(bounds);
+            }
+            item.attrs.push(attr.clone());
+        }
+        // implementing an `unsafe trait` requires an `unsafe impl`, whose
+        // safety invariants are upheld by the ones of the generic type
+        item.unsafety = trait_.unsafety;
+        // add the additional bounds given with `bound = "..."`
+        if !self.bounds.is_empty() {
+            let where_clause = item.generics.make_where_clause();
+            where_clause.predicates.extend(self.bounds.iter().cloned());
+        }
+        // require the generic type to be `'static` if the `static` flag is
+        // given, which derives implemented for a trait object do not have
+        if self.static_ {
+            let generic_type = utils::trait_to_generic_ident(trait_, self.generic.as_ref());
+            let generic = item.generics.type_params().any(|p| p.ident == generic_type);
+            if generic {
+                let where_clause = item.generics.make_where_clause();
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#generic_type: 'static));
+            }
+        }
+        // relax the bound of the generic type of the `Box` derive if the
+        // `unsized` flag is given
+        if self.unsized_ {
+            let generic_type = utils::trait_to_generic_ident(trait_, self.generic.as_ref());
+            if utils::is_std_path(&item.self_ty, "boxed") {
+                for param in item.generics.type_params_mut() {
+                    if param.ident == generic_type {
+                        param.bounds.push(parse_quote!(?Sized));
+                    }
+                }
+            }
+        }
+        // require the generic type to be `Send + Sync` if the `thread_safe`
+        // flag is given, except for the auto traits already supertraits
+        if self.thread_safe {
+            let generic_type = utils::trait_to_generic_ident(trait_, self.generic.as_ref());
+            let generic = item.generics.type_params().any(|p| p.ident == generic_type);
+            let bounds = ["Send", "Sync"]
+                .iter()
+                .filter(|name| !utils::has_supertrait(trait_, name))
+                .map(|name| syn::Ident::new(name, proc_macro2::Span::call_site()))
+                .collect::<Vec<_>>();
+            if generic && !bounds.is_empty() {
+                let where_clause = item.generics.make_where_clause();
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#generic_type: #(std::marker::#bounds)+*));
+            }
+        }
+        // remove the bounds repeated between the generic parameters and
+        // the where clause, e.g. with a supertrait also given as `Self` bound
+        utils::dedup_bounds(&mut item.generics);
+        // replace the smart pointer with the type given with `wrapper = "..."`,
+        // keeping the generic arguments of the original type, but not the
+        // types given with `for(...)` or `for_field(...)`
+        let pointer = ["boxed", "rc"]
+            .iter()
+            .any(|module| utils::is_std_path(&item.self_ty, module));
+        if let (Some(ref wrapper), true) = (&self.wrapper, pointer) {
+            if let syn::Type::Path(ref mut ty) = *item.self_ty {
+                let mut path = wrapper.clone();
+                if let (Some(new), Some(old)) = (path.segments.last_mut(), ty.path.segments.last())
+                {
+                    new.arguments = old.arguments.clone();
+                }
+                ty.path = path;
+            }
+        }
+        // copy the documentation of the trait methods to the forwarding methods
+        // if the `copy_docs` flag is given, skipping attributes already present
+        if self.copy_docs {
+            for impl_item in item.items.iter_mut() {
+                if let syn::ImplItem::Method(ref mut m) = impl_item {
+                    let trait_method = trait_.items.iter().find_map(|t| match t {
+                        syn::TraitItem::Method(tm) if tm.sig.ident == m.sig.ident => Some(tm),
+                        _ => None,
+                    });
+                    if let Some(tm) = trait_method {
+                        let mut seen = m
+                            .attrs
+                            .iter()
+                            .filter_map(utils::doc_string)
+                            .collect::<HashSet<_>>();
+                        let mut attrs = Vec::new();
+                        for attr in tm.attrs.iter() {
+                            if let Some(doc) = utils::doc_string(attr) {
+                                if seen.insert(doc) {
+                                    attrs.push(attr.clone());
+                                }
+                            }
+                        }
+                        attrs.append(&mut m.attrs);
+                        m.attrs = attrs;
+                    }
+                }
+            }
+        }
+        // implement the trait for the type given with `inner = "..."` instead
+        // of the generic type, relying on its own implementation of the trait
+        if let Some(ref inner) = self.inner {
+            *item = utils::impl_for_inner(item, trait_, inner);
+        }
+        // remove the `#[automatically_derived]` attribute of the derives if
+        // the `no_auto_derived` flag is given
+        if !self.auto_derived {
+            item.attrs
+                .retain(|attr| !attr.path.is_ident("automatically_derived"));
+        }
+        // remove the documentation of the derives and the `#[inline]`
+        // attributes of the forwarding methods if the `raw` flag is given
+        if self.raw {
+            item.attrs.retain(|attr| !attr.path.is_ident("doc"));
+            for impl_item in item.items.iter_mut() {
+                if let syn::ImplItem::Method(ref mut m) = impl_item {
+                    m.attrs.retain(|attr| !attr.path.is_ident("inline"));
+                }
+            }
+        }
+        // hide the implementation from the documentation if the `doc_hidden`
+        // flag is given
+        if self.doc_hidden {
+            item.attrs.push(parse_quote!(#[doc(hidden)]));
+        }
+        // silence Clippy lints triggered by the forwarding code, unless
+        // the `no_clippy_allow` flag is given
+        if self.clippy_allow {
+            item.attrs.push(parse_quote!(#[allow(clippy::all)]));
+        }
     }
 }
 
@@ -95,35 +836,1208 @@ pub fn blanket(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    proc_macro::TokenStream::from(expand(args.into(), input.into()))
+}
+
+/// Expand the `#[blanket]` attribute with the given arguments on a trait.
+///
+/// Errors are emitted as `compile_error!` invocations in the returned tokens,
+/// like the attribute macro does, so that it can be tested outside of a
+/// procedural macro invocation.
+fn expand(
+    args: proc_macro2::TokenStream,
+    input: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     // parse input
-    let trait_ = parse_macro_input!(input as syn::ItemTrait);
-    let attribute_args = parse_macro_input!(args as syn::AttributeArgs);
+    let trait_ = match syn::parse2::<syn::Item>(input) {
+        Ok(syn::Item::Trait(trait_)) => trait_,
+        Ok(item) => {
+            let msg = "`#[blanket]` can only be applied to trait definitions";
+            let err = syn::Error::new(item.span(), msg).to_compile_error();
+            return quote!(#err #item);
+        }
+        Err(e) => return e.to_compile_error(),
+    };
+    let parser = Punctuated::<syn::NestedMeta, Token![,]>::parse_terminated;
+    let attribute_args = match parser.parse2(args) {
+        Ok(args) => args.into_iter().collect::<syn::AttributeArgs>(),
+        Err(e) => {
+            let err = e.to_compile_error();
+            return quote!(#err #trait_);
+        }
+    };
     // parse macro arguments and immediately exit if they are invalid
     let args = match Args::from_args(&attribute_args) {
         Ok(args) => args,
         Err(e) => {
             let err = e.to_compile_error();
-            return proc_macro::TokenStream::from(quote!(#err #trait_));
+            return quote!(#err #trait_);
         }
     };
     // generate output
     let mut out = proc_macro2::TokenStream::new();
+    // remove the `#[blanket(...)]` attributes of the trait items, which are
+    // only read by the derives, from the declared trait
+    let mut declared = trait_.clone();
+    declared
+        .items
+        .iter_mut()
+        .for_each(utils::strip_item_attributes);
     // update trait methods declaration if given a `default = "..."` argument,
     // otherwise simply keep the output
     match args.default {
-        None => out.extend(quote!(#trait_)),
-        Some(d) => match default::defer_trait_methods(trait_.clone(), d) {
+        None => out.extend(quote!(#declared)),
+        Some(ref d) => match default::defer_trait_methods(declared, d.clone()) {
             Ok(trait_) => out.extend(quote!(#trait_)),
             Err(err) => out.extend(err.to_compile_error()),
         },
     };
-    // add derived implementations
-    for d in args.derives {
-        match d.defer_trait_methods(&trait_) {
+    // add the type alias of the boxed trait object given with
+    // `boxed_alias = "..."` next to the trait
+    if let Some(ref alias) = args.boxed_alias {
+        match utils::boxed_alias(&trait_, alias) {
             Ok(item) => out.extend(quote!(#item)),
             Err(e) => out.extend(e.to_compile_error()),
         }
     }
-    // return the new `proc-macro2` token stream as a `proc-macro` stream
-    proc_macro::TokenStream::from(out)
+    // add derived implementations, forwarding only the methods given
+    // with `only(...)` if any
+    let trait_ = match args.forwarded_trait(&trait_) {
+        Ok(trait_) => trait_,
+        Err(e) => {
+            out.extend(e.to_compile_error());
+            return out;
+        }
+    };
+    let generic = args.generic.as_ref();
+    for item in derive::Derive::defer_many(args.derives.iter(), &trait_, generic, args.ufcs) {
+        match item {
+            Ok(mut item) => {
+                args.update_impl(&trait_, &mut item);
+                out.extend(quote!(#item))
+            }
+            Err(e) => out.extend(e.to_compile_error()),
+        }
+    }
+    // add the implementations for the types given with `for_field(...)`,
+    // forwarding the methods to the given field
+    for (ty, field) in args.fields.iter() {
+        match derive::Derive::defer_field(&trait_, ty, field, args.ufcs) {
+            Ok(mut item) => {
+                args.update_impl(&trait_, &mut item);
+                out.extend(quote!(#item))
+            }
+            Err(e) => out.extend(e.to_compile_error()),
+        }
+    }
+    // add the implementations for the pointer types given with `for(...)`,
+    // retargeting the implementation of the `Deref` derive to each of them
+    if !args.pointers.is_empty() {
+        let deref = derive::Derive::Deref;
+        let mut items = derive::Derive::defer_many(std::iter::once(&deref), &trait_, generic, args.ufcs);
+        match items.remove(0) {
+            Ok(item) => {
+                for pointer in args.pointers.iter() {
+                    let mut item = utils::impl_for_pointer(&item, &trait_, pointer, generic);
+                    args.update_impl(&trait_, &mut item);
+                    out.extend(quote!(#item))
+                }
+            }
+            Err(e) => out.extend(e.to_compile_error()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+
+    use syn::parse_quote;
+
+    use super::Args;
+
+    #[test]
+    fn expand() {
+        let args = quote::quote!(derive(Ref, Box));
+        let input = quote::quote!(
+            pub trait Counter {
+                fn get(&self) -> u32;
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert_eq!(file.items.len(), 3);
+        assert!(matches!(file.items[0], syn::Item::Trait(_)));
+        assert!(matches!(file.items[1], syn::Item::Impl(_)));
+        assert!(matches!(file.items[2], syn::Item::Impl(_)));
+
+        let args = quote::quote!(derive(Box) = 1);
+        let input = quote::quote!(
+            pub trait Counter {}
+        );
+        let output = super::expand(args, input).to_string();
+        assert!(output.starts_with("compile_error !"));
+        assert!(output.contains("pub trait Counter"));
+
+        let args = quote::quote!(derive(Boxx));
+        let input = quote::quote!(
+            pub trait Counter {}
+        );
+        let output = super::expand(args, input).to_string();
+        assert!(output.starts_with("compile_error !"));
+        assert!(output.contains("pub trait Counter"));
+    }
+
+    #[test]
+    fn expand_pointers() {
+        let args = quote::quote!(for(MyArc, crate::MyBox), no_clippy_allow);
+        let input = quote::quote!(
+            pub trait Counter {
+                fn get(&self) -> u32;
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert_eq!(file.items.len(), 3);
+        assert_eq!(
+            file.items[1],
+            parse_quote!(
+                #[automatically_derived]
+                impl<C: Counter> Counter for MyArc<C> {
+                    #[inline]
+                    fn get(&self) -> u32 {
+                        (*(*self)).get()
+                    }
+                }
+            )
+        );
+        assert_eq!(
+            file.items[2],
+            parse_quote!(
+                #[automatically_derived]
+                impl<C: Counter> Counter for crate::MyBox<C> {
+                    #[inline]
+                    fn get(&self) -> u32 {
+                        (*(*self)).get()
+                    }
+                }
+            )
+        );
+
+        let args = quote::quote!(derive(Deref), for(MyArc));
+        let output = super::expand(args, quote::quote!(pub trait Counter {})).to_string();
+        assert!(output.starts_with("compile_error !"));
+    }
+
+    #[test]
+    fn expand_fields() {
+        let args = quote::quote!(derive(Box), for_field(Meter = "value", Seconds = "0"));
+        let input = quote::quote!(
+            pub trait Quantity {
+                fn get(&self) -> f64;
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        let types = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Impl(i) => Some(quote::ToTokens::to_token_stream(&i.self_ty).to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec!["std :: boxed :: Box < Q >", "Meter", "Seconds"]);
+
+        let args = quote::quote!(for_field(Meter));
+        let output = super::expand(args, quote::quote!(pub trait Quantity {})).to_string();
+        assert!(output.starts_with("compile_error !"));
+    }
+
+    #[test]
+    fn expand_not_trait() {
+        let args = quote::quote!(derive(Box));
+        let input = quote::quote!(
+            pub struct Counter(u32);
+        );
+        let output = super::expand(args, input).to_string();
+        assert!(output.starts_with("compile_error !"));
+        assert!(output.contains("can only be applied to trait definitions"));
+        assert!(output.contains("pub struct Counter"));
+    }
+
+    #[test]
+    fn expand_async_trait() {
+        let args = quote::quote!(derive(Box));
+        let input = quote::quote!(
+            #[async_trait]
+            pub trait Fetcher {
+                async fn get(&self) -> Vec<u8>;
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        let trait_ = match &file.items[0] {
+            syn::Item::Trait(trait_) => trait_,
+            item => panic!("expected a trait, got {:?}", item),
+        };
+        let expected: syn::Attribute = parse_quote!(#[async_trait]);
+        assert_eq!(trait_.attrs, vec![expected.clone()]);
+        let item = match &file.items[1] {
+            syn::Item::Impl(item) => item,
+            item => panic!("expected an impl, got {:?}", item),
+        };
+        assert!(item.attrs.contains(&expected));
+        let predicate: syn::WherePredicate = parse_quote!(Self: std::marker::Sync);
+        let where_clause = item.generics.where_clause.as_ref().unwrap();
+        assert!(where_clause.predicates.iter().any(|p| *p == predicate));
+    }
+
+    #[test]
+    fn update_impl_unsafe() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            unsafe trait Zeroable {
+                fn zero(&mut self);
+            }
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<Z: Zeroable + ?Sized> Zeroable for Box<Z> {}
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert!(item.unsafety.is_some());
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Zeroable {
+                fn zero(&mut self);
+            }
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<Z: Zeroable + ?Sized> Zeroable for Box<Z> {}
+        );
+        args.update_impl(&trait_, &mut item);
+        assert!(item.unsafety.is_none());
+    }
+
+    #[test]
+    fn update_impl_clippy_allow() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for Box<T> {}
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                #[allow(clippy::all)]
+                impl<T: Trait> Trait for Box<T> {}
+            )
+        );
+
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for Box<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                impl<T: Trait> Trait for Box<T> {}
+            )
+        );
+    }
+
+    #[test]
+    fn update_impl_doc_hidden() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for Box<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(doc_hidden),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                #[doc(hidden)]
+                impl<T: Trait> Trait for Box<T> {}
+            )
+        );
+
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for Box<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert!(item.attrs.is_empty());
+    }
+
+    #[test]
+    fn update_impl_auto_derived() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            #[automatically_derived]
+            impl<T: Trait> Trait for Box<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                #[automatically_derived]
+                impl<T: Trait> Trait for Box<T> {}
+            )
+        );
+
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(no_auto_derived),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert!(item.attrs.is_empty());
+    }
+
+    #[test]
+    fn update_impl_raw() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[cfg(unix)]
+                fn get(&self) -> u8;
+            }
+        );
+        let derived: syn::ItemImpl = parse_quote!(
+            #[automatically_derived]
+            impl<T: Trait> Trait for std::boxed::Box<T> {
+                #[cfg(unix)]
+                #[inline]
+                fn get(&self) -> u8 {
+                    (*(*self)).get()
+                }
+            }
+        );
+
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        let mut item = derived.clone();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                #[automatically_derived]
+                #[allow(clippy::all)]
+                impl<T: Trait> Trait for std::boxed::Box<T> {
+                    #[cfg(unix)]
+                    #[inline]
+                    fn get(&self) -> u8 {
+                        (*(*self)).get()
+                    }
+                }
+            )
+        );
+
+        let args = Args::from_args(&vec![parse_quote!(derive(Box)), parse_quote!(raw)]).unwrap();
+        let mut item = derived;
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                impl<T: Trait> Trait for std::boxed::Box<T> {
+                    #[cfg(unix)]
+                    fn get(&self) -> u8 {
+                        (*(*self)).get()
+                    }
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn update_impl_unsized() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box, Ref)),
+            parse_quote!(unsized),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for std::boxed::Box<T> {}
+        );
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                impl<T: Trait + ?Sized> Trait for std::boxed::Box<T> {}
+            )
+        );
+
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait + ?Sized> Trait for &T {}
+        );
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                impl<T: Trait + ?Sized> Trait for &T {}
+            )
+        );
+
+        let err = Args::from_args(&vec![parse_quote!(derive(Rc)), parse_quote!(unsized)])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "expected a `Box` derive with the `unsized` flag"
+        );
+    }
+
+    #[test]
+    fn forwarded_trait_unsized() {
+        let args =
+            Args::from_args(&vec![parse_quote!(derive(Box)), parse_quote!(unsized)]).unwrap();
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                fn a(&self);
+                fn b(&mut self);
+            }
+        );
+        assert!(args.forwarded_trait(&trait_).is_ok());
+
+        for method in [
+            quote::quote!(fn c(self);),
+            quote::quote!(fn c(&self) -> Self;),
+            quote::quote!(fn c(&self) -> Option<Self>;),
+            quote::quote!(fn c(&self) where Self: Sized;),
+        ]
+        .iter()
+        {
+            let trait_: syn::ItemTrait = parse_quote!(
+                trait Trait {
+                    fn a(&self);
+                    #method
+                }
+            );
+            let err = args.forwarded_trait(&trait_).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "cannot derive `Box` with the `unsized` flag for a trait declaring methods requiring `Self: Sized`"
+            );
+        }
+    }
+
+    #[test]
+    fn update_impl_generic() {
+        // the default generic type of `Counter` is `C`, which must not be
+        // confused with the associated type of the same name
+        let args = quote::quote!(derive(Box), generic = "Inner", static, no_clippy_allow);
+        let input = quote::quote!(
+            trait Counter {
+                type C;
+                const C: u8;
+                fn get(&self) -> Self::C;
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        let expected: syn::Item = parse_quote!(
+            #[automatically_derived]
+            impl<Inner: Counter> Counter for std::boxed::Box<Inner>
+            where
+                Inner: 'static
+            {
+                type C = <Inner as Counter>::C;
+                const C: u8 = <Inner as Counter>::C;
+                #[inline]
+                fn get(&self) -> Self::C {
+                    (*(*self)).get()
+                }
+            }
+        );
+        assert_eq!(file.items[1], expected);
+    }
+
+    #[test]
+    fn forwarded_trait_generic() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait MyTrait<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(generic = "T"),
+        ])
+        .unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "generic type `T` is already declared by trait `MyTrait`"
+        );
+
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Deref)),
+            parse_quote!(generic = "P"),
+        ])
+        .unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "generic type `P` is already used by the `Deref` derive"
+        );
+
+        let err = Args::from_args(&vec![parse_quote!(generic = "Box<T>")])
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "expected identifier");
+    }
+
+    #[test]
+    fn update_impl_wrapper() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for std::rc::Rc<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Rc)),
+            parse_quote!(wrapper = "crate::Shared"),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                impl<T: Trait> Trait for crate::Shared<T> {}
+            )
+        );
+    }
+
+    #[test]
+    fn update_impl_inner() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait + std::clone::Clone> Trait for std::sync::Arc<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(ArcCow)),
+            parse_quote!(inner = "str"),
+            parse_quote!(static),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                impl Trait for std::sync::Arc<str> {}
+            )
+        );
+
+        let err = Args::from_args(&vec![
+            parse_quote!(derive(Box, BoxDyn)),
+            parse_quote!(inner = "str"),
+        ])
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "cannot combine `BoxDyn` with an inner type given with `inner = \"...\"`"
+        );
+
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(inner = "str"),
+            parse_quote!(generic = "Inner"),
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn from_args_boxed_alias() {
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(boxed_alias = "BoxedCounter"),
+        ])
+        .unwrap();
+        let expected: syn::Ident = parse_quote!(BoxedCounter);
+        assert_eq!(args.boxed_alias, Some(expected));
+
+        let err = Args::from_args(&vec![parse_quote!(boxed_alias = "Box<dyn Counter>")])
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "expected identifier");
+    }
+
+    #[test]
+    fn expand_boxed_alias() {
+        let args = quote::quote!(derive(Box), boxed_alias = "BoxedCounter");
+        let input = quote::quote!(
+            pub trait Counter {
+                fn get(&self) -> u32;
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert_eq!(file.items.len(), 3);
+        assert!(matches!(file.items[0], syn::Item::Trait(_)));
+        assert!(matches!(file.items[1], syn::Item::Type(ref t) if t.ident == "BoxedCounter"));
+        assert!(matches!(file.items[2], syn::Item::Impl(_)));
+    }
+
+    #[test]
+    fn from_args_box_dyn() {
+        let args = Args::from_args(&vec![parse_quote!(derive(Box, BoxDyn, BoxDynSendSync))]);
+        assert!(args.is_ok());
+
+        let args = Args::from_args(&vec![parse_quote!(derive(Box, BoxDyn)), parse_quote!(unsized)]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn from_args_wrapper_invalid_derive() {
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Ref)),
+            parse_quote!(wrapper = "crate::Shared"),
+        ]);
+        assert!(args.is_err());
+
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box, Rc)),
+            parse_quote!(wrapper = "crate::Shared"),
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn forwarded_trait_only() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                type Item;
+                fn a(&self);
+                fn b(&self);
+                fn c(&self) {}
+            }
+        );
+        let args =
+            Args::from_args(&vec![parse_quote!(derive(Box)), parse_quote!(only(a, b))]).unwrap();
+        let forwarded = args.forwarded_trait(&trait_).unwrap();
+        let expected: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                type Item;
+                fn a(&self);
+                fn b(&self);
+            }
+        );
+        assert_eq!(forwarded, expected);
+
+        let args =
+            Args::from_args(&vec![parse_quote!(derive(Box)), parse_quote!(only(a, d))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "no method `d` declared in trait `Trait`");
+
+        let args =
+            Args::from_args(&vec![parse_quote!(derive(Box)), parse_quote!(only(c))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot skip method `a` without a default implementation"
+        );
+    }
+
+    #[test]
+    fn forwarded_trait_skip() {
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(skip)]
+                const NAME: &'static str = "trait";
+                #[blanket(skip)]
+                type Error = std::io::Error;
+                #[doc(hidden)]
+                #[blanket(skip)]
+                fn a(&self) {}
+                fn b(&self);
+            }
+        );
+        let forwarded = args.forwarded_trait(&trait_).unwrap();
+        let expected: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                fn b(&self);
+            }
+        );
+        assert_eq!(forwarded, expected);
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(skip)]
+                const NAME: &'static str;
+            }
+        );
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot skip associated const `NAME` without a default value"
+        );
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(skip)]
+                type Error;
+            }
+        );
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot skip associated type `Error` without a default type"
+        );
+    }
+
+    #[test]
+    fn expand_ufcs() {
+        let input = quote::quote!(
+            pub trait Counter {
+                fn get(&self) -> u32;
+                fn add(&mut self, n: u32);
+            }
+        );
+
+        let args = quote::quote!(derive(Box), no_clippy_allow);
+        let output = super::expand(args, input.clone());
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert_eq!(
+            file.items[1],
+            parse_quote!(
+                #[automatically_derived]
+                impl<C: Counter> Counter for std::boxed::Box<C> {
+                    #[inline]
+                    fn get(&self) -> u32 {
+                        (*(*self)).get()
+                    }
+                    #[inline]
+                    fn add(&mut self, n: u32) {
+                        (*(*self)).add(n)
+                    }
+                }
+            )
+        );
+
+        let args = quote::quote!(derive(Box), ufcs, no_clippy_allow);
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert_eq!(file.items.len(), 2);
+        assert_eq!(
+            file.items[0],
+            parse_quote!(
+                pub trait Counter {
+                    fn get(&self) -> u32;
+                    fn add(&mut self, n: u32);
+                }
+            )
+        );
+        assert_eq!(
+            file.items[1],
+            parse_quote!(
+                #[automatically_derived]
+                impl<C: Counter> Counter for std::boxed::Box<C> {
+                    #[inline]
+                    fn get(&self) -> u32 {
+                        <C as Counter>::get(&(*(*self)))
+                    }
+                    #[inline]
+                    fn add(&mut self, n: u32) {
+                        <C as Counter>::add(&mut (*(*self)), n)
+                    }
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn expand_skip_receiver_mut() {
+        let args = quote::quote!(derive(Rc), no_clippy_allow);
+        let input = quote::quote!(
+            pub trait Store {
+                fn get(&self, key: &str) -> Option<u32>;
+                #[blanket(skip)]
+                fn set(&mut self, _key: &str, _value: u32) {}
+            }
+        );
+        let output = super::expand(args, input);
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert_eq!(file.items.len(), 2);
+        assert_eq!(
+            file.items[1],
+            parse_quote!(
+                #[automatically_derived]
+                impl<S: Store + ?Sized> Store for std::rc::Rc<S> {
+                    #[inline]
+                    fn get(&self, key: &str) -> Option<u32> {
+                        (*(*self)).get(key)
+                    }
+                }
+            )
+        );
+
+        let args = quote::quote!(derive(Rc));
+        let input = quote::quote!(
+            pub trait Store {
+                #[blanket(skip)]
+                fn set(&mut self, key: &str, value: u32);
+            }
+        );
+        let output = super::expand(args, input).to_string();
+        assert!(output.starts_with("pub trait Store"));
+        assert!(output.contains("cannot skip method `set` without a default implementation"));
+    }
+
+    #[test]
+    fn forwarded_trait_take() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(as = "take")]
+                fn a(&self);
+                fn b(&self);
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Cell))]).unwrap();
+        let forwarded = args.forwarded_trait(&trait_).unwrap();
+        assert_eq!(forwarded, trait_);
+
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "expected a `Cell` derive with `as = \"take\"`");
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(as = "take")]
+                const SIZE: usize;
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Cell))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "`as` is only supported on trait methods");
+    }
+
+    #[test]
+    fn forwarded_trait_via() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(via = "as_ref")]
+                fn a(&self);
+                fn b(&self);
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Box, Rc))]).unwrap();
+        let forwarded = args.forwarded_trait(&trait_).unwrap();
+        assert_eq!(forwarded, trait_);
+
+        let args = Args::from_args(&vec![parse_quote!(derive(Box, RcRefCell))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "cannot combine `RcRefCell` with `via = \"...\"`");
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(via = "as ref")]
+                fn a(&self);
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "expected method identifier");
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(via = "as_ref")]
+                type Item;
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "`via` is only supported on trait methods");
+    }
+
+    #[test]
+    fn forwarded_trait_lock() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(lock = "write")]
+                fn a(&self);
+                fn b(&self);
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(ArcRwLock))]).unwrap();
+        let forwarded = args.forwarded_trait(&trait_).unwrap();
+        assert_eq!(forwarded, trait_);
+
+        let args = Args::from_args(&vec![parse_quote!(derive(ArcRwLock, ArcMutex))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "cannot combine `ArcMutex` with `lock = \"...\"`");
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(lock = "upgradable")]
+                fn a(&self);
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(ArcRwLock))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "expected `read` or `write`");
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                #[blanket(lock = "write")]
+                const SIZE: usize;
+            }
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(ArcRwLock))]).unwrap();
+        let err = args.forwarded_trait(&trait_).unwrap_err();
+        assert_eq!(err.to_string(), "`lock` is only supported on trait methods");
+    }
+
+    #[test]
+    fn update_impl_copy_docs() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                /// Do something.
+                #[doc = " Do something."]
+                #[inline]
+                fn method(&self);
+            }
+        );
+        let item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait> Trait for Box<T> {
+                #[inline]
+                fn method(&self) {
+                    (*(*self)).method()
+                }
+            }
+        );
+
+        let mut copied = item.clone();
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(no_clippy_allow),
+            parse_quote!(copy_docs),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut copied);
+        assert_eq!(
+            copied,
+            parse_quote!(
+                impl<T: Trait> Trait for Box<T> {
+                    /// Do something.
+                    #[inline]
+                    fn method(&self) {
+                        (*(*self)).method()
+                    }
+                }
+            )
+        );
+
+        let mut uncopied = item.clone();
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut uncopied);
+        assert_eq!(uncopied, item);
+    }
+
+    #[test]
+    fn update_impl_dedup_bounds() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait<T: Send>: Send
+            where
+                Self: Send,
+            {
+            }
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            impl<T: Send, MT: Trait<T> + Send> Trait<T> for Box<MT>
+            where
+                Self: Send,
+                MT: Send,
+            {
+            }
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Box)),
+            parse_quote!(bound = "T: Send"),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        args.update_impl(&trait_, &mut item);
+        let expected: syn::WhereClause = parse_quote!(where Self: Send);
+        assert_eq!(item.generics.where_clause, Some(expected));
+    }
+
+    #[test]
+    fn update_impl_static() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait + ?Sized> Trait for std::rc::Rc<T> {}
+        );
+
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Rc)),
+            parse_quote!(static),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        let mut derived = item.clone();
+        args.update_impl(&trait_, &mut derived);
+        let expected: syn::WhereClause = parse_quote!(where T: 'static);
+        assert_eq!(derived.generics.where_clause, Some(expected));
+
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Rc)),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+        let mut derived = item.clone();
+        args.update_impl(&trait_, &mut derived);
+        assert_eq!(derived.generics.where_clause, None);
+    }
+
+    #[test]
+    fn update_impl_thread_safe() {
+        let item: syn::ItemImpl = parse_quote!(
+            impl<T: Trait + ?Sized> Trait for std::rc::Rc<T> {}
+        );
+        let args = Args::from_args(&vec![
+            parse_quote!(derive(Rc)),
+            parse_quote!(thread_safe),
+            parse_quote!(no_clippy_allow),
+        ])
+        .unwrap();
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {}
+        );
+        let mut derived = item.clone();
+        args.update_impl(&trait_, &mut derived);
+        let expected: syn::WhereClause = parse_quote!(where T: std::marker::Send + std::marker::Sync);
+        assert_eq!(derived.generics.where_clause, Some(expected));
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait: std::marker::Send {}
+        );
+        let mut derived = item.clone();
+        args.update_impl(&trait_, &mut derived);
+        let expected: syn::WhereClause = parse_quote!(where T: std::marker::Sync);
+        assert_eq!(derived.generics.where_clause, Some(expected));
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait: Sync + Send {}
+        );
+        let mut derived = item.clone();
+        args.update_impl(&trait_, &mut derived);
+        assert_eq!(derived.generics.where_clause, None);
+    }
+
+    #[test]
+    fn from_args_unknown_derive() {
+        let err = Args::from_args(&vec![parse_quote!(derive(Boxx))])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("did you mean `Box`?"));
+        for name in ["`Box`", "`Ref`", "`Mut`", "`Rc`", "`Deref`", "`BoxDyn`"].iter() {
+            assert!(err.contains(name));
+        }
+
+        let err = Args::from_args(&vec![parse_quote!(derive(Clone))])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(!err.contains("did you mean"));
+        assert!(err.contains("`Box`"));
+    }
+
+    #[test]
+    fn from_args_unexpected() {
+        let err = Args::from_args(&vec![parse_quote!(derive)]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "expected a list of blanket derives, as in `derive(Box)`"
+        );
+
+        let err = Args::from_args(&vec![parse_quote!(drive(Box))]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "unexpected argument `drive`, did you mean `derive`?"
+        );
+
+        let err = Args::from_args(&vec![parse_quote!(frobnicate)]).err().unwrap();
+        assert_eq!(err.to_string(), "unexpected argument `frobnicate`");
+    }
+
+    #[test]
+    fn from_args_empty_derive() {
+        let err = Args::from_args(&vec![parse_quote!(derive())])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.starts_with("expected at least one blanket derive"));
+        assert!(err.contains("`Box`"));
+    }
+
+    #[test]
+    fn update_impl_cfg() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            #[cfg(feature = "x")]
+            #[cfg_attr(feature = "y", allow(unused))]
+            #[cfg_attr(feature = "z", cfg(unix))]
+            /// A trait.
+            trait Trait {}
+        );
+        let mut item: syn::ItemImpl = parse_quote!(
+            #[automatically_derived]
+            impl<T: Trait> Trait for Box<T> {}
+        );
+        let args = Args::from_args(&vec![parse_quote!(derive(Box))]).unwrap();
+        args.update_impl(&trait_, &mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                #[cfg(feature = "x")]
+                #[cfg_attr(feature = "z", cfg(unix))]
+                #[automatically_derived]
+                #[allow(clippy::all)]
+                impl<T: Trait> Trait for Box<T> {}
+            )
+        );
+    }
 }
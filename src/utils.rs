@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use quote::quote;
 use quote::quote_spanned;
+use quote::ToTokens;
 use syn::parse_quote;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 use syn::GenericParam;
 use syn::Token;
 
@@ -49,16 +55,40 @@ pub fn signature_to_function_call(sig: &syn::Signature) -> syn::Result<syn::Expr
 }
 
 /// Convert a function signature to a method call with the same arguments.
+///
+/// Given `fn get<T, const N: usize>(&self, key: &str) -> T`, get the method
+/// call `self.get::<T, N>(key)`, whose receiver is meant to be wrapped with
+/// [`deref_expr`] by the caller. A typed `self` first argument is treated as
+/// the receiver as well. Methods with an explicit ABI or a variadic parameter
+/// cannot be forwarded, and neither can arguments bound to a pattern: these
+/// return an error spanning the offending part of the signature.
+///
+/// # Panics
+///
+/// Panics if the signature has no `self` receiver, which callers are expected
+/// to check for beforehand.
 pub fn signature_to_method_call(sig: &syn::Signature) -> syn::Result<syn::ExprMethodCall> {
+    // Reject signatures which cannot be forwarded
+    if let Some(ref abi) = sig.abi {
+        let msg = "cannot forward methods with an explicit ABI";
+        return Err(syn::Error::new(abi.span(), msg));
+    }
+    if let Some(ref variadic) = sig.variadic {
+        let msg = "cannot forward methods with a variadic parameter";
+        return Err(syn::Error::new(variadic.span(), msg));
+    }
+
     // Extract receiver
     let receiver = sig.receiver().unwrap();
     let span = receiver.span();
 
     // Extract arguments
     let mut funcargs = Punctuated::new();
-    for item in &sig.inputs {
+    for (i, item) in sig.inputs.iter().enumerate() {
         match item {
             syn::FnArg::Receiver(_) => {}
+            // a typed receiver such as `self: Pin<&mut Self>`
+            syn::FnArg::Typed(_) if i == 0 && is_typed_self(item) => {}
             syn::FnArg::Typed(argty) => {
                 if let syn::Pat::Ident(ref id) = *argty.pat {
                     let argpath = syn::ExprPath {
@@ -74,6 +104,29 @@ pub fn signature_to_method_call(sig: &syn::Signature) -> syn::Result<syn::ExprMe
         }
     }
 
+    // Forward the type and const generics of the method with a turbofish,
+    // since they cannot always be inferred from the arguments
+    let mut generic_args = Punctuated::new();
+    for param in sig.generics.params.iter() {
+        // const parameters are emitted as paths, which also parse as types
+        let ident = match param {
+            syn::GenericParam::Type(t) => &t.ident,
+            syn::GenericParam::Const(c) => &c.ident,
+            syn::GenericParam::Lifetime(_) => continue,
+        };
+        generic_args.push(syn::GenericMethodArgument::Type(parse_quote!(#ident)));
+    }
+    let turbofish = if generic_args.is_empty() {
+        None
+    } else {
+        Some(syn::MethodTurbofish {
+            colon2_token: Default::default(),
+            lt_token: Default::default(),
+            args: generic_args,
+            gt_token: Default::default(),
+        })
+    };
+
     // Write the method call
     Ok(syn::ExprMethodCall {
         attrs: Vec::new(),
@@ -82,7 +135,7 @@ pub fn signature_to_method_call(sig: &syn::Signature) -> syn::Result<syn::ExprMe
             spans: [sig.span()],
         },
         method: sig.ident.clone(),
-        turbofish: None,
+        turbofish,
         paren_token: syn::token::Paren { span: sig.span() },
         args: funcargs,
     })
@@ -101,6 +154,9 @@ pub fn prepend_function_path(call: &mut syn::ExprCall, module: syn::Path) -> syn
 }
 
 /// Deref an expression and wrap it in brackets to preserve operation priority.
+///
+/// Given `self`, get `(*self)`; applied twice, get `(*(*self))`, which is how
+/// the derives reach the wrapped value behind a reference receiver.
 pub fn deref_expr(expr: syn::Expr) -> syn::Expr {
     syn::Expr::Paren(syn::ExprParen {
         attrs: Vec::new(),
@@ -113,104 +169,3080 @@ pub fn deref_expr(expr: syn::Expr) -> syn::Expr {
     })
 }
 
-/// Build a generic identifier suitable for the given trait.
+/// Dereference the arguments of a forwarding call typed `Self`, `&Self` or
+/// `&mut Self`, so that they are passed to the wrapped type.
 ///
-/// This function extracts the initials of the trait identifier. If this results
-/// in a generic type identifier already present in the generics of that trait,
-/// as many underscores are added to the end of the identifier.
-pub fn trait_to_generic_ident(trait_: &syn::ItemTrait) -> syn::Ident {
-    let mut raw = trait_
-        .ident
-        .to_string()
-        .chars()
-        .filter(|c| c.is_uppercase())
-        .collect::<String>();
-    loop {
-        if !trait_.generics.params.iter().any(|g| match g {
-            syn::GenericParam::Type(param) if param.ident == raw => true,
-            syn::GenericParam::Const(param) if param.ident == raw => true,
+/// Given `other: &Self`, the argument becomes `&**other`. Arguments taken by
+/// value or by mutable reference can only be dereferenced if the pointer
+/// owns its value, as `Box` does: otherwise an error is returned, using
+/// `derive` as the name of the derive in the error message.
+pub fn deref_self_arguments(
+    sig: &syn::Signature,
+    call: &mut syn::ExprMethodCall,
+    derive: &str,
+    owned: bool,
+) -> syn::Result<()> {
+    // the receiver is not part of the call arguments
+    let inputs = sig.inputs.iter().skip(1);
+    for (input, arg) in inputs.zip(call.args.iter_mut()) {
+        let ty = match input {
+            syn::FnArg::Typed(pat) => &*pat.ty,
+            syn::FnArg::Receiver(_) => continue,
+        };
+        match ty {
+            syn::Type::Reference(r) if is_self_type(&r.elem) && r.mutability.is_none() => {
+                *arg = parse_quote!(&**#arg);
+            }
+            syn::Type::Reference(r) if is_self_type(&r.elem) => {
+                if !owned {
+                    let msg = format!(
+                        "cannot derive `{}` for a trait declaring methods with `&mut Self` arguments",
+                        derive
+                    );
+                    return Err(syn::Error::new(ty.span(), msg));
+                }
+                *arg = parse_quote!(&mut **#arg);
+            }
+            ty if is_self_type(ty) => {
+                if !owned {
+                    let msg = format!(
+                        "cannot derive `{}` for a trait declaring methods with `Self` arguments",
+                        derive
+                    );
+                    return Err(syn::Error::new(ty.span(), msg));
+                }
+                *arg = parse_quote!(*#arg);
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Check whether the methods of a trait may have the same name as methods of
+/// its supertraits, which is the case for any supertrait other than the
+/// marker traits of the standard library.
+pub fn has_method_supertraits(trait_: &syn::ItemTrait) -> bool {
+    trait_.supertraits.iter().any(|bound| match bound {
+        syn::TypeParamBound::Trait(t) => {
+            let markers = ["Send", "Sync", "Sized", "Copy", "Unpin"];
+            let marker = t
+                .path
+                .segments
+                .last()
+                .map(|s| markers.iter().any(|m| s.ident == m))
+                .unwrap_or(false);
+            !marker && !matches!(t.modifier, syn::TraitBoundModifier::Maybe(_))
+        }
+        syn::TypeParamBound::Lifetime(_) => false,
+    })
+}
+
+/// Rewrite a forwarding call as a fully qualified call if it may be ambiguous.
+///
+/// A method of the trait with the same name as a method of a supertrait is
+/// ambiguous in method call syntax, and any method may be shadowed by an
+/// inherent method of the wrapped type. For traits with supertraits, or
+/// when given the `ufcs` flag, given
+/// `(*(*self)).get::<K>(key)` forwarding `fn get<K>(&self, key: K)`, get
+/// `<T as Trait>::get::<K>(&(*(*self)), key)`: the receiver is borrowed the
+/// way the method call would, and dereferenced to the generic type of the
+/// implementation by the argument coercion. If no generic type is given, the
+/// self type is instead inferred from the receiver, as for trait objects.
+pub fn disambiguate_method_call(
+    trait_: &syn::ItemTrait,
+    sig: &syn::Signature,
+    call: syn::ExprMethodCall,
+    generic_type: Option<&syn::Ident>,
+    ufcs: bool,
+) -> syn::Result<syn::Expr> {
+    if !has_method_supertraits(trait_) && !ufcs {
+        return Ok(syn::Expr::MethodCall(call));
+    }
+
+    let receiver = &call.receiver;
+    let receiver: syn::Expr = match sig.receiver() {
+        Some(syn::FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_some() => {
+            parse_quote!(&mut #receiver)
+        }
+        Some(syn::FnArg::Receiver(r)) if r.reference.is_some() => parse_quote!(&#receiver),
+        _ => (**receiver).clone(),
+    };
+    let self_ty: syn::Type = match generic_type {
+        Some(generic_type) => parse_quote!(#generic_type),
+        None => parse_quote!(_),
+    };
+    let trait_ident = &trait_.ident;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let method = &call.method;
+    let turbofish = &call.turbofish;
+    let args = call.args.iter();
+    Ok(parse_quote!(
+        <#self_ty as #trait_ident #trait_generic_names>::#method #turbofish(#receiver #(, #args)*)
+    ))
+}
+
+/// Build the body of a forwarding method from the forwarding call.
+///
+/// The call is awaited if the signature declares an `async fn`, since the
+/// forwarding method must return the output of the inner future and not the
+/// future itself. It is wrapped in an `unsafe` block if the signature
+/// declares an `unsafe fn`.
+pub fn forwarding_body<E: Into<syn::Expr>>(sig: &syn::Signature, call: E) -> syn::Expr {
+    let mut body = call.into();
+    if sig.asyncness.is_some() {
+        body = parse_quote!(#body.await);
+    }
+    if sig.unsafety.is_some() {
+        body = parse_quote!(unsafe { #body });
+    }
+    body
+}
+
+/// The handling of a trait method attribute by its forwarding method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeKind {
+    /// Attributes changing how the method is compiled or called, which are
+    /// copied: `#[cfg]`, `#[cfg_attr]`, `#[track_caller]`, and the lint levels
+    /// `#[allow]`, `#[warn]` and `#[deny]`, since the forwarding method has the
+    /// same signature as the trait method and triggers the same lints.
+    Semantic,
+    /// Lint attributes checked against the trait method declaration, which
+    /// are rejected on the methods of a trait implementation: `#[must_use]`
+    /// and `#[deprecated]`.
+    Lint,
+    /// Attributes without effect on the forwarding method, such as `#[doc]`
+    /// or `#[inline]`, which are only copied with the `copy_docs` flag for
+    /// the documentation. `#[forbid]` and `#[expect]` are not copied either,
+    /// since the forwarding code may not fulfill them.
+    Cosmetic,
+    /// Attributes of other tools or attribute macros, which are dropped.
+    Unknown,
+}
+
+/// Classify a trait method attribute by its handling in forwarding methods.
+pub fn attribute_kind(attr: &syn::Attribute) -> AttributeKind {
+    let name = match attr.path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => return AttributeKind::Unknown,
+    };
+    match name.as_str() {
+        "cfg" | "cfg_attr" | "track_caller" | "allow" | "warn" | "deny" => {
+            AttributeKind::Semantic
+        }
+        "must_use" | "deprecated" => AttributeKind::Lint,
+        "doc" | "forbid" | "expect" | "inline" | "cold" => AttributeKind::Cosmetic,
+        _ => AttributeKind::Unknown,
+    }
+}
+
+/// Extract the attributes of a trait method to keep on its forwarding method.
+///
+/// Conditional compilation attributes (`#[cfg]` and `#[cfg_attr]`) are
+/// kept, so that the generated method exists under the same configuration
+/// as the trait method it forwards to.
+///
+/// `#[track_caller]` is copied as well, so that the caller location is
+/// propagated through the forwarding method to the wrapped implementation,
+/// and so are the lint levels, such as `#[allow(clippy::too_many_arguments)]`,
+/// so that the forwarding method is linted like the trait method.
+///
+/// Lint attributes such as `#[must_use]` or `#[deprecated]` are checked by
+/// `rustc` against the trait method declaration, and are rejected on the
+/// methods of a trait implementation, so they are not copied. However, a
+/// deprecated method gets an `#[allow(deprecated)]` attribute so that the
+/// forwarding call does not trigger the lint inside the generated code. The
+/// same goes for the attributes of a `#[cfg_attr]`, so that the forwarding
+/// method matches the trait method under every configuration.
+///
+/// See [`AttributeKind`] for the handling of the other attributes.
+pub fn forwarded_attributes(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    let mut forwarded: Vec<syn::Attribute> = Vec::new();
+    let mut deprecated = false;
+    for attr in attrs.iter() {
+        match attribute_kind(attr) {
+            AttributeKind::Semantic if attr.path.is_ident("cfg_attr") => {
+                // attributes which cannot be parsed as a meta are kept as they are
+                match attr.parse_meta() {
+                    Ok(syn::Meta::List(ref list)) => {
+                        if let Some(list) = forwarded_cfg_attr(list) {
+                            forwarded.push(parse_quote!(#[#list]));
+                        }
+                    }
+                    _ => forwarded.push(attr.clone()),
+                }
+            }
+            AttributeKind::Semantic => forwarded.push(attr.clone()),
+            AttributeKind::Lint => deprecated |= attr.path.is_ident("deprecated"),
+            AttributeKind::Cosmetic | AttributeKind::Unknown => (),
+        }
+    }
+    if deprecated {
+        forwarded.push(parse_quote!(#[allow(deprecated)]));
+    }
+    forwarded
+}
+
+/// Rewrite the attributes of a `#[cfg_attr]` for a forwarding method.
+///
+/// `deprecated` becomes `allow(deprecated)` and `must_use` is removed, as
+/// in [`forwarded_attributes`]; `None` is returned if no attribute is left.
+fn forwarded_cfg_attr(list: &syn::MetaList) -> Option<syn::MetaList> {
+    let mut nested = list.nested.iter();
+    let predicate = nested.next()?;
+    let attrs = nested
+        .filter_map(|meta| match meta {
+            syn::NestedMeta::Meta(syn::Meta::List(ref l)) if l.path.is_ident("cfg_attr") => {
+                forwarded_cfg_attr(l).map(|l| syn::NestedMeta::Meta(syn::Meta::List(l)))
+            }
+            syn::NestedMeta::Meta(ref m) if m.path().is_ident("deprecated") => {
+                Some(parse_quote!(allow(deprecated)))
+            }
+            syn::NestedMeta::Meta(ref m) if m.path().is_ident("must_use") => None,
+            _ => Some(meta.clone()),
+        })
+        .collect::<Vec<syn::NestedMeta>>();
+    if attrs.is_empty() {
+        None
+    } else {
+        Some(parse_quote!(cfg_attr(#predicate, #(#attrs),*)))
+    }
+}
+
+/// Extract the conditional compilation attributes of a trait to gate its
+/// derived implementations with.
+///
+/// `#[cfg]` attributes are copied as they are. The other attributes of a
+/// `#[cfg_attr]` apply to the trait declaration and not to its
+/// implementations, so only its `cfg` attributes are kept: given
+/// `#[cfg_attr(unix, cfg(feature = "fs"), allow(unused))]`, get
+/// `#[cfg_attr(unix, cfg(feature = "fs"))]`.
+pub fn cfg_attributes(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    let mut cfgs: Vec<syn::Attribute> = Vec::new();
+    for attr in attrs.iter() {
+        if attr.path.is_ident("cfg") {
+            cfgs.push(attr.clone());
+        } else if attr.path.is_ident("cfg_attr") {
+            if let Ok(syn::Meta::List(ref list)) = attr.parse_meta() {
+                if let Some(list) = cfg_attr_cfgs(list) {
+                    cfgs.push(parse_quote!(#[#list]));
+                }
+            }
+        }
+    }
+    cfgs
+}
+
+/// Keep only the `cfg` attributes of a `#[cfg_attr]`, including the ones of
+/// nested `cfg_attr`; `None` is returned if no attribute is left.
+fn cfg_attr_cfgs(list: &syn::MetaList) -> Option<syn::MetaList> {
+    let mut nested = list.nested.iter();
+    let predicate = nested.next()?;
+    let attrs = nested
+        .filter_map(|meta| match meta {
+            syn::NestedMeta::Meta(syn::Meta::List(ref l)) if l.path.is_ident("cfg_attr") => {
+                cfg_attr_cfgs(l).map(|l| syn::NestedMeta::Meta(syn::Meta::List(l)))
+            }
+            syn::NestedMeta::Meta(ref m) if m.path().is_ident("cfg") => Some(meta.clone()),
+            _ => None,
+        })
+        .collect::<Vec<syn::NestedMeta>>();
+    if attrs.is_empty() {
+        None
+    } else {
+        Some(parse_quote!(cfg_attr(#predicate, #(#attrs),*)))
+    }
+}
+
+/// Extract the documentation string of a `#[doc = "..."]` attribute.
+///
+/// Doc comments are desugared to such attributes, so `/// text` and
+/// `#[doc = " text"]` return the same string.
+pub fn doc_string(attr: &syn::Attribute) -> Option<String> {
+    if !attr.path.is_ident("doc") {
+        return None;
+    }
+    match attr.parse_meta() {
+        Ok(syn::Meta::NameValue(syn::MetaNameValue {
+            lit: syn::Lit::Str(s),
+            ..
+        })) => Some(s.value()),
+        _ => None,
+    }
+}
+
+/// Check whether an attribute is an invocation of the `async_trait` macro.
+///
+/// Only the `#[async_trait]` and `#[async_trait::async_trait]` paths are
+/// recognized, so that unrelated macros with the same name are not.
+pub fn is_async_trait_attribute(attr: &syn::Attribute) -> bool {
+    let segments = attr.path.segments.iter().map(|s| &s.ident).collect::<Vec<_>>();
+    match segments.as_slice() {
+        [name] => attr.path.leading_colon.is_none() && *name == "async_trait",
+        [krate, name] => *krate == "async_trait" && *name == "async_trait",
+        _ => false,
+    }
+}
+
+/// Build the bounds required by `#[async_trait]` on an implementation.
+///
+/// The futures of the rewritten methods borrow or own the receiver, and
+/// are `Send` unless the attribute is given `?Send`: the implementing type
+/// must be `Sync` for `&self` methods and `Send` for the other ones.
+pub fn async_trait_bounds(
+    trait_: &syn::ItemTrait,
+    attr: &syn::Attribute,
+) -> Vec<syn::WherePredicate> {
+    let mut bounds = Vec::new();
+    if attr.tokens.to_string().replace(' ', "") == "(?Send)" {
+        return bounds;
+    }
+    let (mut sync, mut send) = (false, false);
+    for item in trait_.items.iter() {
+        if let syn::TraitItem::Method(ref m) = item {
+            match m.sig.receiver() {
+                _ if m.sig.asyncness.is_none() => (),
+                Some(syn::FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_none() => {
+                    sync = true
+                }
+                Some(arg) if is_pointer_receiver(arg, "Arc") => {
+                    sync = true;
+                    send = true;
+                }
+                Some(_) => send = true,
+                None => (),
+            }
+        }
+    }
+    if sync {
+        bounds.push(parse_quote!(Self: std::marker::Sync));
+    }
+    if send {
+        bounds.push(parse_quote!(Self: std::marker::Send));
+    }
+    bounds
+}
+
+/// Check whether an attribute of a trait item is a `#[blanket(...)]` attribute.
+pub fn is_blanket_attribute(attr: &syn::Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .map(|segment| segment.ident == "blanket")
+        .unwrap_or(false)
+}
+
+/// Get the attributes of a trait item, if it can have any.
+pub fn trait_item_attributes(item: &syn::TraitItem) -> Option<&Vec<syn::Attribute>> {
+    match item {
+        syn::TraitItem::Const(c) => Some(&c.attrs),
+        syn::TraitItem::Method(m) => Some(&m.attrs),
+        syn::TraitItem::Type(t) => Some(&t.attrs),
+        syn::TraitItem::Macro(m) => Some(&m.attrs),
+        _ => None,
+    }
+}
+
+/// Check whether a trait item is excluded from the derived implementations.
+///
+/// An item is excluded with a `#[blanket(skip)]` attribute. A method may
+/// also have a `#[blanket(as = "take")]`, `#[blanket(via = "...")]` or
+/// `#[blanket(lock = "...")]` attribute, see [`take_attribute`],
+/// [`via_attribute`] and [`lock_attribute`]; any other `#[blanket(...)]`
+/// attribute on a trait item is reported as an error.
+pub fn is_skipped(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut skipped = false;
+    for attr in attrs.iter().filter(|attr| is_blanket_attribute(attr)) {
+        match attr.parse_meta()? {
+            syn::Meta::List(ref l)
+                if l.nested.len() == 1 && l.nested[0].to_token_stream().to_string() == "skip" =>
+            {
+                skipped = true
+            }
+            syn::Meta::List(ref l) if l.nested.len() == 1 && is_take_argument(&l.nested[0]) => (),
+            syn::Meta::List(ref l) if l.nested.len() == 1 && is_via_argument(&l.nested[0]) => (),
+            syn::Meta::List(ref l) if l.nested.len() == 1 && is_lock_argument(&l.nested[0]) => (),
+            meta => {
+                let msg = "expected `skip`, `as = \"take\"`, `via = \"...\"` or `lock = \"...\"`";
+                return Err(syn::Error::new(meta.span(), msg));
+            }
+        }
+    }
+    Ok(skipped)
+}
+
+/// Check whether a `#[blanket(...)]` argument is `as = "take"`.
+fn is_take_argument(arg: &syn::NestedMeta) -> bool {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(ref n)) => {
+            n.path.to_token_stream().to_string() == "as"
+                && matches!(n.lit, syn::Lit::Str(ref s) if s.value() == "take")
+        }
+        _ => false,
+    }
+}
+
+/// Get the `#[blanket(as = "take")]` attribute of a trait method, if any.
+///
+/// The `Cell` derive forwards such a method by taking the value out of the
+/// cell, leaving the default value in its place, instead of copying it.
+pub fn take_attribute(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| is_blanket_attribute(attr))
+        .find(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(ref l)) => l.nested.len() == 1 && is_take_argument(&l.nested[0]),
             _ => false,
-        }) {
-            break;
-        } else {
-            raw.push('_');
+        })
+}
+
+/// Check whether a `#[blanket(...)]` argument is `via = "..."`.
+fn is_via_argument(arg: &syn::NestedMeta) -> bool {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(ref n)) => {
+            n.path.to_token_stream().to_string() == "via" && matches!(n.lit, syn::Lit::Str(_))
         }
+        _ => false,
     }
+}
 
-    syn::Ident::new(&raw, trait_.ident.span())
+/// Get the `#[blanket(via = "...")]` attribute of a trait method, if any.
+pub fn via_attribute(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| is_blanket_attribute(attr))
+        .find(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(ref l)) => l.nested.len() == 1 && is_via_argument(&l.nested[0]),
+            _ => false,
+        })
 }
 
-/// Convert a generic type declaration to a generic with the same arguments.
+/// Get the adapter method given with `#[blanket(via = "...")]`, if any.
 ///
-/// Given a generic section `<T: 'static + Send>`, get simply `<T>`.
-pub fn generics_declaration_to_generics(
-    generics: &Punctuated<GenericParam, Token![,]>,
-) -> syn::Result<Punctuated<GenericParam, Token![,]>> {
-    generics
+/// Given `#[blanket(via = "as_ref")]`, get `as_ref`: the derives supporting
+/// the attribute forward the method with `self.as_ref().method(...)` instead
+/// of dereferencing the receiver. Methods taking `self` by value cannot be
+/// forwarded through an adapter, which only borrows the wrapper type.
+pub fn via_adapter(
+    attrs: &[syn::Attribute],
+    sig: &syn::Signature,
+) -> syn::Result<Option<syn::Ident>> {
+    let attr = match via_attribute(attrs) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    if let Some(syn::FnArg::Receiver(r)) = sig.receiver() {
+        if r.reference.is_none() {
+            let msg = "cannot use `via = \"...\"` on a method taking `self` by value";
+            return Err(syn::Error::new(attr.path.span(), msg));
+        }
+    }
+    match attr.parse_meta()? {
+        syn::Meta::List(ref l) => match l.nested[0] {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(ref s),
+                ..
+            })) => match s.parse::<syn::Ident>() {
+                Ok(ident) => Ok(Some(ident)),
+                Err(_) => Err(syn::Error::new(s.span(), "expected method identifier")),
+            },
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Check whether a `#[blanket(...)]` argument is `lock = "..."`.
+fn is_lock_argument(arg: &syn::NestedMeta) -> bool {
+    match arg {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(ref n)) => {
+            n.path.to_token_stream().to_string() == "lock" && matches!(n.lit, syn::Lit::Str(_))
+        }
+        _ => false,
+    }
+}
+
+/// Get the `#[blanket(lock = "...")]` attribute of a trait method, if any.
+pub fn lock_attribute(attrs: &[syn::Attribute]) -> Option<&syn::Attribute> {
+    attrs
         .iter()
-        .map(|gen| match gen {
-            syn::GenericParam::Type(t) => Ok(syn::GenericParam::Type(syn::TypeParam {
-                attrs: t.attrs.clone(),
-                ident: t.ident.clone(),
-                colon_token: None,
-                bounds: Punctuated::new(),
-                eq_token: None,
-                default: None,
-            })),
-            syn::GenericParam::Lifetime(l) => Ok(syn::GenericParam::Lifetime(syn::LifetimeDef {
-                attrs: l.attrs.clone(),
-                lifetime: l.lifetime.clone(),
-                colon_token: None,
-                bounds: Punctuated::new(),
-            })),
-            syn::GenericParam::Const(c) => {
-                Err(syn::Error::new(c.span(), "cannot handle const generics"))
-            }
+        .filter(|attr| is_blanket_attribute(attr))
+        .find(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(ref l)) => l.nested.len() == 1 && is_lock_argument(&l.nested[0]),
+            _ => false,
         })
-        .collect()
 }
 
-#[cfg(test)]
-mod tests {
+/// Get the lock kind given with `#[blanket(lock = "...")]`, if any.
+///
+/// Given `#[blanket(lock = "write")]`, get `true`, and given
+/// `#[blanket(lock = "read")]`, get `false`: the `ArcRwLock` derive takes the
+/// given lock instead of the one matching the receiver of the method.
+pub fn lock_kind(attrs: &[syn::Attribute]) -> syn::Result<Option<bool>> {
+    let attr = match lock_attribute(attrs) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    match attr.parse_meta()? {
+        syn::Meta::List(ref l) => match l.nested[0] {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(ref s),
+                ..
+            })) => match s.value().as_str() {
+                "read" => Ok(Some(false)),
+                "write" => Ok(Some(true)),
+                _ => Err(syn::Error::new(s.span(), "expected `read` or `write`")),
+            },
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
 
-    use syn::parse_quote;
+/// Remove the `#[blanket(...)]` attributes of a trait item.
+pub fn strip_item_attributes(item: &mut syn::TraitItem) {
+    let attrs = match item {
+        syn::TraitItem::Const(c) => &mut c.attrs,
+        syn::TraitItem::Method(m) => &mut m.attrs,
+        syn::TraitItem::Type(t) => &mut t.attrs,
+        syn::TraitItem::Macro(m) => &mut m.attrs,
+        _ => return,
+    };
+    attrs.retain(|attr| !is_blanket_attribute(attr));
+}
 
-    #[test]
-    fn prepend_function_path() {
-        let path = parse_quote!(crate::qualified::path);
-        let mut call = parse_quote!(myfunction(arg1, arg2));
-        super::prepend_function_path(&mut call, path).unwrap();
-        assert_eq!(
-            call,
-            parse_quote!(crate::qualified::path::myfunction(arg1, arg2))
-        );
+/// Compute the Levenshtein distance between two strings.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut row = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + (ca != *cb) as usize;
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
     }
+    row[b.len()]
+}
 
-    #[test]
-    fn deref_expr() {
-        let expr = parse_quote!(self);
-        let dereffed = super::deref_expr(expr);
-        assert_eq!(dereffed, parse_quote!((*self)));
+/// Render a type the way it is usually written, for use in error messages.
+pub fn type_to_string(ty: &syn::Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace("& ", "&")
+        .replace(" ,", ",")
+}
+
+/// Check whether a type is a path into a module of the standard library,
+/// such as `std::boxed::Box<T>` for the `boxed` module.
+pub fn is_std_path(ty: &syn::Type, module: &str) -> bool {
+    match ty {
+        syn::Type::Path(ref ty) => {
+            let idents = ty.path.segments.iter().map(|s| s.ident.to_string());
+            idents.take(2).eq(["std", module].iter().map(|s| s.to_string()))
+        }
+        _ => false,
     }
+}
 
-    #[test]
-    fn trait_to_generic_ident() {
-        let trait_ = syn::parse_quote!(
-            trait Trait {}
-        );
-        let expected: syn::Ident = syn::parse_quote!(T);
-        assert_eq!(super::trait_to_generic_ident(&trait_), expected);
+/// Check whether a type is the `Self` type.
+pub fn is_self_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ref ty) => ty.qself.is_none() && ty.path.is_ident("Self"),
+        _ => false,
+    }
+}
 
-        let trait_ = syn::parse_quote!(
-            trait SomeTrait {}
-        );
-        let expected: syn::Ident = syn::parse_quote!(ST);
-        assert_eq!(super::trait_to_generic_ident(&trait_), expected);
+/// Check whether a type mentions the `Self` type, such as `(Self, u8)`.
+///
+/// Paths into `Self`, such as `Self::Item`, do not mention the `Self` type
+/// itself.
+fn contains_self_type(ty: &syn::Type) -> bool {
+    match ty {
+        ty if is_self_type(ty) => true,
+        syn::Type::Path(ref ty) => ty.path.segments.iter().any(|s| match s.arguments {
+            syn::PathArguments::AngleBracketed(ref args) => args.args.iter().any(|a| match a {
+                syn::GenericArgument::Type(ref t) => contains_self_type(t),
+                _ => false,
+            }),
+            _ => false,
+        }),
+        syn::Type::Tuple(ref t) => t.elems.iter().any(contains_self_type),
+        syn::Type::Array(ref a) => contains_self_type(&a.elem),
+        syn::Type::Slice(ref s) => contains_self_type(&s.elem),
+        syn::Type::Paren(ref p) => contains_self_type(&p.elem),
+        syn::Type::Group(ref g) => contains_self_type(&g.elem),
+        syn::Type::Reference(ref r) => contains_self_type(&r.elem),
+        _ => false,
+    }
+}
 
-        let trait_ = syn::parse_quote!(
-            trait Trait<T> {}
+/// Convert the `Self` values of the given type from the wrapped type to the
+/// wrapper type, or get `None` if they cannot be converted.
+fn wrap_self_value(ty: &syn::Type, value: syn::Expr) -> Option<syn::Expr> {
+    match ty {
+        ty if !contains_self_type(ty) => Some(value),
+        ty if is_self_type(ty) => Some(parse_quote!(std::convert::From::from(#value))),
+        syn::Type::Paren(ref p) => wrap_self_value(&p.elem, value),
+        syn::Type::Group(ref g) => wrap_self_value(&g.elem, value),
+        // the tuple is destructured to convert its elements one by one
+        syn::Type::Tuple(ref t) => {
+            let names = (0..t.elems.len())
+                .map(|i| quote::format_ident!("__{}", i))
+                .collect::<Vec<_>>();
+            let elems = t
+                .elems
+                .iter()
+                .zip(names.iter())
+                .map(|(ty, name)| wrap_self_value(ty, parse_quote!(#name)))
+                .collect::<Option<Vec<_>>>()?;
+            let comma = if names.len() == 1 { Some(<Token![,]>::default()) } else { None };
+            Some(parse_quote!({
+                let (#(#names),* #comma) = #value;
+                (#(#elems),* #comma)
+            }))
+        }
+        // only the value of a `Result` or an `Option` can be converted
+        syn::Type::Path(ref ty) if ty.qself.is_none() => {
+            let segment = ty.path.segments.last()?;
+            if segment.ident != "Result" && segment.ident != "Option" {
+                return None;
+            }
+            let mut types = match segment.arguments {
+                syn::PathArguments::AngleBracketed(ref args) => {
+                    args.args.iter().filter_map(|a| match a {
+                        syn::GenericArgument::Type(ref t) => Some(t),
+                        _ => None,
+                    })
+                }
+                _ => return None,
+            };
+            let inner = types.next()?;
+            if types.any(contains_self_type) {
+                return None;
+            }
+            if is_self_type(inner) {
+                Some(parse_quote!((#value).map(std::convert::From::from)))
+            } else {
+                let wrapped = wrap_self_value(inner, parse_quote!(__value))?;
+                Some(parse_quote!((#value).map(|__value| #wrapped)))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Convert the value returned by a forwarded method to the wrapper type
+/// wherever the method signature returns `Self`.
+///
+/// `Self` can be returned as it is, as the value of a `Result` or an
+/// `Option`, or in a tuple such as `(Self, Self)`, possibly nested. The
+/// other types mentioning `Self`, such as `Vec<Self>`, are rejected.
+pub fn wrap_returned_self(
+    sig: &syn::Signature,
+    value: syn::Expr,
+    derive: &str,
+) -> syn::Result<syn::Expr> {
+    let ty = match sig.output {
+        syn::ReturnType::Type(_, ref ty) => ty,
+        syn::ReturnType::Default => return Ok(value),
+    };
+    wrap_self_value(ty, value).ok_or_else(|| {
+        let msg = format!(
+            "cannot derive `{}` for method `{}` returning `Self` in `{}`",
+            derive,
+            sig.ident,
+            type_to_string(ty)
         );
-        let expected: syn::Ident = syn::parse_quote!(T_);
-        assert_eq!(super::trait_to_generic_ident(&trait_), expected);
+        syn::Error::new(ty.span(), msg)
+    })
+}
+
+/// Check whether a bound is a `Sized` bound, and not a `?Sized` one.
+fn is_sized_bound(bound: &syn::TypeParamBound) -> bool {
+    match bound {
+        syn::TypeParamBound::Trait(ref t) => {
+            matches!(t.modifier, syn::TraitBoundModifier::None)
+                && t.path.segments.last().map(|s| s.ident == "Sized") == Some(true)
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a method signature requires `Self: Sized`.
+pub fn is_sized_method(sig: &syn::Signature) -> bool {
+    let where_clause = match sig.generics.where_clause {
+        Some(ref where_clause) => where_clause,
+        None => return false,
+    };
+    where_clause
+        .predicates
+        .iter()
+        .any(|predicate| match predicate {
+            syn::WherePredicate::Type(ref pt) if is_self_type(&pt.bounded_ty) => {
+                pt.bounds.iter().any(is_sized_bound)
+            }
+            _ => false,
+        })
+}
+
+/// Get the methods of a trait forwarded by the derives, and whether the
+/// generic type of their implementation can be unsized.
+///
+/// Methods requiring `Self: Sized` cannot be forwarded to an unsized type:
+/// they inherit their default implementation if they have one, otherwise the
+/// trait is only implemented for sized types.
+pub fn forwarded_methods(trait_: &syn::ItemTrait) -> (Vec<&syn::TraitItemMethod>, bool) {
+    let mut unsized_ = true;
+    let mut methods = Vec::new();
+    for item in trait_.items.iter() {
+        if let syn::TraitItem::Method(ref m) = item {
+            if is_sized_method(&m.sig) {
+                if m.default.is_some() {
+                    continue;
+                }
+                unsized_ = false;
+            }
+            methods.push(m);
+        }
+    }
+    (methods, unsized_)
+}
+
+/// Rewrite the typed `self` receiver of a method signature to its shorthand.
+///
+/// `self: Self`, `self: &Self` and `self: &mut Self` are equivalent to `self`,
+/// `&self` and `&mut self`, and are rewritten so that every derive handles
+/// them like the shorthand receivers. Other typed receivers are kept as is.
+pub fn normalize_receiver(sig: &mut syn::Signature) {
+    let receiver = match sig.inputs.first() {
+        Some(syn::FnArg::Typed(pat)) => {
+            let mutability = match *pat.pat {
+                syn::Pat::Ident(ref id) if id.ident == "self" && id.by_ref.is_none() => {
+                    id.mutability
+                }
+                _ => return,
+            };
+            let attrs = &pat.attrs;
+            match *pat.ty {
+                syn::Type::Path(ref ty) if ty.qself.is_none() && ty.path.is_ident("Self") => {
+                    quote!(#(#attrs)* #mutability self)
+                }
+                syn::Type::Reference(ref ty) if mutability.is_none() => match *ty.elem {
+                    syn::Type::Path(ref elem)
+                        if elem.qself.is_none() && elem.path.is_ident("Self") =>
+                    {
+                        let lifetime = &ty.lifetime;
+                        let mutability = &ty.mutability;
+                        quote!(#(#attrs)* & #lifetime #mutability self)
+                    }
+                    _ => return,
+                },
+                _ => return,
+            }
+        }
+        _ => return,
+    };
+    if let Ok(receiver) = syn::parse2(receiver) {
+        if let Some(first) = sig.inputs.first_mut() {
+            *first = syn::FnArg::Receiver(receiver);
+        }
+    }
+}
+
+/// Check whether a function argument is a `self` receiver with a type.
+fn is_typed_self(arg: &syn::FnArg) -> bool {
+    match arg {
+        syn::FnArg::Typed(pat) => match *pat.pat {
+            syn::Pat::Ident(ref id) => id.ident == "self",
+            _ => false,
+        },
+        syn::FnArg::Receiver(_) => false,
+    }
+}
+
+/// Get the mutability of a `self: Pin<&Self>` or `self: Pin<&mut Self>`
+/// receiver, or `None` for any other function argument.
+fn pin_receiver_mutability(arg: &syn::FnArg) -> Option<bool> {
+    match arg {
+        syn::FnArg::Typed(pat) if is_typed_self(arg) => pin_self_mutability(&pat.ty),
+        _ => None,
+    }
+}
+
+/// Get the mutability of a `Pin<&Self>` or `Pin<&mut Self>` type, or `None`
+/// for any other type.
+fn pin_self_mutability(ty: &syn::Type) -> Option<bool> {
+    let segment = match ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => match ty.path.segments.last() {
+            Some(segment) if segment.ident == "Pin" => segment,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(syn::Type::Reference(r))) => match *r.elem {
+                    syn::Type::Path(ref elem)
+                        if elem.qself.is_none() && elem.path.is_ident("Self") =>
+                    {
+                        Some(r.mutability.is_some())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Check whether a function argument is a `self: Pin<&mut Self>` receiver.
+pub fn is_pin_mut_receiver(arg: &syn::FnArg) -> bool {
+    pin_receiver_mutability(arg) == Some(true)
+}
+
+/// Check whether a function argument is a `self: Pin<&Self>` receiver.
+pub fn is_pin_ref_receiver(arg: &syn::FnArg) -> bool {
+    pin_receiver_mutability(arg) == Some(false)
+}
+
+/// Check whether a function argument is a `self: Rc<Self>` receiver, or a
+/// receiver of another smart pointer given by name, such as `Arc`.
+pub fn is_pointer_receiver(arg: &syn::FnArg, pointer: &str) -> bool {
+    let ty = match arg {
+        syn::FnArg::Typed(pat) if is_typed_self(arg) => &*pat.ty,
+        _ => return false,
+    };
+    let segment = match ty {
+        syn::Type::Path(ty) if ty.qself.is_none() => match ty.path.segments.last() {
+            Some(segment) if segment.ident == pointer => segment,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ref ty)) => is_self_type(ty),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Remove the `mut` binding modes from the arguments of a method signature.
+///
+/// The forwarding methods pass their arguments to the wrapped type as they
+/// received them, so a `mut` binding only needed by the default
+/// implementation of a trait method would trigger `unused_mut` warnings.
+pub fn strip_argument_mutability(sig: &mut syn::Signature) {
+    for input in sig.inputs.iter_mut() {
+        match input {
+            syn::FnArg::Receiver(r) if r.reference.is_none() => r.mutability = None,
+            syn::FnArg::Receiver(_) => (),
+            syn::FnArg::Typed(pat) => {
+                if let syn::Pat::Ident(ref mut id) = *pat.pat {
+                    if id.by_ref.is_none() {
+                        id.mutability = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Check whether a method signature returns `Self`.
+pub fn returns_self(sig: &syn::Signature) -> bool {
+    match sig.output {
+        syn::ReturnType::Type(_, ref ty) => match **ty {
+            syn::Type::Path(ref ty) => ty.qself.is_none() && ty.path.is_ident("Self"),
+            _ => false,
+        },
+        syn::ReturnType::Default => false,
+    }
+}
+
+/// Check whether a method signature returns `Pin<&mut Self>`.
+pub fn returns_pin_mut_self(sig: &syn::Signature) -> bool {
+    match sig.output {
+        syn::ReturnType::Type(_, ref ty) => pin_self_mutability(ty) == Some(true),
+        syn::ReturnType::Default => false,
+    }
+}
+
+/// Check whether a method signature returns `Self` wrapped in a `Result` or
+/// an `Option`, such as `Result<Self, Error>`.
+///
+/// Only the name of the returned type is checked, as for [`returns_result`].
+pub fn returns_wrapped_self(sig: &syn::Signature) -> bool {
+    let ty = match sig.output {
+        syn::ReturnType::Type(_, ref ty) => ty,
+        syn::ReturnType::Default => return false,
+    };
+    let segment = match **ty {
+        syn::Type::Path(ref ty) if ty.qself.is_none() => match ty.path.segments.last() {
+            Some(segment) if segment.ident == "Result" || segment.ident == "Option" => segment,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    match segment.arguments {
+        syn::PathArguments::AngleBracketed(ref args) => match args.args.first() {
+            Some(syn::GenericArgument::Type(syn::Type::Path(ref ty))) => {
+                ty.qself.is_none() && ty.path.is_ident("Self")
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Check whether a method signature returns a `Result`.
+///
+/// Only the name of the returned type is checked, so that aliases such as
+/// `io::Result<T>` are accepted as well.
+pub fn returns_result(sig: &syn::Signature) -> bool {
+    match sig.output {
+        syn::ReturnType::Type(_, ref ty) => match **ty {
+            syn::Type::Path(ref ty) => {
+                ty.qself.is_none()
+                    && ty
+                        .path
+                        .segments
+                        .last()
+                        .map(|s| s.ident == "Result")
+                        .unwrap_or(false)
+            }
+            _ => false,
+        },
+        syn::ReturnType::Default => false,
+    }
+}
+
+/// Get the span of a reference borrowed from the receiver in the return type
+/// of a method signature, if any.
+///
+/// References with an elided or anonymous lifetime, such as `&str` or
+/// `Ref<'_, T>`, or with the lifetime of the receiver, are borrowed from
+/// `self`, while references with another named lifetime, such as
+/// `&'static str` or `&'a str` for a trait lifetime `'a`, are not. Lifetimes
+/// elided from a path, as in `Ref<T>`, cannot be told apart from a type
+/// without lifetime and are not detected. Function pointers and the arguments
+/// of the `Fn` traits have their own lifetime elision and are skipped.
+pub fn borrowed_return(sig: &syn::Signature) -> Option<proc_macro2::Span> {
+    let ty = match sig.output {
+        syn::ReturnType::Type(_, ref ty) => ty,
+        syn::ReturnType::Default => return None,
+    };
+    let receiver_lifetime = match sig.receiver() {
+        Some(syn::FnArg::Receiver(syn::Receiver {
+            reference: Some((_, Some(ref lifetime))),
+            ..
+        })) => Some(lifetime.ident.to_string()),
+        _ => None,
+    };
+    fn borrowed_lifetime(lifetime: &syn::Lifetime, receiver_lifetime: &Option<String>) -> bool {
+        let name = lifetime.ident.to_string();
+        name == "_" || Some(name) == *receiver_lifetime
+    }
+    fn find_in_path(path: &syn::Path, receiver_lifetime: &Option<String>) -> Option<proc_macro2::Span> {
+        // only generic arguments are checked: the parenthesized arguments
+        // of the `Fn` traits have their own elision rules
+        for segment in path.segments.iter() {
+            if let syn::PathArguments::AngleBracketed(ref args) = segment.arguments {
+                for arg in args.args.iter() {
+                    let span = match arg {
+                        syn::GenericArgument::Lifetime(l) if borrowed_lifetime(l, receiver_lifetime) => {
+                            Some(l.span())
+                        }
+                        syn::GenericArgument::Type(ty) => find(ty, receiver_lifetime),
+                        syn::GenericArgument::Binding(b) => find(&b.ty, receiver_lifetime),
+                        _ => None,
+                    };
+                    if span.is_some() {
+                        return span;
+                    }
+                }
+            }
+        }
+        None
+    }
+    fn find(ty: &syn::Type, receiver_lifetime: &Option<String>) -> Option<proc_macro2::Span> {
+        match ty {
+            syn::Type::Reference(r) => match r.lifetime {
+                None => Some(r.and_token.span()),
+                Some(ref l) if borrowed_lifetime(l, receiver_lifetime) => Some(l.span()),
+                Some(_) => find(&r.elem, receiver_lifetime),
+            },
+            syn::Type::Path(p) => p
+                .qself
+                .as_ref()
+                .and_then(|q| find(&q.ty, receiver_lifetime))
+                .or_else(|| find_in_path(&p.path, receiver_lifetime)),
+            syn::Type::TraitObject(syn::TypeTraitObject { bounds, .. })
+            | syn::Type::ImplTrait(syn::TypeImplTrait { bounds, .. }) => {
+                bounds.iter().find_map(|bound| match bound {
+                    syn::TypeParamBound::Lifetime(l) if borrowed_lifetime(l, receiver_lifetime) => {
+                        Some(l.span())
+                    }
+                    syn::TypeParamBound::Trait(t) => find_in_path(&t.path, receiver_lifetime),
+                    _ => None,
+                })
+            }
+            syn::Type::Tuple(t) => t.elems.iter().find_map(|ty| find(ty, receiver_lifetime)),
+            syn::Type::Array(a) => find(&a.elem, receiver_lifetime),
+            syn::Type::Slice(s) => find(&s.elem, receiver_lifetime),
+            syn::Type::Paren(p) => find(&p.elem, receiver_lifetime),
+            syn::Type::Group(g) => find(&g.elem, receiver_lifetime),
+            // function pointers and raw pointers do not borrow from `self`
+            _ => None,
+        }
+    }
+    find(ty, &receiver_lifetime)
+}
+
+/// Check whether a method signature returns an `Option`.
+///
+/// Only the name of the returned type is checked, so that paths such as
+/// `std::option::Option<T>` are accepted as well.
+pub fn returns_option(sig: &syn::Signature) -> bool {
+    match sig.output {
+        syn::ReturnType::Type(_, ref ty) => match **ty {
+            syn::Type::Path(ref ty) => {
+                ty.qself.is_none()
+                    && ty
+                        .path
+                        .segments
+                        .last()
+                        .map(|s| s.ident == "Option")
+                        .unwrap_or(false)
+            }
+            _ => false,
+        },
+        syn::ReturnType::Default => false,
+    }
+}
+
+/// Check whether a trait declares the given supertrait.
+///
+/// Only the last segment of the supertrait path is compared, so that both
+/// `Send` and `std::marker::Send` are recognized.
+pub fn has_supertrait(trait_: &syn::ItemTrait, name: &str) -> bool {
+    trait_.supertraits.iter().any(|bound| match bound {
+        syn::TypeParamBound::Trait(ref t) => t
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident == name)
+            .unwrap_or(false),
+        syn::TypeParamBound::Lifetime(_) => false,
+    })
+}
+
+/// Check whether a trait item prevents to derive an implementation for a
+/// trait object.
+///
+/// Methods requiring `Self: Sized` are excluded from trait objects, and are
+/// accepted provided they have a default implementation to inherit. The
+/// error names the offending item, since the compiler would otherwise only
+/// report the trait as not object safe where the trait object is used. The
+/// error starts with the action requiring a trait object, such as
+/// ``derive `BoxDyn` ``.
+pub fn check_object_safety(item: &syn::TraitItem, action: &str) -> syn::Result<()> {
+    let unsafe_ = "prevents the trait from being made into an object";
+    let (span, what, ident, why) = match item {
+        syn::TraitItem::Method(ref m) if is_sized_method(&m.sig) => {
+            if m.default.is_some() {
+                return Ok(());
+            }
+            (
+                m.sig.span(),
+                "required methods with a `Self: Sized` bound",
+                &m.sig.ident,
+                "is not part of the trait object and has no default implementation",
+            )
+        }
+        syn::TraitItem::Method(ref m) => {
+            // `Self` may only appear as the receiver, which is the first input
+            let receiver = m.sig.receiver();
+            let self_argument = m
+                .sig
+                .inputs
+                .iter()
+                .skip(if receiver.is_some() { 1 } else { 0 })
+                .find(|arg| match arg {
+                    syn::FnArg::Typed(pat) => contains_self_type(&pat.ty),
+                    syn::FnArg::Receiver(_) => false,
+                });
+            let (span, what) = if let Some(param) = m.sig.generics.type_params().next() {
+                (param.span(), "generic methods")
+            } else if matches!(m.sig.output, syn::ReturnType::Type(_, ref ty) if contains_self_type(ty)) {
+                (m.sig.output.span(), "methods returning `Self`")
+            } else if receiver.is_none() {
+                (m.sig.span(), "associated functions")
+            } else if let Some(arg) = self_argument {
+                (arg.span(), "methods taking `Self` arguments")
+            } else {
+                return Ok(());
+            };
+            (span, what, &m.sig.ident, unsafe_)
+        }
+        syn::TraitItem::Type(ref ty) => (
+            ty.span(),
+            "associated types",
+            &ty.ident,
+            "would have to be given by the trait object",
+        ),
+        syn::TraitItem::Const(ref c) => (c.span(), "associated constants", &c.ident, unsafe_),
+        _ => return Ok(()),
+    };
+    let msg = format!(
+        "cannot {} for a trait declaring {}: `{}` {}",
+        action, what, ident, why
+    );
+    Err(syn::Error::new(span, msg))
+}
+
+/// Check whether a trait can be made into an object.
+///
+/// Every item of the trait is checked with [`check_object_safety`], and the
+/// errors are combined so that all the offending items are reported at once.
+pub fn is_object_safe(trait_: &syn::ItemTrait, action: &str) -> syn::Result<()> {
+    let mut errors = trait_
+        .items
+        .iter()
+        .filter_map(|item| check_object_safety(item, action).err());
+    match errors.next() {
+        None => Ok(()),
+        Some(mut err) => {
+            errors.for_each(|e| err.combine(e));
+            Err(err)
+        }
+    }
+}
+
+/// Build the associated types and constants of a derived implementation.
+///
+/// Every associated type and constant declared without a default in the
+/// trait is forwarded to the one of the generic type, see
+/// [`forward_associated_type`] and [`forward_associated_const`], while the
+/// ones with a default are inherited as they are.
+pub fn forwarded_associated_items(
+    trait_: &syn::ItemTrait,
+    generic_type: &syn::Ident,
+    trait_path: &syn::Path,
+) -> syn::Result<Vec<syn::ImplItem>> {
+    let mut items = Vec::new();
+    for item in trait_.items.iter() {
+        match item {
+            syn::TraitItem::Type(ref ty) if ty.default.is_none() => {
+                items.push(forward_associated_type(ty, generic_type, trait_path)?)
+            }
+            syn::TraitItem::Const(ref c) if c.default.is_none() => {
+                items.push(forward_associated_const(c, generic_type, trait_path))
+            }
+            _ => (),
+        }
+    }
+    Ok(items)
+}
+
+/// Build an associated constant forwarding to the constant of a generic type.
+///
+/// Given `const MASK: [u8; 4];` declared in the trait `Trait<T>` and the
+/// generic type `MT`, get `const MASK: [u8; 4] = <MT as Trait<T>>::MASK;`.
+pub fn forward_associated_const(
+    c: &syn::TraitItemConst,
+    generic_type: &syn::Ident,
+    trait_path: &syn::Path,
+) -> syn::ImplItem {
+    let attrs = forwarded_attributes(&c.attrs);
+    let ident = &c.ident;
+    let ty = &c.ty;
+    parse_quote!(
+        #(#attrs)*
+        const #ident: #ty = <#generic_type as #trait_path>::#ident;
+    )
+}
+
+/// Build an associated type forwarding to the associated type of a generic type.
+///
+/// Given `type Iter<'a>: Iterator where Self: 'a;` declared in the trait
+/// `Trait<T>` and the generic type `MT`, get
+/// `type Iter<'a> = <MT as Trait<T>>::Iter<'a> where Self: 'a;`.
+pub fn forward_associated_type(
+    ty: &syn::TraitItemType,
+    generic_type: &syn::Ident,
+    trait_path: &syn::Path,
+) -> syn::Result<syn::ImplItem> {
+    let attrs = forwarded_attributes(&ty.attrs);
+    let ident = &ty.ident;
+    let generics = &ty.generics;
+    let where_clause = &ty.generics.where_clause;
+    let mut names = ty.generics.clone();
+    names.params = generics_declaration_to_generics(&ty.generics.params)?;
+    // the where clause is written after the type, which is not supported
+    // by the `ImplItemType` printer, so the tokens are parsed directly
+    syn::parse2(quote!(
+        #(#attrs)*
+        type #ident #generics = <#generic_type as #trait_path>::#ident #names #where_clause;
+    ))
+}
+
+/// Build the where clause of a derived implementation from the trait where clause.
+///
+/// Predicates of the trait where clause mentioning `Self` are kept as they
+/// are, since they must hold for the wrapper type the trait is implemented
+/// for, and repeated with `Self` replaced by the generic type, since they
+/// must also hold for the generic type to implement the trait. Given the
+/// generic type `MT`, `where T: Into<Self>` becomes
+/// `where T: Into<Self>, T: Into<MT>`.
+///
+/// The associated types of the wrapper type are the ones of the generic type,
+/// and cannot be constrained by the where clause of their own implementation,
+/// so `Self::Item` paths are always replaced with a path qualified with the
+/// trait, which cannot be ambiguous with the associated types of other bounds:
+/// `where Self::Item: Clone` becomes `where <MT as Trait>::Item: Clone`.
+///
+/// `Sized` bounds of `Self` are dropped, since every type the traits are
+/// derived for is sized, and the generic type is already required to be sized
+/// by its trait bound.
+pub fn impl_where_clause(
+    trait_: &syn::ItemTrait,
+    generic_type: &syn::Ident,
+) -> syn::Result<Option<syn::WhereClause>> {
+    let where_clause = match trait_.generics.where_clause {
+        Some(ref where_clause) => where_clause,
+        None => return Ok(None),
+    };
+    let names = trait_
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::TraitItem::Type(ty) => Some(ty.ident.to_string()),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+    let trait_ident = &trait_.ident;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let trait_path: syn::Path = parse_quote!(#trait_ident #trait_generic_names);
+
+    let mut predicates: Vec<syn::WherePredicate> = Vec::new();
+    let mut replaced_predicates: Vec<syn::WherePredicate> = Vec::new();
+    for predicate in where_clause.predicates.iter() {
+        // `Self: Sized` holds for every wrapper type, and is implied for the
+        // generic type by the trait bound, so the `Sized` bound is dropped
+        let mut predicate = predicate.clone();
+        if let syn::WherePredicate::Type(ref mut pred) = predicate {
+            if is_self_type(&pred.bounded_ty) {
+                pred.bounds = pred
+                    .bounds
+                    .iter()
+                    .filter(|bound| !is_sized_bound(bound))
+                    .cloned()
+                    .collect();
+                if pred.bounds.is_empty() {
+                    continue;
+                }
+            }
+        }
+        let mut visitor = ReplaceSelf {
+            generic_type,
+            trait_path: &trait_path,
+            names: &names,
+            self_type: false,
+            replaced: false,
+        };
+        visitor.visit_where_predicate_mut(&mut predicate);
+        let mut replaced = predicate.clone();
+        visitor.self_type = true;
+        visitor.visit_where_predicate_mut(&mut replaced);
+        if visitor.replaced {
+            replaced_predicates.push(replaced);
+        }
+        predicates.push(predicate);
+    }
+
+    if predicates.is_empty() {
+        return Ok(None);
+    }
+    let mut rewritten = where_clause.clone();
+    rewritten.predicates = predicates.into_iter().collect();
+    if !replaced_predicates.is_empty() {
+        rewritten.predicates.extend(replaced_predicates);
+    }
+    if where_clause.predicates.trailing_punct() {
+        rewritten.predicates.push_punct(Default::default());
+    }
+    Ok(Some(rewritten))
+}
+
+/// A visitor replacing `Self` with the generic type in a where predicate.
+///
+/// Paths to the associated types of the trait, such as `Self::Item` or
+/// `<Self as Trait>::Item`, are always replaced with `<MT as Trait>::Item`,
+/// while the other mentions of `Self` are only replaced if `self_type` is set.
+struct ReplaceSelf<'a> {
+    generic_type: &'a syn::Ident,
+    trait_path: &'a syn::Path,
+    names: &'a HashSet<String>,
+    self_type: bool,
+    replaced: bool,
+}
+
+impl VisitMut for ReplaceSelf<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        let generic_type = self.generic_type;
+        if let syn::Type::Path(ref mut p) = ty {
+            match p.qself {
+                // `<Self as Trait>::Item`
+                Some(ref mut qself) if is_self_type(&qself.ty) => {
+                    let trait_ident = &self.trait_path.segments[0].ident;
+                    if p.path.segments[0].ident == *trait_ident {
+                        *qself.ty = parse_quote!(#generic_type);
+                    } else if self.self_type {
+                        *qself.ty = parse_quote!(#generic_type);
+                        self.replaced = true;
+                    }
+                }
+                // `Self::Item`
+                None if p.path.segments.len() > 1 && p.path.segments[0].ident == "Self" => {
+                    if self.names.contains(&p.path.segments[1].ident.to_string()) {
+                        let trait_path = self.trait_path;
+                        let rest = p.path.segments.iter().skip(1);
+                        *ty = parse_quote!(<#generic_type as #trait_path>::#(#rest)::*);
+                    } else if self.self_type {
+                        p.path.segments[0].ident = generic_type.clone();
+                        self.replaced = true;
+                    }
+                }
+                _ => (),
+            }
+        }
+        if self.self_type && is_self_type(ty) {
+            *ty = parse_quote!(#generic_type);
+            self.replaced = true;
+        } else {
+            syn::visit_mut::visit_type_mut(self, ty);
+        }
+    }
+}
+
+/// Remove the redundant bounds of a generics declaration.
+///
+/// Bounds of a where predicate already declared on the bounded type parameter,
+/// or by a previous where predicate for the same type, are removed, and the
+/// predicates left without any bound are dropped altogether.
+pub fn dedup_bounds(generics: &mut syn::Generics) {
+    // bounds are compared as syntax trees, so that the order of the bounds
+    // of a predicate, or their formatting, does not matter
+    type Bounded = (Option<syn::BoundLifetimes>, syn::Type);
+    let mut seen: HashMap<Bounded, HashSet<syn::TypeParamBound>> = HashMap::new();
+    for param in generics.type_params() {
+        let ident = &param.ident;
+        let bounds = param.bounds.iter().cloned().collect();
+        seen.insert((None, parse_quote!(#ident)), bounds);
+    }
+
+    let where_clause = match generics.where_clause {
+        Some(ref mut where_clause) => where_clause,
+        None => return,
+    };
+    let trailing = where_clause.predicates.trailing_punct();
+    let predicates = std::mem::take(&mut where_clause.predicates);
+    for mut predicate in predicates.into_iter() {
+        if let syn::WherePredicate::Type(ref mut ty) = predicate {
+            let bounds = seen
+                .entry((ty.lifetimes.clone(), ty.bounded_ty.clone()))
+                .or_default();
+            ty.bounds = ty
+                .bounds
+                .iter()
+                .filter(|bound| bounds.insert((*bound).clone()))
+                .cloned()
+                .collect();
+            if ty.bounds.is_empty() {
+                continue;
+            }
+        }
+        where_clause.predicates.push(predicate);
+    }
+
+    if where_clause.predicates.is_empty() {
+        generics.where_clause = None;
+    } else if trailing {
+        where_clause.predicates.push_punct(Default::default());
+    }
+}
+
+/// Build a generic identifier suitable for the given trait.
+///
+/// This function extracts the initials of the trait identifier, such as `II`
+/// for `trait IntoIterator`. If the initials
+/// are the whole trait identifier, such as for `trait T` or `trait IO`, the
+/// generic would shadow the trait itself, so the trait identifier is given the
+/// reserved `__Blanket` prefix instead (`__BlanketT`, `__BlanketIO`). If this
+/// results in a generic type identifier already present in the generics of
+/// that trait or of one of its methods, as many underscores are added to the
+/// end of the identifier.
+///
+/// The identifier given with `generic = "..."`, if any, is used instead.
+pub fn trait_to_generic_ident(trait_: &syn::ItemTrait, generic: Option<&syn::Ident>) -> syn::Ident {
+    if let Some(generic) = generic {
+        return generic.clone();
+    }
+    let ident = trait_.ident.to_string();
+    let mut raw = ident
+        .chars()
+        .filter(|c| c.is_uppercase())
+        .collect::<String>();
+    if raw.is_empty() || raw == ident {
+        raw = format!("__Blanket{}", ident);
+    }
+    while generic_names(trait_).any(|name| *name == raw) {
+        raw.push('_');
+    }
+
+    syn::Ident::new(&raw, trait_.ident.span())
+}
+
+/// Check whether an identifier is already declared by a trait, as the trait
+/// itself or as a type or const generic of the trait or of its methods.
+pub fn is_declared_by_trait(trait_: &syn::ItemTrait, ident: &syn::Ident) -> bool {
+    trait_.ident == *ident || generic_names(trait_).any(|name| name == ident)
+}
+
+/// Iterate over the type and const generics of a trait and of its methods.
+fn generic_names(trait_: &syn::ItemTrait) -> impl Iterator<Item = &syn::Ident> {
+    let methods = trait_.items.iter().filter_map(|item| match item {
+        syn::TraitItem::Method(m) => Some(&m.sig.generics),
+        _ => None,
+    });
+    std::iter::once(&trait_.generics)
+        .chain(methods)
+        .flat_map(|generics| generics.params.iter())
+        .filter_map(|g| match g {
+            syn::GenericParam::Type(param) => Some(&param.ident),
+            syn::GenericParam::Const(param) => Some(&param.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+}
+
+/// Build a generic identifier for a pointer type implementing the given trait.
+///
+/// This function uses `P`, with as many underscores added to the end of the
+/// identifier as needed to avoid collisions with the trait identifier, the
+/// generics of the trait and of its methods, and with the given generic type
+/// identifier.
+pub fn trait_to_pointer_ident(trait_: &syn::ItemTrait, generic_type: &syn::Ident) -> syn::Ident {
+    let mut raw = String::from("P");
+    while *generic_type == raw
+        || trait_.ident == raw
+        || generic_names(trait_).any(|name| *name == raw)
+    {
+        raw.push('_');
+    }
+
+    syn::Ident::new(&raw, trait_.ident.span())
+}
+
+/// Retarget an implementation of the `Deref` derive to a pointer type.
+///
+/// Given `impl<T: Trait + ?Sized, P: Deref<Target = T>> Trait for P`, get
+/// `impl<T: Trait> Trait for Pointer<T>`: the pointer type is expected to
+/// dereference to its generic argument, and the generic type is left sized
+/// since most pointer types declared outside of the standard library do
+/// not accept unsized types.
+pub fn impl_for_pointer(
+    item: &syn::ItemImpl,
+    trait_: &syn::ItemTrait,
+    pointer: &syn::Path,
+    generic: Option<&syn::Ident>,
+) -> syn::ItemImpl {
+    let generic_type = trait_to_generic_ident(trait_, generic);
+    let pointer_type = trait_to_pointer_ident(trait_, &generic_type);
+
+    let mut item = item.clone();
+    let params = std::mem::take(&mut item.generics.params);
+    for param in params.into_iter() {
+        match param {
+            GenericParam::Type(ref p) if p.ident == pointer_type => (),
+            GenericParam::Type(mut p) => {
+                if p.ident == generic_type {
+                    p.bounds = p
+                        .bounds
+                        .into_iter()
+                        .filter(|bound| match bound {
+                            syn::TypeParamBound::Trait(t) => {
+                                !matches!(t.modifier, syn::TraitBoundModifier::Maybe(_))
+                            }
+                            _ => true,
+                        })
+                        .collect();
+                }
+                item.generics.params.push(GenericParam::Type(p));
+            }
+            param => item.generics.params.push(param),
+        }
+    }
+    item.self_ty = parse_quote!(#pointer<#generic_type>);
+    item
+}
+
+/// Implement a trait for a concrete inner type instead of the generic type.
+///
+/// Given `impl<T: Trait + ?Sized> Trait for Arc<T> where T: Send`, get
+/// `impl Trait for Arc<str>`: the generic type is removed along with the
+/// predicates bounding it, and relies on the existing implementation of the
+/// trait for the inner type.
+pub fn impl_for_inner(item: &syn::ItemImpl, trait_: &syn::ItemTrait, inner: &syn::Type) -> syn::ItemImpl {
+    // an inner type cannot be combined with a generic type given with
+    // `generic = "..."`, so the implementation uses the default one
+    let generic_type = trait_to_generic_ident(trait_, None);
+
+    let mut item = item.clone();
+    let params = std::mem::take(&mut item.generics.params);
+    item.generics.params = params
+        .into_iter()
+        .filter(|param| !matches!(param, GenericParam::Type(p) if p.ident == generic_type))
+        .collect();
+    if item.generics.params.is_empty() {
+        item.generics.lt_token = None;
+        item.generics.gt_token = None;
+    }
+    if let Some(ref mut where_clause) = item.generics.where_clause {
+        let predicates = std::mem::take(&mut where_clause.predicates);
+        where_clause.predicates = predicates
+            .into_iter()
+            .filter(|predicate| match predicate {
+                syn::WherePredicate::Type(t) => !is_generic_type(&t.bounded_ty, &generic_type),
+                _ => true,
+            })
+            .collect();
+        if where_clause.predicates.is_empty() {
+            item.generics.where_clause = None;
+        }
+    }
+
+    // only replace the types, so that items named like the generic type
+    // (such as an associated const `C` in `trait Counter`) are left alone
+    ReplaceGenericType { generic_type: &generic_type, inner }.visit_item_impl_mut(&mut item);
+    item
+}
+
+/// A visitor replacing the generic type with a concrete type.
+struct ReplaceGenericType<'a> {
+    generic_type: &'a syn::Ident,
+    inner: &'a syn::Type,
+}
+
+impl VisitMut for ReplaceGenericType<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if is_generic_type(ty, self.generic_type) {
+            *ty = self.inner.clone();
+        } else {
+            syn::visit_mut::visit_type_mut(self, ty);
+        }
+    }
+}
+
+/// Build a type alias for the boxed trait object of a trait.
+///
+/// Given `pub trait Trait<'a, T: Clone>` and the alias `BoxedTrait`, get
+/// `pub type BoxedTrait<'a, T> = Box<dyn Trait<'a, T>>;`: the bounds of the
+/// generics are left out, since they are not enforced in type aliases. The
+/// trait must be object safe, see [`is_object_safe`].
+pub fn boxed_alias(trait_: &syn::ItemTrait, alias: &syn::Ident) -> syn::Result<syn::ItemType> {
+    is_object_safe(trait_, &format!("declare `boxed_alias = \"{}\"`", alias))?;
+    let mut generics = trait_.generics.clone();
+    generics.where_clause = None;
+    for param in generics.params.iter_mut() {
+        match param {
+            syn::GenericParam::Type(ty) => {
+                ty.colon_token = None;
+                ty.bounds.clear();
+            }
+            syn::GenericParam::Lifetime(lt) => {
+                lt.colon_token = None;
+                lt.bounds.clear();
+            }
+            syn::GenericParam::Const(_) => (),
+        }
+    }
+    let vis = &trait_.vis;
+    let ident = &trait_.ident;
+    let trait_generic_names = trait_generic_names(trait_)?;
+    let doc = format!(" A boxed trait object of [`{}`].", ident);
+    Ok(parse_quote!(
+        #[doc = #doc]
+        #vis type #alias #generics = std::boxed::Box<dyn #ident #trait_generic_names>;
+    ))
+}
+
+/// Check whether a type is exactly the given generic type.
+fn is_generic_type(ty: &syn::Type, generic_type: &syn::Ident) -> bool {
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p.path.is_ident(generic_type),
+        _ => false,
+    }
+}
+
+/// Convert a generic type declaration to a generic with the same arguments.
+///
+/// Given a generic section `<'a, T: 'static + Send, const N: usize>`, get
+/// simply `<'a, T, N>`: the bounds, defaults and const types are removed so
+/// that the parameters can be used as generic arguments. Const parameters are
+/// returned as type parameters, whose identifiers are emitted as paths which
+/// the compiler resolves to the const parameters.
+pub fn generics_declaration_to_generics(
+    generics: &Punctuated<GenericParam, Token![,]>,
+) -> syn::Result<Punctuated<GenericParam, Token![,]>> {
+    generics
+        .iter()
+        .map(|gen| match gen {
+            syn::GenericParam::Type(t) => Ok(syn::GenericParam::Type(syn::TypeParam {
+                attrs: t.attrs.clone(),
+                ident: t.ident.clone(),
+                colon_token: None,
+                bounds: Punctuated::new(),
+                eq_token: None,
+                default: None,
+            })),
+            syn::GenericParam::Lifetime(l) => Ok(syn::GenericParam::Lifetime(syn::LifetimeDef {
+                attrs: l.attrs.clone(),
+                lifetime: l.lifetime.clone(),
+                colon_token: None,
+                bounds: Punctuated::new(),
+            })),
+            syn::GenericParam::Const(c) => Ok(syn::GenericParam::Type(syn::TypeParam {
+                attrs: c.attrs.clone(),
+                ident: c.ident.clone(),
+                colon_token: None,
+                bounds: Punctuated::new(),
+                eq_token: None,
+                default: None,
+            })),
+        })
+        .collect()
+}
+
+/// Build the generic arguments referring to the generics of a trait.
+///
+/// Given a trait `Trait<'a, T: 'a + Send>`, get simply `<'a, T>`.
+pub fn trait_generic_names(trait_: &syn::ItemTrait) -> syn::Result<syn::Generics> {
+    Ok(syn::Generics {
+        lt_token: trait_.generics.lt_token,
+        params: generics_declaration_to_generics(&trait_.generics.params)?,
+        gt_token: trait_.generics.gt_token,
+        where_clause: None,
+    })
+}
+
+/// Build the generics declaration of a derived implementation.
+///
+/// The generics of the trait are declared first, without their defaults
+/// which are only allowed in the trait declaration, followed by the given type
+/// parameters. The where clause is left out, since it must be adapted to the
+/// generic type of the implementation with [`impl_where_clause`].
+///
+/// The lifetime supertraits of the trait, such as `'static` in
+/// `trait Event: 'static`, bound the given type parameters as well, since
+/// the wrapper type only outlives them if the wrapped type does.
+pub fn impl_generics<I>(trait_: &syn::ItemTrait, params: I) -> syn::Generics
+where
+    I: IntoIterator<Item = syn::TypeParam>,
+{
+    let mut generics = syn::Generics {
+        lt_token: trait_.generics.lt_token,
+        params: trait_.generics.params.clone(),
+        gt_token: trait_.generics.gt_token,
+        where_clause: None,
+    };
+    for param in generics.params.iter_mut() {
+        match param {
+            GenericParam::Type(ref mut ty) => {
+                ty.eq_token = None;
+                ty.default = None;
+            }
+            GenericParam::Const(ref mut c) => {
+                c.eq_token = None;
+                c.default = None;
+            }
+            GenericParam::Lifetime(_) => (),
+        }
+    }
+    let lifetimes = trait_
+        .supertraits
+        .iter()
+        .filter(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+    for mut param in params {
+        param.bounds.extend(lifetimes.clone().cloned());
+        if !param.bounds.is_empty() {
+            param.colon_token.get_or_insert_with(Default::default);
+        }
+        generics.params.push(syn::GenericParam::Type(param));
+    }
+    if !generics.params.is_empty() {
+        generics.lt_token.get_or_insert_with(Default::default);
+        generics.gt_token.get_or_insert_with(Default::default);
+    }
+    generics
+}
+
+/// Pretty-print a derived implementation, to compare it with the code it is
+/// expected to be formatted as.
+#[cfg(test)]
+pub fn pretty_print(item: &syn::ItemImpl) -> String {
+    let file = syn::File {
+        shebang: None,
+        attrs: Vec::new(),
+        items: vec![syn::Item::Impl(item.clone())],
+    };
+    prettyplease::unparse(&file)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    #[test]
+    fn signature_to_method_call_unsupported() {
+        let sig = syn::parse_quote!(extern "C" fn callback(&self));
+        let err = super::signature_to_method_call(&sig).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot forward methods with an explicit ABI"
+        );
+
+        let sig = syn::parse_quote!(unsafe fn printf(&self, format: *const u8, ...));
+        let err = super::signature_to_method_call(&sig).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot forward methods with a variadic parameter"
+        );
+    }
+
+    #[test]
+    fn signature_to_method_call_generics() {
+        let sig =
+            syn::parse_quote!(fn fill<'a, T: Default, const N: usize>(&'a self, t: T) -> [T; N]);
+        let call = super::signature_to_method_call(&sig).unwrap();
+        let expected: syn::ExprMethodCall = syn::parse_quote!(self.fill::<T, N>(t));
+        assert_eq!(call, expected);
+    }
+
+    #[test]
+    fn signature_to_method_call_generics_default() {
+        let sig = syn::parse_quote!(fn parse<T: std::str::FromStr = i32>(&self) -> T);
+        let call = super::signature_to_method_call(&sig).unwrap();
+        let expected: syn::ExprMethodCall = syn::parse_quote!(self.parse::<T>());
+        assert_eq!(call, expected);
+    }
+
+    #[test]
+    fn signature_to_method_call_typed_receiver() {
+        let sig =
+            syn::parse_quote!(fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>);
+        let call = super::signature_to_method_call(&sig).unwrap();
+        let expected: syn::ExprMethodCall = syn::parse_quote!(self.poll(cx));
+        assert_eq!(call, expected);
+    }
+
+    #[test]
+    fn signature_to_method_call_mut_reference() {
+        let sig = syn::parse_quote!(fn read(&mut self, buf: &mut [u8]) -> usize);
+        let call = super::signature_to_method_call(&sig).unwrap();
+        let expected: syn::ExprMethodCall = syn::parse_quote!(self.read(buf));
+        assert_eq!(call, expected);
+    }
+
+    #[test]
+    fn signature_to_method_call_arguments() {
+        let sig = syn::parse_quote!(fn len(&self) -> usize);
+        let call = super::signature_to_method_call(&sig).unwrap();
+        assert_eq!(call.to_token_stream().to_string(), "self . len ()");
+
+        let sig = syn::parse_quote!(fn push(&mut self, item: u8,));
+        let call = super::signature_to_method_call(&sig).unwrap();
+        assert_eq!(call.to_token_stream().to_string(), "self . push (item)");
+
+        let sig = syn::parse_quote!(fn splice(&mut self, start: usize, end: usize, items: &[u8],));
+        let call = super::signature_to_method_call(&sig).unwrap();
+        assert_eq!(
+            call.to_token_stream().to_string(),
+            "self . splice (start , end , items)"
+        );
+    }
+
+    #[test]
+    fn signature_to_method_call_trait_object() {
+        let sig = syn::parse_quote!(
+            fn visit(&self, v: &mut dyn Visitor, f: Box<dyn Fn(u8) + '_>, d: &(dyn Debug + Send))
+        );
+        let call = super::signature_to_method_call(&sig).unwrap();
+        let expected: syn::ExprMethodCall = syn::parse_quote!(self.visit(v, f, d));
+        assert_eq!(call, expected);
+    }
+
+    #[test]
+    fn strip_argument_mutability() {
+        let mut sig = parse_quote!(fn read_exact(mut self, mut buf: &mut [u8], ref mut n: usize));
+        super::strip_argument_mutability(&mut sig);
+        let expected: syn::Signature =
+            parse_quote!(fn read_exact(self, buf: &mut [u8], ref mut n: usize));
+        assert_eq!(sig, expected);
+    }
+
+    #[test]
+    fn is_pin_mut_receiver() {
+        let sig: syn::Signature = parse_quote!(fn poll(self: Pin<&mut Self>));
+        assert!(super::is_pin_mut_receiver(&sig.inputs[0]));
+        let sig: syn::Signature = parse_quote!(fn poll(self: std::pin::Pin<&'a mut Self>));
+        assert!(super::is_pin_mut_receiver(&sig.inputs[0]));
+        let sig: syn::Signature = parse_quote!(fn poll(self: Pin<&Self>));
+        assert!(!super::is_pin_mut_receiver(&sig.inputs[0]));
+        let sig: syn::Signature = parse_quote!(fn poll(&mut self));
+        assert!(!super::is_pin_mut_receiver(&sig.inputs[0]));
+        let sig: syn::Signature = parse_quote!(fn poll(this: Pin<&mut Self>));
+        assert!(!super::is_pin_mut_receiver(&sig.inputs[0]));
+    }
+
+    #[test]
+    fn returns_pin_mut_self() {
+        let sig: syn::Signature = parse_quote!(fn as_pin(self: Pin<&mut Self>) -> Pin<&mut Self>);
+        assert!(super::returns_pin_mut_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn as_pin(&mut self) -> std::pin::Pin<&'a mut Self>);
+        assert!(super::returns_pin_mut_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn as_pin(self: Pin<&mut Self>) -> Pin<&Self>);
+        assert!(!super::returns_pin_mut_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn as_pin(self: Pin<&mut Self>) -> Pin<Box<Self>>);
+        assert!(!super::returns_pin_mut_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn as_pin(self: Pin<&mut Self>));
+        assert!(!super::returns_pin_mut_self(&sig));
+    }
+
+    #[test]
+    fn is_pin_ref_receiver() {
+        let sig: syn::Signature = parse_quote!(fn poll(self: Pin<&Self>));
+        assert!(super::is_pin_ref_receiver(&sig.inputs[0]));
+        let sig: syn::Signature = parse_quote!(fn poll(self: Pin<&mut Self>));
+        assert!(!super::is_pin_ref_receiver(&sig.inputs[0]));
+        let sig: syn::Signature = parse_quote!(fn poll(&self));
+        assert!(!super::is_pin_ref_receiver(&sig.inputs[0]));
+    }
+
+    #[test]
+    fn prepend_function_path() {
+        let path = parse_quote!(crate::qualified::path);
+        let mut call = parse_quote!(myfunction(arg1, arg2));
+        super::prepend_function_path(&mut call, path).unwrap();
+        assert_eq!(
+            call,
+            parse_quote!(crate::qualified::path::myfunction(arg1, arg2))
+        );
+    }
+
+    #[test]
+    fn deref_expr() {
+        let expr = parse_quote!(self);
+        let dereffed = super::deref_expr(expr);
+        assert_eq!(dereffed, parse_quote!((*self)));
+    }
+
+    #[test]
+    fn forwarding_body() {
+        let sig: syn::Signature = parse_quote!(fn get(&self, url: &str) -> u8);
+        let call: syn::Expr = parse_quote!((*(*self)).get(url));
+        assert_eq!(super::forwarding_body(&sig, call.clone()), call);
+
+        let sig: syn::Signature = parse_quote!(async fn get(&self, url: &str) -> u8);
+        let expected: syn::Expr = parse_quote!((*(*self)).get(url).await);
+        assert_eq!(super::forwarding_body(&sig, call.clone()), expected);
+
+        let sig: syn::Signature = parse_quote!(unsafe fn get(&self, url: &str) -> u8);
+        let expected: syn::Expr = parse_quote!(unsafe { (*(*self)).get(url) });
+        assert_eq!(super::forwarding_body(&sig, call.clone()), expected);
+
+        let sig: syn::Signature = parse_quote!(async unsafe fn get(&self, url: &str) -> u8);
+        let expected: syn::Expr = parse_quote!(unsafe { (*(*self)).get(url).await });
+        assert_eq!(super::forwarding_body(&sig, call), expected);
+    }
+
+    #[test]
+    fn has_method_supertraits() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Named: Send + Sync + ?Sized + 'static {}
+        );
+        assert!(!super::has_method_supertraits(&trait_));
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Named: Send + Base {}
+        );
+        assert!(super::has_method_supertraits(&trait_));
+    }
+
+    #[test]
+    fn disambiguate_method_call() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Named<T>: Base {}
+        );
+        let sig: syn::Signature = parse_quote!(fn get<K>(&self, key: K));
+        let mut call = super::signature_to_method_call(&sig).unwrap();
+        *call.receiver = parse_quote!((*(*self)));
+        let expected: syn::Expr = parse_quote!(<N as Named<T>>::get::<K>(&(*(*self)), key));
+        let generic_type: syn::Ident = parse_quote!(N);
+        let qualified = super::disambiguate_method_call(
+            &trait_,
+            &sig,
+            call.clone(),
+            Some(&generic_type),
+            false,
+        );
+        assert_eq!(qualified.unwrap(), expected);
+
+        let sig: syn::Signature = parse_quote!(fn set(&mut self));
+        let mut call = super::signature_to_method_call(&sig).unwrap();
+        *call.receiver = parse_quote!((*(*self)));
+        let expected: syn::Expr = parse_quote!(<_ as Named<T>>::set(&mut (*(*self))));
+        let qualified = super::disambiguate_method_call(&trait_, &sig, call.clone(), None, false);
+        assert_eq!(qualified.unwrap(), expected);
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Named<T>: Send {}
+        );
+        let qualified = super::disambiguate_method_call(
+            &trait_,
+            &sig,
+            call.clone(),
+            Some(&generic_type),
+            false,
+        );
+        assert_eq!(qualified.unwrap(), syn::Expr::MethodCall(call.clone()));
+        let expected: syn::Expr = parse_quote!(<N as Named<T>>::set(&mut (*(*self))));
+        let qualified =
+            super::disambiguate_method_call(&trait_, &sig, call, Some(&generic_type), true);
+        assert_eq!(qualified.unwrap(), expected);
+    }
+
+    #[test]
+    fn attribute_kind() {
+        use super::AttributeKind;
+
+        let method: syn::TraitItemMethod = parse_quote!(
+            #[cfg(feature = "extra")]
+            #[cfg_attr(feature = "extra", inline)]
+            #[track_caller]
+            #[must_use]
+            #[deprecated]
+            /// Some documentation.
+            #[doc(hidden)]
+            #[allow(unused)]
+            #[inline]
+            #[rustfmt::skip]
+            #[instrument]
+            fn extra(&self);
+        );
+        let kinds = method
+            .attrs
+            .iter()
+            .map(super::attribute_kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                AttributeKind::Semantic,
+                AttributeKind::Semantic,
+                AttributeKind::Semantic,
+                AttributeKind::Lint,
+                AttributeKind::Lint,
+                AttributeKind::Cosmetic,
+                AttributeKind::Cosmetic,
+                AttributeKind::Semantic,
+                AttributeKind::Cosmetic,
+                AttributeKind::Unknown,
+                AttributeKind::Unknown,
+            ]
+        );
+    }
+
+    #[test]
+    fn forwarded_attributes() {
+        let method: syn::TraitItemMethod = parse_quote!(
+            /// Some documentation.
+            #[cfg(feature = "extra")]
+            #[cfg_attr(feature = "extra", inline)]
+            #[allow(unused)]
+            #[track_caller]
+            #[doc(hidden)]
+            #[must_use]
+            #[deprecated]
+            #[instrument]
+            fn extra(&self);
+        );
+        let attrs = super::forwarded_attributes(&method.attrs);
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote!(#[cfg(feature = "extra")]),
+            parse_quote!(#[cfg_attr(feature = "extra", inline)]),
+            parse_quote!(#[allow(unused)]),
+            parse_quote!(#[track_caller]),
+            parse_quote!(#[allow(deprecated)]),
+        ];
+        assert_eq!(attrs, expected);
+    }
+
+    #[test]
+    fn deref_self_arguments() {
+        let sig: syn::Signature = parse_quote!(fn merge(&mut self, a: &Self, b: &mut Self, c: Self, d: u8));
+        let mut call = super::signature_to_method_call(&sig).unwrap();
+        super::deref_self_arguments(&sig, &mut call, "Box", true).unwrap();
+        let expected: syn::ExprMethodCall = parse_quote!(self.merge(&**a, &mut **b, *c, d));
+        assert_eq!(call, expected);
+
+        let mut call = super::signature_to_method_call(&sig).unwrap();
+        let err = super::deref_self_arguments(&sig, &mut call, "Rc", false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot derive `Rc` for a trait declaring methods with `&mut Self` arguments"
+        );
+    }
+
+    #[test]
+    fn forwarded_attributes_cfg_attr() {
+        let method: syn::TraitItemMethod = parse_quote!(
+            #[cfg_attr(feature = "x", deprecated)]
+            #[cfg_attr(feature = "y", must_use)]
+            #[cfg_attr(feature = "z", must_use, deprecated(note = "unused"), inline)]
+            #[cfg_attr(feature = "x", cfg_attr(feature = "y", deprecated))]
+            fn old(&self) -> u8;
+        );
+        let attrs = super::forwarded_attributes(&method.attrs);
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote!(#[cfg_attr(feature = "x", allow(deprecated))]),
+            parse_quote!(#[cfg_attr(feature = "z", allow(deprecated), inline)]),
+            parse_quote!(#[cfg_attr(feature = "x", cfg_attr(feature = "y", allow(deprecated)))]),
+        ];
+        assert_eq!(attrs, expected);
+    }
+
+    #[test]
+    fn forwarded_attributes_lint_levels() {
+        let method: syn::TraitItemMethod = parse_quote!(
+            #[allow(clippy::too_many_arguments)]
+            #[warn(unused)]
+            #[deny(clippy::float_cmp)]
+            #[forbid(unsafe_code)]
+            #[expect(unused)]
+            fn set(&mut self, a: u8, b: u8, c: u8, d: u8, e: u8, f: u8, g: u8, h: u8);
+        );
+        let attrs = super::forwarded_attributes(&method.attrs);
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote!(#[allow(clippy::too_many_arguments)]),
+            parse_quote!(#[warn(unused)]),
+            parse_quote!(#[deny(clippy::float_cmp)]),
+        ];
+        assert_eq!(attrs, expected);
+    }
+
+    #[test]
+    fn forwarded_attributes_deprecated() {
+        let method: syn::TraitItemMethod = parse_quote!(
+            #[must_use]
+            #[deprecated(note = "use `other` instead")]
+            fn old(&self) -> u8;
+        );
+        let attrs = super::forwarded_attributes(&method.attrs);
+        let expected: Vec<syn::Attribute> = vec![parse_quote!(#[allow(deprecated)])];
+        assert_eq!(attrs, expected);
+    }
+
+    #[test]
+    fn doc_string() {
+        let attr: syn::Attribute = syn::parse_quote!(#[doc = " text"]);
+        assert_eq!(super::doc_string(&attr), Some(String::from(" text")));
+        let attr: syn::Attribute = syn::parse_quote!(#[inline]);
+        assert_eq!(super::doc_string(&attr), None);
+    }
+
+    #[test]
+    fn is_async_trait_attribute() {
+        let attr: syn::Attribute = parse_quote!(#[async_trait]);
+        assert!(super::is_async_trait_attribute(&attr));
+        let attr: syn::Attribute = parse_quote!(#[async_trait::async_trait(?Send)]);
+        assert!(super::is_async_trait_attribute(&attr));
+        let attr: syn::Attribute = parse_quote!(#[doc = "async_trait"]);
+        assert!(!super::is_async_trait_attribute(&attr));
+        let attr: syn::Attribute = parse_quote!(#[my::async_trait]);
+        assert!(!super::is_async_trait_attribute(&attr));
+    }
+
+    #[test]
+    fn async_trait_bounds() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Fetcher {
+                async fn get(&self) -> Vec<u8>;
+                async fn put(&mut self, data: Vec<u8>);
+                fn len(&self) -> usize;
+            }
+        );
+        let attr: syn::Attribute = parse_quote!(#[async_trait]);
+        let bounds = super::async_trait_bounds(&trait_, &attr);
+        let expected: Vec<syn::WherePredicate> = vec![
+            parse_quote!(Self: std::marker::Sync),
+            parse_quote!(Self: std::marker::Send),
+        ];
+        assert_eq!(bounds, expected);
+
+        let attr: syn::Attribute = parse_quote!(#[async_trait(?Send)]);
+        assert!(super::async_trait_bounds(&trait_, &attr).is_empty());
+    }
+
+    #[test]
+    fn is_skipped() {
+        let item: syn::TraitItem = parse_quote!(
+            #[doc(hidden)]
+            #[blanket(skip)]
+            const NAME: &'static str = "name";
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert!(super::is_skipped(attrs).unwrap());
+
+        let mut item: syn::TraitItem = parse_quote!(
+            #[blanket(forward)]
+            type Item;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        let err = super::is_skipped(attrs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected `skip`, `as = \"take\"`, `via = \"...\"` or `lock = \"...\"`"
+        );
+        super::strip_item_attributes(&mut item);
+        assert_eq!(
+            item,
+            parse_quote!(
+                type Item;
+            )
+        );
+
+        let item: syn::TraitItem = parse_quote!(
+            #[inline]
+            fn method(&self);
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert!(!super::is_skipped(attrs).unwrap());
+    }
+
+    #[test]
+    fn is_std_path() {
+        let ty: syn::Type = parse_quote!(std::boxed::Box<T>);
+        assert!(super::is_std_path(&ty, "boxed"));
+        assert!(!super::is_std_path(&ty, "rc"));
+        let ty: syn::Type = parse_quote!(Box<T>);
+        assert!(!super::is_std_path(&ty, "boxed"));
+        let ty: syn::Type = parse_quote!(&std::boxed::Box<T>);
+        assert!(!super::is_std_path(&ty, "boxed"));
+    }
+
+    #[test]
+    fn take_attribute() {
+        let item: syn::TraitItem = parse_quote!(
+            #[inline]
+            #[blanket(as = "take")]
+            fn flush(&self) -> u32;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert!(!super::is_skipped(attrs).unwrap());
+        assert_eq!(super::take_attribute(attrs), Some(&attrs[1]));
+
+        let item: syn::TraitItem = parse_quote!(
+            #[blanket(as = "replace")]
+            fn flush(&self) -> u32;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert!(super::is_skipped(attrs).is_err());
+        assert_eq!(super::take_attribute(attrs), None);
+    }
+
+    #[test]
+    fn lock_kind() {
+        let item: syn::TraitItem = parse_quote!(
+            #[blanket(lock = "write")]
+            fn get(&self) -> u32;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert!(!super::is_skipped(attrs).unwrap());
+        assert_eq!(super::lock_attribute(attrs), Some(&attrs[0]));
+        assert_eq!(super::lock_kind(attrs).unwrap(), Some(true));
+
+        let item: syn::TraitItem = parse_quote!(
+            #[blanket(lock = "read")]
+            fn get(&self) -> u32;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert_eq!(super::lock_kind(attrs).unwrap(), Some(false));
+
+        let item: syn::TraitItem = parse_quote!(
+            #[blanket(lock = "exclusive")]
+            fn get(&self) -> u32;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        let err = super::lock_kind(attrs).unwrap_err();
+        assert_eq!(err.to_string(), "expected `read` or `write`");
+
+        let item: syn::TraitItem = parse_quote!(
+            fn get(&self) -> u32;
+        );
+        let attrs = super::trait_item_attributes(&item).unwrap();
+        assert_eq!(super::lock_kind(attrs).unwrap(), None);
+    }
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(super::edit_distance("Box", "Box"), 0);
+        assert_eq!(super::edit_distance("Boxx", "Box"), 1);
+        assert_eq!(super::edit_distance("Rf", "Ref"), 1);
+        assert_eq!(super::edit_distance("kitten", "sitting"), 3);
+        assert_eq!(super::edit_distance("", "Mut"), 3);
+    }
+
+    #[test]
+    fn is_sized_method() {
+        let sig: syn::Signature = parse_quote!(fn method(&self));
+        assert!(!super::is_sized_method(&sig));
+        let sig: syn::Signature = parse_quote!(fn method(&self) where Self: Sized);
+        assert!(super::is_sized_method(&sig));
+        let sig: syn::Signature =
+            parse_quote!(fn method(&self) where Self: Clone + std::marker::Sized);
+        assert!(super::is_sized_method(&sig));
+        let sig: syn::Signature = parse_quote!(fn method(&self) where Self: ?Sized);
+        assert!(!super::is_sized_method(&sig));
+        let sig: syn::Signature = parse_quote!(fn method<T>(&self, t: T) where T: Sized);
+        assert!(!super::is_sized_method(&sig));
+    }
+
+    #[test]
+    fn forwarded_methods() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Shape {
+                fn area(&self) -> f64;
+                fn scaled(&self) -> Vec<f64> where Self: Sized {
+                    vec![self.area()]
+                }
+            }
+        );
+        let (methods, unsized_) = super::forwarded_methods(&trait_);
+        let names = methods.iter().map(|m| m.sig.ident.to_string()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["area"]);
+        assert!(unsized_);
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Shape {
+                fn area(&self) -> f64;
+                fn scaled(&self) -> Vec<f64> where Self: Sized;
+            }
+        );
+        let (methods, unsized_) = super::forwarded_methods(&trait_);
+        assert_eq!(methods.len(), 2);
+        assert!(!unsized_);
+    }
+
+    #[test]
+    fn cfg_attributes() {
+        let attrs: Vec<syn::Attribute> = vec![
+            parse_quote!(#[cfg(feature = "x")]),
+            parse_quote!(#[cfg_attr(feature = "y", allow(unused))]),
+            parse_quote!(#[cfg_attr(unix, cfg(feature = "fs"), doc = "Unix only.")]),
+            parse_quote!(#[cfg_attr(unix, cfg_attr(test, cfg(debug_assertions), must_use))]),
+            parse_quote!(#[allow(unused)]),
+            parse_quote!(#[doc = "A trait."]),
+        ];
+        let expected: Vec<syn::Attribute> = vec![
+            parse_quote!(#[cfg(feature = "x")]),
+            parse_quote!(#[cfg_attr(unix, cfg(feature = "fs"))]),
+            parse_quote!(#[cfg_attr(unix, cfg_attr(test, cfg(debug_assertions)))]),
+        ];
+        assert_eq!(super::cfg_attributes(&attrs), expected);
+    }
+
+    #[test]
+    fn is_pointer_receiver() {
+        let sig: syn::Signature = parse_quote!(fn spawn(self: Rc<Self>));
+        assert!(super::is_pointer_receiver(&sig.inputs[0], "Rc"));
+        assert!(!super::is_pointer_receiver(&sig.inputs[0], "Arc"));
+        let sig: syn::Signature = parse_quote!(fn spawn(self: std::rc::Rc<Self>));
+        assert!(super::is_pointer_receiver(&sig.inputs[0], "Rc"));
+        let sig: syn::Signature = parse_quote!(fn spawn(self: Arc<Self>));
+        assert!(super::is_pointer_receiver(&sig.inputs[0], "Arc"));
+        assert!(!super::is_pointer_receiver(&sig.inputs[0], "Rc"));
+        let sig: syn::Signature = parse_quote!(fn spawn(&self, rc: Rc<Self>));
+        assert!(!super::is_pointer_receiver(&sig.inputs[0], "Rc"));
+        assert!(!super::is_pointer_receiver(&sig.inputs[1], "Rc"));
+    }
+
+    #[test]
+    fn normalize_receiver() {
+        let mut sig: syn::Signature = parse_quote!(fn read(self: &Self) -> u8);
+        super::normalize_receiver(&mut sig);
+        assert_eq!(sig, parse_quote!(fn read(&self) -> u8));
+
+        let mut sig: syn::Signature = parse_quote!(fn write<'a>(self: &'a mut Self, x: u8));
+        super::normalize_receiver(&mut sig);
+        assert_eq!(sig, parse_quote!(fn write<'a>(&'a mut self, x: u8)));
+
+        let mut sig: syn::Signature = parse_quote!(fn consume(mut self: Self));
+        super::normalize_receiver(&mut sig);
+        assert_eq!(sig, parse_quote!(fn consume(mut self)));
+
+        let mut sig: syn::Signature = parse_quote!(fn boxed(self: Box<Self>));
+        super::normalize_receiver(&mut sig);
+        assert_eq!(sig, parse_quote!(fn boxed(self: Box<Self>)));
+    }
+
+    #[test]
+    fn type_to_string() {
+        let ty: syn::Type = parse_quote!(std::pin::Pin<std::rc::Rc<Self>>);
+        assert_eq!(
+            super::type_to_string(&ty),
+            "std::pin::Pin<std::rc::Rc<Self>>"
+        );
+        let ty: syn::Type = parse_quote!(&'a mut HashMap<K, V>);
+        assert_eq!(super::type_to_string(&ty), "&'a mut HashMap<K, V>");
+    }
+
+    #[test]
+    fn returns_self() {
+        let sig: syn::Signature = parse_quote!(fn with_flag(self) -> Self);
+        assert!(super::returns_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn with_flag(self) -> Option<Self>);
+        assert!(!super::returns_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn with_flag(self));
+        assert!(!super::returns_self(&sig));
+    }
+
+    #[test]
+    fn returns_wrapped_self() {
+        let sig: syn::Signature = parse_quote!(fn build(self) -> Result<Self, Error>);
+        assert!(super::returns_wrapped_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn build(self) -> std::io::Result<Self>);
+        assert!(super::returns_wrapped_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn next(&self) -> Option<Self>);
+        assert!(super::returns_wrapped_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn next(&self) -> Option<u8>);
+        assert!(!super::returns_wrapped_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn build(self) -> Result<u8, Self>);
+        assert!(!super::returns_wrapped_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn first(&self) -> Option<<Self as Collection>::Item>);
+        assert!(!super::returns_wrapped_self(&sig));
+        let sig: syn::Signature = parse_quote!(fn build(self) -> Self);
+        assert!(!super::returns_wrapped_self(&sig));
+    }
+
+    #[test]
+    fn returns_result() {
+        let sig: syn::Signature = parse_quote!(fn get(&self) -> Result<u8, Error>);
+        assert!(super::returns_result(&sig));
+        let sig: syn::Signature = parse_quote!(fn get(&self) -> std::io::Result<u8>);
+        assert!(super::returns_result(&sig));
+        let sig: syn::Signature = parse_quote!(fn get(&self) -> Option<u8>);
+        assert!(!super::returns_result(&sig));
+        let sig: syn::Signature = parse_quote!(fn set(&mut self, value: u8));
+        assert!(!super::returns_result(&sig));
+    }
+
+    #[test]
+    fn returns_option() {
+        let sig: syn::Signature = parse_quote!(fn get(&self) -> Option<u8>);
+        assert!(super::returns_option(&sig));
+        let sig: syn::Signature = parse_quote!(fn get(&self) -> std::option::Option<u8>);
+        assert!(super::returns_option(&sig));
+        let sig: syn::Signature = parse_quote!(fn get(&self) -> Result<u8, Error>);
+        assert!(!super::returns_option(&sig));
+        let sig: syn::Signature = parse_quote!(fn set(&self, value: u8));
+        assert!(!super::returns_option(&sig));
+    }
+
+    #[test]
+    fn has_supertrait() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait: std::marker::Send + Clone + 'static {}
+        );
+        assert!(super::has_supertrait(&trait_, "Send"));
+        assert!(super::has_supertrait(&trait_, "Clone"));
+        assert!(!super::has_supertrait(&trait_, "Sync"));
+    }
+
+    #[test]
+    fn wrap_returned_self() {
+        let sig: syn::Signature = parse_quote!(fn f(&self) -> (u8, ((Self,),)));
+        let wrapped = super::wrap_returned_self(&sig, parse_quote!(value), "Box").unwrap();
+        let expected: syn::Expr = parse_quote!({
+            let (__0, __1) = value;
+            (__0, {
+                let (__0,) = __1;
+                ({
+                    let (__0,) = __0;
+                    (std::convert::From::from(__0),)
+                },)
+            })
+        });
+        assert_eq!(wrapped, expected);
+
+        let sig: syn::Signature = parse_quote!(fn f(&self) -> Result<u8, Self::Error>);
+        let wrapped = super::wrap_returned_self(&sig, parse_quote!(value), "Box").unwrap();
+        assert_eq!(wrapped, parse_quote!(value));
+
+        for sig in [
+            parse_quote!(fn f(&self) -> &Self),
+            parse_quote!(fn f(&self) -> [Self; 2]),
+            parse_quote!(fn f(&self) -> Result<u8, Self>),
+        ]
+        .iter()
+        {
+            assert!(super::wrap_returned_self(sig, parse_quote!(value), "Box").is_err());
+        }
+    }
+
+    #[test]
+    fn check_object_safety() {
+        let item: syn::TraitItem = parse_quote!(
+            fn method(&self);
+        );
+        assert!(super::check_object_safety(&item, "derive `BoxDyn`").is_ok());
+        let item: syn::TraitItem = parse_quote!(
+            fn method<T>(&self, t: T);
+        );
+        let err = super::check_object_safety(&item, "derive `BoxDyn`").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot derive `BoxDyn` for a trait declaring generic methods: `method` prevents the trait from being made into an object"
+        );
+        let item: syn::TraitItem = parse_quote!(
+            fn method<T>(&self, t: T)
+            where
+                Self: Sized,
+            {
+            }
+        );
+        assert!(super::check_object_safety(&item, "derive `BoxDyn`").is_ok());
+        let item: syn::TraitItem = parse_quote!(
+            const SIZE: usize;
+        );
+        let err = super::check_object_safety(&item, "derive `BoxDyn`").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot derive `BoxDyn` for a trait declaring associated constants: `SIZE` prevents the trait from being made into an object"
+        );
+        let item: syn::TraitItem = parse_quote!(
+            fn new() -> u8;
+        );
+        let err = super::check_object_safety(&item, "derive `RefDyn`").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot derive `RefDyn` for a trait declaring associated functions: `new` prevents the trait from being made into an object"
+        );
+    }
+
+    #[test]
+    fn is_object_safe() {
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                fn method(&self);
+                fn consume(self);
+                fn sized(self) -> Self
+                where
+                    Self: Sized,
+                {
+                    self
+                }
+            }
+        );
+        assert!(super::is_object_safe(&trait_, "derive `BoxDyn`").is_ok());
+
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                const SIZE: usize;
+                fn generic<T>(&self, t: T);
+                fn compare(&self, other: &Self) -> bool;
+                fn new() -> u8;
+                fn fork(&self) -> Option<Self>;
+                fn method(&self);
+            }
+        );
+        let err = super::is_object_safe(&trait_, "derive `BoxDyn`").unwrap_err();
+        let messages = err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "cannot derive `BoxDyn` for a trait declaring associated constants: `SIZE` prevents the trait from being made into an object",
+                "cannot derive `BoxDyn` for a trait declaring generic methods: `generic` prevents the trait from being made into an object",
+                "cannot derive `BoxDyn` for a trait declaring methods taking `Self` arguments: `compare` prevents the trait from being made into an object",
+                "cannot derive `BoxDyn` for a trait declaring associated functions: `new` prevents the trait from being made into an object",
+                "cannot derive `BoxDyn` for a trait declaring methods returning `Self`: `fork` prevents the trait from being made into an object",
+            ]
+        );
+    }
+
+    #[test]
+    fn forwarded_associated_items() {
+        let generic_type = parse_quote!(MT);
+        let trait_path = parse_quote!(Trait);
+        let trait_: syn::ItemTrait = parse_quote!(
+            trait Trait {
+                type Item;
+                type Error = ();
+                const MASK: u8;
+                const SHIFT: u8 = 2;
+                fn next(&mut self) -> Option<Self::Item>;
+            }
+        );
+        let forwarded = super::forwarded_associated_items(&trait_, &generic_type, &trait_path);
+        let expected: Vec<syn::ImplItem> = vec![
+            parse_quote!(type Item = <MT as Trait>::Item;),
+            parse_quote!(const MASK: u8 = <MT as Trait>::MASK;),
+        ];
+        assert_eq!(forwarded.unwrap(), expected);
+    }
+
+    #[test]
+    fn forward_associated_const() {
+        let generic_type = parse_quote!(MT);
+        let trait_path = parse_quote!(Trait<T>);
+        let c = parse_quote!(
+            const MASK: [u8; 4];
+        );
+        let forwarded = super::forward_associated_const(&c, &generic_type, &trait_path);
+        let expected: syn::ImplItem = parse_quote!(
+            const MASK: [u8; 4] = <MT as Trait<T>>::MASK;
+        );
+        assert_eq!(forwarded, expected);
+
+        let c = parse_quote!(
+            const PAIR: (&'static str, Option<u8>) = ("", None);
+        );
+        let forwarded = super::forward_associated_const(&c, &generic_type, &trait_path);
+        let expected: syn::ImplItem = parse_quote!(
+            const PAIR: (&'static str, Option<u8>) = <MT as Trait<T>>::PAIR;
+        );
+        assert_eq!(forwarded, expected);
+    }
+
+    #[test]
+    fn forward_associated_type() {
+        let generic_type = parse_quote!(MT);
+        let trait_path = parse_quote!(Trait<T>);
+
+        let ty = parse_quote!(
+            type Item: Clone;
+        );
+        let forwarded = super::forward_associated_type(&ty, &generic_type, &trait_path);
+        let expected: syn::ImplItem = parse_quote!(
+            type Item = <MT as Trait<T>>::Item;
+        );
+        assert_eq!(forwarded.unwrap(), expected);
+
+        let ty = parse_quote!(
+            type Iter<'a>: Iterator
+            where
+                Self: 'a;
+        );
+        let forwarded = super::forward_associated_type(&ty, &generic_type, &trait_path);
+        let expected: syn::ImplItem = parse_quote!(
+            type Iter<'a>
+                = <MT as Trait<T>>::Iter<'a>
+            where
+                Self: 'a;
+        );
+        assert_eq!(forwarded.unwrap(), expected);
+    }
+
+    #[test]
+    fn impl_where_clause() {
+        let generic_type = parse_quote!(MT);
+        let trait_ = parse_quote!(
+            trait MyTrait {}
+        );
+        assert_eq!(
+            super::impl_where_clause(&trait_, &generic_type).unwrap(),
+            None
+        );
+
+        let trait_ = parse_quote!(
+            trait MyTrait<T>
+            where
+                T: Clone,
+            {
+            }
+        );
+        let expected = parse_quote!(where T: Clone,);
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+
+        let trait_ = parse_quote!(
+            trait MyTrait
+            where
+                Self: Clone,
+            {
+            }
+        );
+        let expected = parse_quote!(where Self: Clone, MT: Clone,);
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+
+        let trait_ = parse_quote!(
+            trait MyTrait<T>
+            where
+                T: Into<Self>,
+            {
+            }
+        );
+        let expected = parse_quote!(where T: Into<Self>, T: Into<MT>,);
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn impl_where_clause_sized() {
+        let generic_type = parse_quote!(PT);
+        let trait_ = parse_quote!(
+            trait Proc<T>
+            where
+                T: Send,
+                T: 'static,
+                Self: Sized,
+            {
+            }
+        );
+        let expected = parse_quote!(where T: Send, T: 'static,);
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+
+        let trait_ = parse_quote!(
+            trait Proc<T>
+            where
+                Self: Sized + Clone,
+            {
+            }
+        );
+        let expected = parse_quote!(where Self: Clone, PT: Clone,);
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+
+        let trait_ = parse_quote!(
+            trait Proc
+            where
+                Self: std::marker::Sized,
+            {
+            }
+        );
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), None);
+    }
+
+    #[test]
+    fn impl_where_clause_associated_types() {
+        let generic_type = parse_quote!(MT);
+        let trait_ = parse_quote!(
+            trait MyTrait<T>
+            where
+                Self::Item: Clone,
+                T: Into<Self::Item>,
+                Self::Other: Clone,
+            {
+                type Item;
+            }
+        );
+        let expected = parse_quote!(
+            where
+                <MT as MyTrait<T>>::Item: Clone,
+                T: Into<<MT as MyTrait<T>>::Item>,
+                Self::Other: Clone,
+                MT::Other: Clone,
+        );
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+
+        let trait_ = parse_quote!(
+            trait MyTrait: Source
+            where
+                <Self as MyTrait>::Item: Clone,
+                <Self as Source>::Item: Clone,
+            {
+                type Item;
+            }
+        );
+        let expected = parse_quote!(
+            where
+                <MT as MyTrait>::Item: Clone,
+                <Self as Source>::Item: Clone,
+                <MT as Source>::Item: Clone,
+        );
+        let rewritten = super::impl_where_clause(&trait_, &generic_type);
+        assert_eq!(rewritten.unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn trait_to_generic_ident() {
+        let trait_ = syn::parse_quote!(
+            trait Trait {}
+        );
+        let expected: syn::Ident = syn::parse_quote!(T);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+
+        let trait_ = syn::parse_quote!(
+            trait SomeTrait {}
+        );
+        let expected: syn::Ident = syn::parse_quote!(ST);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+
+        let trait_ = syn::parse_quote!(
+            trait Trait<T> {}
+        );
+        let expected: syn::Ident = syn::parse_quote!(T_);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+
+        let trait_ = syn::parse_quote!(
+            trait Trait {
+                fn method<T>(&self, t: T);
+            }
+        );
+        let expected: syn::Ident = syn::parse_quote!(T_);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+    }
+
+    #[test]
+    fn trait_to_generic_ident_short() {
+        let trait_ = syn::parse_quote!(
+            trait T {}
+        );
+        let expected: syn::Ident = syn::parse_quote!(__BlanketT);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+
+        let trait_ = syn::parse_quote!(
+            trait X<T> {}
+        );
+        let expected: syn::Ident = syn::parse_quote!(__BlanketX);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+
+        let trait_ = syn::parse_quote!(
+            trait IO<__BlanketIO> {}
+        );
+        let expected: syn::Ident = syn::parse_quote!(__BlanketIO_);
+        assert_eq!(super::trait_to_generic_ident(&trait_, None), expected);
+    }
+
+    #[test]
+    fn is_declared_by_trait() {
+        let trait_ = syn::parse_quote!(
+            trait Trait<T> {
+                fn method<U, const N: usize>(&self);
+            }
+        );
+        for name in ["Trait", "T", "U", "N"].iter() {
+            let ident = syn::Ident::new(name, proc_macro2::Span::call_site());
+            assert!(super::is_declared_by_trait(&trait_, &ident));
+        }
+        let ident = syn::Ident::new("Inner", proc_macro2::Span::call_site());
+        assert!(!super::is_declared_by_trait(&trait_, &ident));
+    }
+
+    #[test]
+    fn impl_for_pointer() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Trait<'a> {}
+        );
+        let item: syn::ItemImpl = syn::parse_quote!(
+            impl<'a, T: Trait<'a> + ?Sized, P: std::ops::Deref<Target = T>> Trait<'a> for P {}
+        );
+        let pointer: syn::Path = syn::parse_quote!(crate::Shared);
+        let expected: syn::ItemImpl = syn::parse_quote!(
+            impl<'a, T: Trait<'a>> Trait<'a> for crate::Shared<T> {}
+        );
+        assert_eq!(super::impl_for_pointer(&item, &trait_, &pointer, None), expected);
+    }
+
+    #[test]
+    fn boxed_alias() {
+        let alias: syn::Ident = syn::parse_quote!(BoxedTrait);
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            pub trait Trait<'a, 'b: 'a, T: Clone = u8, const N: usize>
+            where
+                T: Send,
+            {
+                fn method(&self, t: &'a T);
+            }
+        );
+        let item = super::boxed_alias(&trait_, &alias).unwrap();
+        let expected: syn::ItemType = syn::parse_quote!(
+            #[doc = " A boxed trait object of [`Trait`]."]
+            pub type BoxedTrait<'a, 'b, T = u8, const N: usize> =
+                std::boxed::Box<dyn Trait<'a, 'b, T, N>>;
+        );
+        assert_eq!(item, expected);
+
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Trait {
+                fn new() -> Self;
+            }
+        );
+        let err = super::boxed_alias(&trait_, &alias).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot declare `boxed_alias = \"BoxedTrait\"` for a trait declaring methods returning `Self`: `new` prevents the trait from being made into an object"
+        );
+    }
+
+    #[test]
+    fn borrowed_return() {
+        let borrowed: Vec<syn::Signature> = vec![
+            syn::parse_quote!(fn name(&self) -> &str),
+            syn::parse_quote!(fn name(&self) -> Option<&str>),
+            syn::parse_quote!(fn name(&self) -> &&str),
+            syn::parse_quote!(fn name(&self) -> std::cell::Ref<'_, str>),
+            syn::parse_quote!(fn name<'s>(&'s self) -> Result<&'s str, ()>),
+            syn::parse_quote!(fn names(&self) -> Box<dyn Iterator<Item = &str>>),
+            syn::parse_quote!(fn name(&self) -> (u8, &str)),
+        ];
+        for sig in borrowed.iter() {
+            assert!(super::borrowed_return(sig).is_some());
+        }
+
+        let owned: Vec<syn::Signature> = vec![
+            syn::parse_quote!(fn name(&self)),
+            syn::parse_quote!(fn name(&self) -> String),
+            syn::parse_quote!(fn name(&self) -> &'static str),
+            syn::parse_quote!(fn name(&self) -> Option<&'a str>),
+            syn::parse_quote!(fn name<'s>(&'s self, other: &str) -> &'a &'a str),
+            syn::parse_quote!(fn callback(&self) -> Box<dyn Fn(&str)>),
+            syn::parse_quote!(fn callback(&self) -> fn(&u8) -> u8),
+            syn::parse_quote!(fn callback(&self) -> Box<dyn for<'a> Fn(&'a str) -> &'a str>),
+        ];
+        for sig in owned.iter() {
+            assert!(super::borrowed_return(sig).is_none());
+        }
+    }
+
+    #[test]
+    fn impl_for_inner() {
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Named {
+                type Name;
+                fn name(&self) -> Self::Name;
+            }
+        );
+        let item: syn::ItemImpl = syn::parse_quote!(
+            impl<N: Named + std::clone::Clone> Named for std::sync::Arc<N>
+            where
+                N: 'static,
+            {
+                type Name = <N as Named>::Name;
+                fn name(&self) -> Self::Name {
+                    (*(*self)).name()
+                }
+            }
+        );
+        let inner: syn::Type = syn::parse_quote!([u8]);
+        let expected: syn::ItemImpl = syn::parse_quote!(
+            impl Named for std::sync::Arc<[u8]> {
+                type Name = <[u8] as Named>::Name;
+                fn name(&self) -> Self::Name {
+                    (*(*self)).name()
+                }
+            }
+        );
+        assert_eq!(super::impl_for_inner(&item, &trait_, &inner), expected);
+    }
+
+    #[test]
+    fn impl_for_inner_associated() {
+        // the default generic type of `Counter` is `C`, which must not be
+        // confused with the associated const of the same name
+        let trait_: syn::ItemTrait = syn::parse_quote!(
+            trait Counter {
+                const C: u8;
+                fn get(&self) -> u8;
+            }
+        );
+        let item: syn::ItemImpl = syn::parse_quote!(
+            impl<C: Counter + ?Sized> Counter for std::rc::Rc<C> {
+                const C: u8 = <C as Counter>::C;
+                fn get(&self) -> u8 {
+                    (*(*self)).get()
+                }
+            }
+        );
+        let inner: syn::Type = syn::parse_quote!(str);
+        let expected: syn::ItemImpl = syn::parse_quote!(
+            impl Counter for std::rc::Rc<str> {
+                const C: u8 = <str as Counter>::C;
+                fn get(&self) -> u8 {
+                    (*(*self)).get()
+                }
+            }
+        );
+        assert_eq!(super::impl_for_inner(&item, &trait_, &inner), expected);
+    }
+
+    #[test]
+    fn trait_to_pointer_ident() {
+        let trait_ = syn::parse_quote!(
+            trait Trait {}
+        );
+        let generic_type = syn::parse_quote!(T);
+        let expected: syn::Ident = syn::parse_quote!(P);
+        assert_eq!(
+            super::trait_to_pointer_ident(&trait_, &generic_type),
+            expected
+        );
+
+        let trait_ = syn::parse_quote!(
+            trait Pointer {}
+        );
+        let generic_type = syn::parse_quote!(P);
+        let expected: syn::Ident = syn::parse_quote!(P_);
+        assert_eq!(
+            super::trait_to_pointer_ident(&trait_, &generic_type),
+            expected
+        );
+
+        let trait_ = syn::parse_quote!(
+            trait Pointer<P_> {}
+        );
+        let generic_type = syn::parse_quote!(P);
+        let expected: syn::Ident = syn::parse_quote!(P__);
+        assert_eq!(
+            super::trait_to_pointer_ident(&trait_, &generic_type),
+            expected
+        );
+    }
+
+    #[test]
+    fn generics_declaration_to_generics() {
+        let generics: syn::Generics =
+            parse_quote!(<'a, 'b: 'a, #[cfg(x)] T: 'a + Send = u8, const N: usize, U>);
+        let expected: syn::Generics = parse_quote!(<'a, 'b, #[cfg(x)] T, N, U>);
+        let params = super::generics_declaration_to_generics(&generics.params).unwrap();
+        assert_eq!(params, expected.params);
+
+        let generics: syn::Generics = parse_quote!(<>);
+        let params = super::generics_declaration_to_generics(&generics.params).unwrap();
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn trait_generic_names() {
+        let trait_ = syn::parse_quote!(
+            trait Trait<'a, 'b: 'a, T: 'a + Send> {}
+        );
+        let expected: syn::Generics = syn::parse_quote!(<'a, 'b, T>);
+        assert_eq!(super::trait_generic_names(&trait_).unwrap(), expected);
+    }
+
+    #[test]
+    fn impl_generics_inter_parameter_bounds() {
+        let trait_ = syn::parse_quote!(
+            trait Pair<A, B: Into<A>> {}
+        );
+        let expected: syn::Generics = syn::parse_quote!(<A, B>);
+        assert_eq!(super::trait_generic_names(&trait_).unwrap(), expected);
+        let expected: syn::Generics = syn::parse_quote!(<A, B: Into<A>, P: Pair<A, B>>);
+        let generics = super::impl_generics(&trait_, Some(syn::parse_quote!(P: Pair<A, B>)));
+        assert_eq!(generics, expected);
+    }
+
+    #[test]
+    fn impl_generics_lifetime_supertraits() {
+        let trait_ = syn::parse_quote!(
+            trait Event<'a>: Send + 'a + 'static {}
+        );
+        let expected: syn::Generics = syn::parse_quote!(<'a, E: Event<'a> + ?Sized + 'a + 'static>);
+        let generics = super::impl_generics(&trait_, Some(syn::parse_quote!(E: Event<'a> + ?Sized)));
+        assert_eq!(generics, expected);
+    }
+
+    #[test]
+    fn impl_generics() {
+        let trait_ = syn::parse_quote!(
+            trait Trait {}
+        );
+        let param = syn::parse_quote!(T: Trait);
+        let expected: syn::Generics = syn::parse_quote!(<T: Trait>);
+        assert_eq!(super::impl_generics(&trait_, Some(param)), expected);
+
+        let trait_ = syn::parse_quote!(
+            trait Trait<'a, T: 'a>
+            where
+                T: Send,
+            {
+            }
+        );
+        let param = syn::parse_quote!(T_: Trait<'a, T>);
+        let expected: syn::Generics = syn::parse_quote!(<'a, T: 'a, T_: Trait<'a, T>>);
+        assert_eq!(super::impl_generics(&trait_, Some(param)), expected);
+    }
+
+    #[test]
+    fn impl_generics_defaults() {
+        let trait_ = syn::parse_quote!(
+            trait Buf<T: Copy = u8, const N: usize = 64> {}
+        );
+        let param = syn::parse_quote!(B: Buf<T, N>);
+        let expected: syn::Generics = syn::parse_quote!(<T: Copy, const N: usize, B: Buf<T, N>>);
+        assert_eq!(super::impl_generics(&trait_, Some(param)), expected);
+    }
+
+    #[test]
+    fn impl_generics_many() {
+        let trait_ = syn::parse_quote!(
+            trait Trait<A: Send, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T: Sync> {}
+        );
+        let names = super::trait_generic_names(&trait_).unwrap();
+        let expected: syn::Generics = syn::parse_quote!(
+            <A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>
+        );
+        assert_eq!(names, expected);
+
+        let param = syn::parse_quote!(T_: Trait #names);
+        let generics = super::impl_generics(&trait_, Some(param));
+        let expected: syn::Generics = syn::parse_quote!(
+            <A: Send, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T: Sync,
+             T_: Trait<A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T>>
+        );
+        assert_eq!(generics, expected);
+    }
+
+    #[test]
+    fn dedup_bounds() {
+        let mut generics: syn::Generics = parse_quote!(<T: Send, MT: Trait<T> + Send>);
+        generics.where_clause = Some(parse_quote!(where MT: Send, T: Send + Sync, T: Sync));
+        super::dedup_bounds(&mut generics);
+        let expected: syn::WhereClause = parse_quote!(where T: Sync);
+        assert_eq!(generics.where_clause, Some(expected));
+
+        let mut generics: syn::Generics = parse_quote!(<T: Send>);
+        generics.where_clause = Some(parse_quote!(where T: Send, for<'a> T: Send));
+        super::dedup_bounds(&mut generics);
+        let expected: syn::WhereClause = parse_quote!(where for<'a> T: Send);
+        assert_eq!(generics.where_clause, Some(expected));
+
+        let mut generics: syn::Generics = parse_quote!(<T: Send>);
+        generics.where_clause = Some(parse_quote!(where T: Send));
+        super::dedup_bounds(&mut generics);
+        assert_eq!(generics.where_clause, None);
+
+        let mut generics: syn::Generics = parse_quote!(<T: Clone + Send>);
+        generics.where_clause = Some(parse_quote!(where T: Send + Clone, Vec<T>: A + B, Vec<T>: B + A));
+        super::dedup_bounds(&mut generics);
+        let expected: syn::WhereClause = parse_quote!(where Vec<T>: A + B);
+        assert_eq!(generics.where_clause, Some(expected));
     }
 }
@@ -51,7 +51,7 @@ fn test_overload() {
 
     impl Visitor for CharBytesCounter {
         fn visit_str(&mut self, s: &str) {
-            self.bytes += s.as_bytes().len();
+            self.bytes += s.len();
             self::visitor::visit_str(self, s);
         }
         fn visit_char(&mut self, c: char) {
@@ -67,5 +67,5 @@ fn test_overload() {
         counter.count,
         string.chars().enumerate().last().unwrap().0 + 1
     );
-    assert_eq!(counter.bytes, string.as_bytes().len());
+    assert_eq!(counter.bytes, string.len());
 }
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(ArcCow))]
+pub trait Counter {
+    fn get(self: Box<Self>) -> u8;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(ArcCow))]
+pub trait Buffer {
+    fn push(&mut self, byte: u8);
+    fn into_bytes(self) -> Vec<u8>;
+}
+
+fn main() {}
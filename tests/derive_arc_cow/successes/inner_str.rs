@@ -0,0 +1,45 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcCow, Rc), inner = "str")]
+pub trait Name {
+    fn name(&self) -> String;
+}
+
+impl Name for str {
+    fn name(&self) -> String {
+        self.to_uppercase()
+    }
+}
+
+#[blanket(derive(ArcCow), inner = "[u8]")]
+pub trait Checksum {
+    fn checksum(&self) -> u8;
+}
+
+impl Checksum for [u8] {
+    fn checksum(&self) -> u8 {
+        self.iter().fold(0, |acc, x| acc.wrapping_add(*x))
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<str>:    Name));
+    assert!(impls!(Rc<str>:     Name));
+    assert!(impls!(Arc<String>: !Name));
+    assert!(impls!(Arc<[u8]>:   Checksum));
+
+    let name: Arc<str> = Arc::from("alice");
+    assert_eq!(Name::name(&name), "ALICE");
+    let name: Rc<str> = Rc::from("bob");
+    assert_eq!(Name::name(&name), "BOB");
+
+    let bytes: Arc<[u8]> = Arc::from(&[1u8, 2, 3][..]);
+    assert_eq!(bytes.checksum(), 6);
+}
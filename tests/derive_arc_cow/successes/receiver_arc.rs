@@ -0,0 +1,41 @@
+extern crate blanket;
+extern crate impls;
+
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcCow))]
+pub trait Task {
+    fn name(&self) -> &str;
+    fn spawn(self: Arc<Self>, times: u32) -> usize;
+}
+
+#[derive(Clone, Default)]
+struct Job {
+    runs: Arc<AtomicU32>,
+}
+
+impl Task for Job {
+    fn name(&self) -> &str {
+        "job"
+    }
+    fn spawn(self: Arc<Self>, times: u32) -> usize {
+        self.runs.fetch_add(times, Ordering::SeqCst);
+        Arc::strong_count(&self)
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<Job>: Task));
+
+    let job = Arc::new(Job::default());
+    let shared = Arc::new(job.clone());
+    // the wrapped `Arc` is given to the wrapped type
+    assert_eq!(Task::spawn(shared, 2), 3);
+    assert_eq!(job.runs.load(Ordering::SeqCst), 2);
+    assert_eq!(Arc::strong_count(&job), 1);
+}
@@ -0,0 +1,49 @@
+extern crate blanket;
+extern crate impls;
+
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcCow))]
+pub trait Buffer {
+    fn bytes(&self) -> &[u8];
+    fn push(&mut self, byte: u8);
+}
+
+#[derive(Clone, Default)]
+struct Bytes(Vec<u8>);
+
+impl Buffer for Bytes {
+    fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+    fn push(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+}
+
+struct Unique(Vec<u8>);
+
+impl Buffer for Unique {
+    fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+    fn push(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<Bytes>: Buffer));
+    assert!(impls!(Arc<Unique>: !Buffer));
+
+    let mut original = Arc::new(Bytes::default());
+    original.push(1);
+    let mut copy = original.clone();
+    copy.push(2);
+    assert_eq!(original.bytes(), [1]);
+    assert_eq!(copy.bytes(), [1, 2]);
+    assert!(!Arc::ptr_eq(&original, &copy));
+}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(ArcMutex))]
+pub trait Queue {
+    fn peek(&self) -> Option<&u32>;
+}
+
+fn main() {}
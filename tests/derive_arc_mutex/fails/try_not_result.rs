@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(ArcMutexTry))]
+pub trait Counter {
+    fn get(&self) -> Option<u8>;
+}
+
+fn main() {}
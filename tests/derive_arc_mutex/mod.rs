@@ -0,0 +1,11 @@
+extern crate trybuild;
+
+fn main() {
+    #[cfg(not(tarpaulin))]
+    let t = trybuild::TestCases::new();
+    t.compile_fail(file!().replace("mod.rs", "fails/*.rs"));
+    #[cfg(not(feature = "parking_lot"))]
+    t.pass(file!().replace("mod.rs", "successes/*.rs"));
+    #[cfg(feature = "parking_lot")]
+    t.pass(file!().replace("mod.rs", "parking_lot/*.rs"));
+}
@@ -0,0 +1,55 @@
+extern crate blanket;
+extern crate impls;
+extern crate parking_lot;
+
+use std::sync::Arc;
+use std::sync::TryLockError;
+
+use blanket::blanket;
+use impls::impls;
+use parking_lot::Mutex;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Busy,
+}
+
+impl From<TryLockError<()>> for Error {
+    fn from(_: TryLockError<()>) -> Self {
+        Error::Busy
+    }
+}
+
+#[blanket(derive(ArcMutexTry))]
+pub trait Counter {
+    fn get(&self) -> Result<u8, Error>;
+    fn set(&mut self, value: u8) -> Result<(), Error>;
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> Result<u8, Error> {
+        Ok(self.0)
+    }
+    fn set(&mut self, value: u8) -> Result<(), Error> {
+        self.0 = value;
+        Ok(())
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<Mutex<SimpleCounter>>: Counter));
+    assert!(impls!(Arc<Mutex<dyn Counter>>:   Counter));
+
+    let counter = Arc::new(Mutex::new(SimpleCounter::default()));
+    let mut shared = counter.clone();
+    assert_eq!(shared.set(3), Ok(()));
+    assert_eq!(counter.get(), Ok(3));
+
+    let guard = counter.lock();
+    assert_eq!(shared.get(), Err(Error::Busy));
+    drop(guard);
+    assert_eq!(shared.get(), Ok(3));
+}
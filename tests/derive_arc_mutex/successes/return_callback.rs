@@ -0,0 +1,37 @@
+extern crate blanket;
+extern crate impls;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcMutex))]
+pub trait Parser {
+    fn callback(&self) -> Box<dyn Fn(&str) -> usize>;
+    fn pointer(&self) -> fn(&u8) -> u8;
+}
+
+struct Length;
+
+fn double(x: &u8) -> u8 {
+    x * 2
+}
+
+impl Parser for Length {
+    fn callback(&self) -> Box<dyn Fn(&str) -> usize> {
+        Box::new(|s| s.len())
+    }
+    fn pointer(&self) -> fn(&u8) -> u8 {
+        double
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<Mutex<Length>>: Parser));
+
+    let parser = Arc::new(Mutex::new(Length));
+    assert_eq!((parser.callback())("abc"), 3);
+    assert_eq!((parser.pointer())(&2), 4);
+}
@@ -0,0 +1,59 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcMutex), thread_safe)]
+pub trait Counter: Send {
+    fn get(&self) -> u8;
+    fn increment(&mut self);
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> u8 {
+        self.0
+    }
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+}
+
+struct CellCounter(std::cell::Cell<u8>);
+
+impl Counter for CellCounter {
+    fn get(&self) -> u8 {
+        self.0.get()
+    }
+    fn increment(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn spawn_increment<C: Counter + Clone + 'static>(counter: &C) -> thread::JoinHandle<()> {
+    let mut counter = counter.clone();
+    thread::spawn(move || counter.increment())
+}
+
+fn main() {
+    assert!(impls!(Arc<Mutex<SimpleCounter>>: Counter));
+    assert!(impls!(Arc<Mutex<CellCounter>>: !Counter));
+    assert!(impls!(Rc<Mutex<SimpleCounter>>: !Counter));
+
+    let counter = Arc::new(Mutex::new(SimpleCounter::default()));
+    let handles = (0..4)
+        .map(|_| spawn_increment(&counter))
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(counter.get(), 4);
+}
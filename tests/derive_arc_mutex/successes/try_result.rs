@@ -0,0 +1,65 @@
+extern crate blanket;
+extern crate impls;
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::TryLockError;
+
+use blanket::blanket;
+use impls::impls;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Poisoned,
+    Busy,
+}
+
+impl From<TryLockError<()>> for Error {
+    fn from(e: TryLockError<()>) -> Self {
+        match e {
+            TryLockError::Poisoned(_) => Error::Poisoned,
+            TryLockError::WouldBlock => Error::Busy,
+        }
+    }
+}
+
+#[blanket(derive(ArcMutexTry))]
+pub trait Counter {
+    fn get(&self) -> Result<u8, Error>;
+    fn set(&mut self, value: u8) -> Result<(), Error>;
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> Result<u8, Error> {
+        Ok(self.0)
+    }
+    fn set(&mut self, value: u8) -> Result<(), Error> {
+        self.0 = value;
+        Ok(())
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<Mutex<SimpleCounter>>: Counter));
+    assert!(impls!(Arc<Mutex<dyn Counter>>:   Counter));
+
+    let counter = Arc::new(Mutex::new(SimpleCounter::default()));
+    let mut shared = counter.clone();
+    assert_eq!(shared.set(3), Ok(()));
+    assert_eq!(counter.get(), Ok(3));
+
+    let guard = counter.lock().unwrap();
+    assert_eq!(shared.get(), Err(Error::Busy));
+    drop(guard);
+
+    let poisoner = counter.clone();
+    let _ = std::thread::spawn(move || {
+        let _guard = poisoner.lock().unwrap();
+        panic!("poison the mutex");
+    })
+    .join();
+    assert_eq!(shared.get(), Err(Error::Poisoned));
+}
@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(ArcRwLock))]
+pub trait Counter {
+    fn get(&self) -> u8;
+    fn into_count(self) -> u8;
+}
+
+fn main() {}
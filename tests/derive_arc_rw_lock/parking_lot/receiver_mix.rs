@@ -0,0 +1,49 @@
+extern crate blanket;
+extern crate impls;
+extern crate parking_lot;
+
+use std::sync::Arc;
+use parking_lot::RwLock;
+use std::thread;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcRwLock))]
+pub trait Counter {
+    fn get(&self) -> u8;
+    fn increment(&mut self);
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> u8 {
+        self.0
+    }
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<RwLock<SimpleCounter>>: Counter));
+    assert!(impls!(Arc<RwLock<dyn Counter + Send + Sync>>: Counter));
+
+    let counter = Arc::new(RwLock::new(SimpleCounter::default()));
+    let handles = (0..4)
+        .map(|_| {
+            let mut counter = counter.clone();
+            thread::spawn(move || counter.increment())
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // several read locks can be held at once
+    let guard = counter.read();
+    assert_eq!(counter.get(), 4);
+    assert_eq!(guard.get(), 4);
+}
@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(ArcRwLock))]
+pub trait Generator {
+    // the interior mutability is only sound with an exclusive lock
+    #[blanket(lock = "write")]
+    fn next_id(&self) -> u32;
+    fn peek(&self) -> u32;
+}
+
+#[derive(Default)]
+struct IdGenerator(Cell<u32>);
+
+impl Generator for IdGenerator {
+    fn next_id(&self) -> u32 {
+        let id = self.0.get();
+        self.0.set(id + 1);
+        id
+    }
+    fn peek(&self) -> u32 {
+        self.0.get()
+    }
+}
+
+fn main() {
+    assert!(impls!(Arc<RwLock<IdGenerator>>: Generator));
+
+    let generator = Arc::new(RwLock::new(IdGenerator::default()));
+    assert_eq!(generator.next_id(), 0);
+    assert_eq!(generator.next_id(), 1);
+    assert_eq!(generator.peek(), 2);
+}
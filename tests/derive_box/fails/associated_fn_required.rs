@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Make {
+    fn make() -> Self;
+    fn name(&self) -> &str;
+}
+
+fn main() {}
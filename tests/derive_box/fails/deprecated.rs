@@ -0,0 +1,24 @@
+#![deny(deprecated)]
+
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[deprecated]
+    fn count(&self) -> u8;
+}
+
+struct Zero;
+
+impl Counter for Zero {
+    fn count(&self) -> u8 {
+        0
+    }
+}
+
+fn main() {
+    let counter = Box::new(Zero);
+    let _ = counter.count();
+}
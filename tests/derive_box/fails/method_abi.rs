@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    extern "C" fn get(&self) -> u8;
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![deny(unused_must_use)]
+
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[must_use]
+    fn count(&self) -> u8;
+}
+
+struct Zero;
+
+impl Counter for Zero {
+    fn count(&self) -> u8 {
+        0
+    }
+}
+
+fn main() {
+    let counter = Box::new(Zero);
+    counter.count();
+}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    fn increment(self: std::pin::Pin<std::rc::Rc<Self>>);
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Tree: Sized {
+    fn children(&self) -> Vec<Self>;
+}
+
+fn main() {}
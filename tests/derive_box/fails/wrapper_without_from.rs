@@ -0,0 +1,22 @@
+extern crate blanket;
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use blanket::blanket;
+
+pub struct Shared<T>(Arc<T>);
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[blanket(derive(Box), wrapper = "crate::Shared")]
+pub trait Counter {
+    fn reset(&self) -> Self;
+}
+
+fn main() {}
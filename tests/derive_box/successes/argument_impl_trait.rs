@@ -0,0 +1,33 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Names {
+    fn push(&mut self, item: impl Into<String>);
+    fn names(&self) -> &[String];
+}
+
+#[derive(Default)]
+struct List(Vec<String>);
+
+impl Names for List {
+    fn push(&mut self, item: impl Into<String>) {
+        self.0.push(item.into());
+    }
+    fn names(&self) -> &[String] {
+        &self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(List:      Names));
+    assert!(impls!(Box<List>: Names));
+
+    let mut list = Box::new(List::default());
+    list.push("a");
+    list.push(String::from("b"));
+    assert_eq!(list.names(), ["a", "b"]);
+}
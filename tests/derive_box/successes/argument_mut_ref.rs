@@ -0,0 +1,56 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Reader {
+    fn read(&mut self, buf: &mut [u8]) -> usize;
+    fn read_into<'a>(&mut self, buf: &'a mut Vec<u8>) -> &'a mut Vec<u8>;
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> usize {
+        let mut total = 0;
+        while !buf.is_empty() {
+            let n = self.read(buf);
+            total += n;
+            buf = &mut buf[n..];
+        }
+        total
+    }
+}
+
+#[derive(Default)]
+struct Zeros {
+    read: usize,
+}
+
+impl Reader for Zeros {
+    fn read(&mut self, buf: &mut [u8]) -> usize {
+        let n = std::cmp::min(buf.len(), 2);
+        for b in buf[..n].iter_mut() {
+            *b = 0;
+        }
+        self.read += n;
+        n
+    }
+    fn read_into<'a>(&mut self, buf: &'a mut Vec<u8>) -> &'a mut Vec<u8> {
+        buf.push(0);
+        self.read += 1;
+        buf
+    }
+}
+
+fn main() {
+    assert!(impls!(Zeros: Reader));
+    assert!(impls!(Box<Zeros>: Reader));
+
+    let mut reader = Box::new(Zeros::default());
+    let mut buf = [1u8; 5];
+    assert_eq!(reader.read(&mut buf), 2);
+    assert_eq!(reader.read_exact(&mut buf), 5);
+    let mut vec = Vec::new();
+    reader.read_into(&mut vec).push(1);
+    assert_eq!(vec, [0, 1]);
+    assert_eq!(reader.read, 8);
+    assert_eq!(buf, [0; 5]);
+}
@@ -0,0 +1,36 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    fn count(&self) -> usize;
+    fn merge(&mut self, other: &Self);
+    fn absorb(&mut self, other: Self);
+}
+
+#[derive(Default)]
+struct Count(usize);
+
+impl Counter for Count {
+    fn count(&self) -> usize {
+        self.0
+    }
+    fn merge(&mut self, other: &Self) {
+        self.0 += other.0;
+    }
+    fn absorb(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Count>: Counter));
+
+    let mut count = Box::new(Count(1));
+    count.merge(&Box::new(Count(2)));
+    count.absorb(Box::new(Count(3)));
+    assert_eq!(count.count(), 6);
+}
@@ -0,0 +1,31 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box, Rc))]
+pub trait Register {
+    const MASK: [u8; 4];
+    const RANGE: (u8, u8);
+    const NAME: &'static str = "register";
+    fn read(&self) -> u8;
+}
+
+struct Status;
+
+impl Register for Status {
+    const MASK: [u8; 4] = [0xff, 0x0f, 0x00, 0x01];
+    const RANGE: (u8, u8) = (1, 4);
+    const NAME: &'static str = "status";
+    fn read(&self) -> u8 {
+        1
+    }
+}
+
+fn main() {
+    assert_eq!(<Box<Status> as Register>::MASK, [0xff, 0x0f, 0x00, 0x01]);
+    assert_eq!(<Box<Status> as Register>::RANGE, (1, 4));
+    assert_eq!(<Status as Register>::NAME, "status");
+    assert_eq!(<Box<Status> as Register>::NAME, "register");
+    assert_eq!(<std::rc::Rc<Status> as Register>::NAME, "register");
+    assert_eq!(<Box<Box<Status>> as Register>::MASK[3], 1);
+}
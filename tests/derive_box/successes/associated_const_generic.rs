@@ -0,0 +1,36 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Packed<T, const N: usize> {
+    const BYTES: usize;
+    fn pack(&self) -> [T; N];
+}
+
+struct Pair(u16);
+
+impl Packed<u8, 2> for Pair {
+    const BYTES: usize = 2;
+    fn pack(&self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+}
+
+impl Packed<u16, 1> for Pair {
+    const BYTES: usize = 2 * std::mem::size_of::<u16>();
+    fn pack(&self) -> [u16; 1] {
+        [self.0]
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Pair>: Packed<u8, 2>));
+    assert!(impls!(&Pair:     Packed<u16, 1>));
+
+    assert_eq!(<Box<Pair> as Packed<u8, 2>>::BYTES, 2);
+    assert_eq!(<&Pair as Packed<u16, 1>>::BYTES, 4);
+    assert_eq!(Packed::<u8, 2>::pack(&Box::new(Pair(0x0102))), [2, 1]);
+}
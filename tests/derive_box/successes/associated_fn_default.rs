@@ -0,0 +1,36 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, Rc))]
+pub trait Named {
+    fn default_name() -> &'static str {
+        "x"
+    }
+    fn name(&self) -> &str;
+}
+
+struct Person;
+
+impl Named for Person {
+    fn default_name() -> &'static str {
+        "person"
+    }
+    fn name(&self) -> &str {
+        "Ada"
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Person>: Named));
+    assert!(impls!(&Person:     Named));
+    assert!(impls!(Rc<Person>:  Named));
+
+    assert_eq!(Person::default_name(), "person");
+    assert_eq!(<Box<Person>>::default_name(), "x");
+    assert_eq!(Box::new(Person).name(), "Ada");
+}
@@ -0,0 +1,34 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Container {
+    type Item;
+    type Iter<'a>: Iterator<Item = &'a Self::Item>
+    where
+        Self: 'a;
+    fn items<'a>(&'a self) -> Self::Iter<'a>;
+}
+
+struct Numbers {
+    numbers: Vec<u32>,
+}
+
+impl Container for Numbers {
+    type Item = u32;
+    type Iter<'a> = std::slice::Iter<'a, u32>;
+    fn items<'a>(&'a self) -> Self::Iter<'a> {
+        self.numbers.iter()
+    }
+}
+
+fn main() {
+    assert!(impls!(Numbers:      Container));
+    assert!(impls!(Box<Numbers>: Container));
+
+    let boxed = Box::new(Numbers { numbers: vec![1, 2, 3] });
+    assert_eq!(boxed.items().sum::<u32>(), 6);
+}
@@ -0,0 +1,44 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Db {
+    type Tx<'a>
+    where
+        Self: 'a;
+    fn begin<'a>(&'a mut self) -> Self::Tx<'a>;
+}
+
+struct Memory {
+    log: Vec<&'static str>,
+}
+
+struct Transaction<'a> {
+    log: &'a mut Vec<&'static str>,
+}
+
+impl Transaction<'_> {
+    fn commit(self) {
+        self.log.push("commit");
+    }
+}
+
+impl Db for Memory {
+    type Tx<'a> = Transaction<'a>;
+    fn begin<'a>(&'a mut self) -> Self::Tx<'a> {
+        self.log.push("begin");
+        Transaction { log: &mut self.log }
+    }
+}
+
+fn main() {
+    assert!(impls!(Memory:      Db));
+    assert!(impls!(Box<Memory>: Db));
+
+    let mut db = Box::new(Memory { log: Vec::new() });
+    db.begin().commit();
+    assert_eq!(db.log, ["begin", "commit"]);
+}
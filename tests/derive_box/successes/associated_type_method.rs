@@ -0,0 +1,54 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, Rc))]
+pub trait Graph {
+    type Node;
+    fn neighbors(&self, n: &Self::Node) -> Vec<Self::Node>;
+    fn first(&self) -> Option<Self::Node>;
+}
+
+struct Line(usize);
+
+impl Graph for Line {
+    type Node = usize;
+    fn neighbors(&self, n: &usize) -> Vec<usize> {
+        let mut neighbors = Vec::new();
+        if *n > 0 {
+            neighbors.push(n - 1);
+        }
+        if *n + 1 < self.0 {
+            neighbors.push(n + 1);
+        }
+        neighbors
+    }
+    fn first(&self) -> Option<usize> {
+        if self.0 > 0 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+fn walk<G: Graph>(graph: &G) -> Vec<G::Node> {
+    match graph.first() {
+        Some(node) => graph.neighbors(&node),
+        None => Vec::new(),
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Line>: Graph));
+    assert!(impls!(&Line: Graph));
+    assert!(impls!(std::rc::Rc<Line>: Graph));
+
+    let boxed = Box::new(Line(3));
+    assert_eq!(boxed.neighbors(&1), vec![0, 2]);
+    assert_eq!(walk(&boxed), vec![1]);
+    assert_eq!(walk(&&Line(3)), vec![1]);
+    assert_eq!(walk(&std::rc::Rc::new(Line(1))), Vec::<usize>::new());
+}
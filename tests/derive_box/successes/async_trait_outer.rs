@@ -0,0 +1,29 @@
+extern crate async_trait;
+extern crate blanket;
+extern crate impls;
+
+use async_trait::async_trait;
+use blanket::blanket;
+use impls::impls;
+
+#[async_trait]
+#[blanket(derive(Box))]
+pub trait Fetcher {
+    async fn get(&self, url: &str) -> Vec<u8>;
+    async fn put(&mut self, url: &str, data: Vec<u8>);
+}
+
+struct Echo;
+
+#[async_trait]
+impl Fetcher for Echo {
+    async fn get(&self, url: &str) -> Vec<u8> {
+        url.as_bytes().to_vec()
+    }
+    async fn put(&mut self, _url: &str, _data: Vec<u8>) {}
+}
+
+fn main() {
+    assert!(impls!(Echo:      Fetcher));
+    assert!(impls!(Box<Echo>: Fetcher));
+}
@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), bound = "C: Send + 'static")]
+pub trait Counter {
+    fn increment(&mut self);
+}
+
+struct LocalCounter<'a> {
+    count: &'a Cell<u8>,
+}
+
+impl<'a> Counter for LocalCounter<'a> {
+    fn increment(&mut self) {
+        self.count.set(self.count.get() + 1);
+    }
+}
+
+#[derive(Default)]
+struct SharedCounter {
+    count: u8,
+}
+
+impl Counter for SharedCounter {
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+fn main() {
+    assert!(impls!(SharedCounter:               Counter));
+    assert!(impls!(Box<SharedCounter>:          Counter));
+    assert!(impls!(LocalCounter<'static>:       Counter));
+    assert!(!impls!(Box<LocalCounter<'static>>: Counter));
+}
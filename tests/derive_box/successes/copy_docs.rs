@@ -0,0 +1,25 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), copy_docs)]
+pub trait Counter {
+    /// Increment the counter.
+    #[doc = " Increment the counter."]
+    fn increment(&mut self);
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<SimpleCounter>: Counter));
+}
@@ -0,0 +1,32 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), generic = "Inner", bound = "Inner: Send")]
+pub trait Counter {
+    fn get(&self) -> u8;
+    fn add<N: Into<u8>>(&mut self, n: N);
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> u8 {
+        self.0
+    }
+    fn add<N: Into<u8>>(&mut self, n: N) {
+        self.0 += n.into();
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<SimpleCounter>: Counter));
+    assert!(impls!(Box<std::rc::Rc<SimpleCounter>>: !Counter));
+
+    let mut counter = Box::new(SimpleCounter::default());
+    counter.add(2u8);
+    assert_eq!(counter.get(), 2);
+}
@@ -0,0 +1,31 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), generic = "Inner")]
+pub trait Counter {
+    type C;
+    const C: u8;
+    fn get(&self) -> Self::C;
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    type C = u8;
+    const C: u8 = 1;
+    fn get(&self) -> Self::C {
+        self.0 + Self::C
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<SimpleCounter>: Counter));
+
+    let counter = Box::new(SimpleCounter::default());
+    assert_eq!(counter.get(), 1);
+    assert_eq!(<Box<SimpleCounter> as Counter>::C, 1);
+}
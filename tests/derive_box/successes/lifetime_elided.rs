@@ -0,0 +1,76 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, Mut))]
+pub trait Named {
+    fn name(&self) -> &str;
+    fn as_bytes(&self) -> &[u8];
+    fn pick(&self, other: &str) -> &str;
+}
+
+#[blanket(derive(Box, Mut))]
+pub trait NamedMut {
+    fn name_mut(&mut self) -> &mut String;
+}
+
+struct Person(String);
+
+impl Named for Person {
+    fn name(&self) -> &str {
+        &self.0
+    }
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+    fn pick(&self, _other: &str) -> &str {
+        &self.0
+    }
+}
+
+impl NamedMut for Person {
+    fn name_mut(&mut self) -> &mut String {
+        &mut self.0
+    }
+}
+
+// the elided output lifetimes must be tied to the receiver
+fn name<N: Named>(named: &N) -> &str {
+    named.name()
+}
+
+fn as_bytes<N: Named>(named: &N) -> &[u8] {
+    named.as_bytes()
+}
+
+fn pick<'a, N: Named>(named: &'a N, other: &str) -> &'a str {
+    named.pick(other)
+}
+
+fn main() {
+    assert!(impls!(Box<Person>:     Named));
+    assert!(impls!(&Person:         Named));
+    assert!(impls!(&mut Person:     Named));
+    assert!(impls!(&Box<Person>:    Named));
+    assert!(impls!(Box<Person>:     NamedMut));
+    assert!(impls!(&mut Person:     NamedMut));
+
+    let mut boxed = Box::new(Person(String::from("Ada")));
+    assert_eq!(name(&boxed), "Ada");
+    assert_eq!(as_bytes(&boxed), b"Ada");
+    assert_eq!(pick(&boxed, &String::from("Bob")), "Ada");
+    boxed.name_mut().push('!');
+    assert_eq!(name(&boxed), "Ada!");
+
+    let person = Person(String::from("Grace"));
+    let by_ref = &person;
+    assert_eq!(name(&by_ref), "Grace");
+    assert_eq!(as_bytes(&by_ref), b"Grace");
+
+    let mut person = Person(String::from("Alan"));
+    let mut by_mut = &mut person;
+    NamedMut::name_mut(&mut by_mut).push('?');
+    assert_eq!(name(&by_mut), "Alan?");
+}
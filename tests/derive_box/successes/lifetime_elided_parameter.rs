@@ -0,0 +1,43 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Finder {
+    fn find(&self, needle: &str) -> Option<&str>;
+}
+
+struct Haystack(String);
+
+impl Finder for Haystack {
+    fn find(&self, needle: &str) -> Option<&str> {
+        self.0.find(needle).map(|i| &self.0[i..])
+    }
+}
+
+// the elided output lifetime is tied to the receiver, not to the needle
+fn find<'a, F: Finder>(finder: &'a F, needle: &str) -> Option<&'a str> {
+    finder.find(needle)
+}
+
+fn main() {
+    assert!(impls!(Box<Haystack>: Finder));
+    assert!(impls!(&Haystack:     Finder));
+
+    let boxed = Box::new(Haystack(String::from("needle in a haystack")));
+    let found = {
+        let needle = String::from("hay");
+        find(&boxed, &needle)
+    };
+    assert_eq!(found, Some("haystack"));
+
+    let haystack = Haystack(String::from("a needle"));
+    let by_ref = &haystack;
+    let found = {
+        let needle = String::from("needle");
+        find(&by_ref, &needle)
+    };
+    assert_eq!(found, Some("needle"));
+}
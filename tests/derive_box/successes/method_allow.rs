@@ -0,0 +1,31 @@
+#![deny(clippy::too_many_arguments)]
+
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Mut), no_clippy_allow)]
+pub trait Canvas {
+    #[allow(clippy::too_many_arguments)]
+    fn blit(&mut self, x: u32, y: u32, w: u32, h: u32, r: u8, g: u8, b: u8, a: u8);
+}
+
+#[derive(Default)]
+struct Pixels(u32);
+
+impl Canvas for Pixels {
+    fn blit(&mut self, _x: u32, _y: u32, w: u32, h: u32, _r: u8, _g: u8, _b: u8, _a: u8) {
+        self.0 += w * h;
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Pixels>: Canvas));
+    assert!(impls!(&mut Pixels: Canvas));
+
+    let mut boxed = Box::new(Pixels::default());
+    boxed.blit(0, 0, 2, 3, 0, 0, 0, 255);
+    assert_eq!(boxed.0, 6);
+}
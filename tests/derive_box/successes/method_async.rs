@@ -0,0 +1,23 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+trait Fetcher {
+    async fn get(&self, url: &str) -> Vec<u8>;
+}
+
+struct Echo;
+
+impl Fetcher for Echo {
+    async fn get(&self, url: &str) -> Vec<u8> {
+        url.as_bytes().to_vec()
+    }
+}
+
+fn main() {
+    assert!(impls!(Echo:      Fetcher));
+    assert!(impls!(Box<Echo>: Fetcher));
+}
@@ -0,0 +1,33 @@
+#![deny(deprecated)]
+
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Counter {
+    #[cfg_attr(all(), deprecated(note = "use `count` instead"))]
+    fn get(&self) -> u8;
+    #[cfg_attr(any(), deprecated(note = "not deprecated"))]
+    fn count(&self) -> u8;
+}
+
+struct Zero;
+
+impl Counter for Zero {
+    fn get(&self) -> u8 {
+        0
+    }
+    fn count(&self) -> u8 {
+        0
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Zero>: Counter));
+    assert!(impls!(&Zero:     Counter));
+
+    assert_eq!(Box::new(Zero).count(), 0);
+}
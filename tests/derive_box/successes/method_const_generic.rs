@@ -0,0 +1,30 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Filler {
+    fn fill<const N: usize>(&self) -> [u8; N];
+    fn size_of<T>(&self) -> usize;
+}
+
+struct Zeroes;
+
+impl Filler for Zeroes {
+    fn fill<const N: usize>(&self) -> [u8; N] {
+        [0; N]
+    }
+    fn size_of<T>(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Zeroes>: Filler));
+
+    let filler = Box::new(Zeroes);
+    assert_eq!(filler.fill::<4>(), [0; 4]);
+    assert_eq!(filler.size_of::<u32>(), 4);
+}
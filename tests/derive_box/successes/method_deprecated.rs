@@ -0,0 +1,26 @@
+#![deny(deprecated)]
+
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[deprecated]
+    fn count(&self) -> u8;
+}
+
+struct Zero;
+
+impl Counter for Zero {
+    fn count(&self) -> u8 {
+        0
+    }
+}
+
+fn main() {
+    assert!(impls!(Zero:      Counter));
+    assert!(impls!(Box<Zero>: Counter));
+}
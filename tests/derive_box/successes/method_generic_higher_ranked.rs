@@ -0,0 +1,47 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::RefCell;
+
+use blanket::blanket;
+use impls::impls;
+
+#[derive(Default)]
+pub struct State {
+    count: usize,
+    name: String,
+}
+
+#[blanket(derive(Box))]
+pub trait Store {
+    fn with<F: for<'a> FnMut(&'a mut State)>(&self, f: F);
+    fn name_with<F>(&self, f: F) -> usize
+    where
+        F: for<'a> Fn(&'a State) -> &'a str;
+}
+
+#[derive(Default)]
+struct Memory(RefCell<State>);
+
+impl Store for Memory {
+    fn with<F: for<'a> FnMut(&'a mut State)>(&self, mut f: F) {
+        f(&mut self.0.borrow_mut())
+    }
+    fn name_with<F>(&self, f: F) -> usize
+    where
+        F: for<'a> Fn(&'a State) -> &'a str,
+    {
+        f(&self.0.borrow()).len()
+    }
+}
+
+fn main() {
+    assert!(impls!(Memory:      Store));
+    assert!(impls!(Box<Memory>: Store));
+
+    let store = Box::new(Memory::default());
+    store.with(|state| state.count += 1);
+    store.with(|state| state.name.push_str("store"));
+    assert_eq!(store.name_with(|state| state.name.as_str()), 5);
+    store.with(|state| assert_eq!(state.count, 1));
+}
@@ -0,0 +1,33 @@
+extern crate blanket;
+extern crate impls;
+
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, Rc))]
+pub trait Bytes {
+    fn collect<C: FromIterator<u8>>(&self) -> C;
+}
+
+struct Message(&'static str);
+
+impl Bytes for Message {
+    fn collect<C: FromIterator<u8>>(&self) -> C {
+        self.0.bytes().collect()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Message>: Bytes));
+    assert!(impls!(&Message: Bytes));
+    assert!(impls!(std::rc::Rc<Message>: Bytes));
+
+    let boxed = Box::new(Message("abba"));
+    let bytes: Vec<u8> = boxed.collect();
+    assert_eq!(bytes, b"abba".to_vec());
+    let unique: BTreeSet<u8> = Bytes::collect(&&*boxed);
+    assert_eq!(unique.len(), 2);
+}
@@ -0,0 +1,32 @@
+extern crate blanket;
+extern crate impls;
+
+use std::panic::Location;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Checked {
+    #[track_caller]
+    fn location(&self) -> &'static Location<'static>;
+}
+
+struct Value;
+
+impl Checked for Value {
+    #[track_caller]
+    fn location(&self) -> &'static Location<'static> {
+        Location::caller()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Value>: Checked));
+    assert!(impls!(&Value:     Checked));
+
+    let location = Box::new(Value).location();
+    assert_eq!(location.line(), line!() - 1);
+    let location = Checked::location(&&Value);
+    assert_eq!(location.line(), line!() - 1);
+}
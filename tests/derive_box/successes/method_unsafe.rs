@@ -0,0 +1,27 @@
+#![deny(unused_unsafe)]
+
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Buffer {
+    unsafe fn raw(&self) -> *const u8;
+}
+
+struct Bytes {
+    data: Vec<u8>,
+}
+
+impl Buffer for Bytes {
+    unsafe fn raw(&self) -> *const u8 {
+        self.data.as_ptr()
+    }
+}
+
+fn main() {
+    assert!(impls!(Bytes:      Buffer));
+    assert!(impls!(Box<Bytes>: Buffer));
+}
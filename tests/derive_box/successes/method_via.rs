@@ -0,0 +1,38 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Rc))]
+pub trait Name {
+    #[blanket(via = "as_ref")]
+    fn name(&self) -> String;
+    fn len(&self) -> usize;
+}
+
+struct User(String);
+
+impl Name for User {
+    fn name(&self) -> String {
+        self.0.clone()
+    }
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<User>: Name));
+    assert!(impls!(Rc<User>:  Name));
+
+    let user = Box::new(User(String::from("alice")));
+    assert_eq!(Name::name(&user), "alice");
+    assert_eq!(Name::len(&user), 5);
+
+    let user = Rc::new(User(String::from("bob")));
+    assert_eq!(Name::name(&user), "bob");
+    assert_eq!(Name::len(&user), 3);
+}
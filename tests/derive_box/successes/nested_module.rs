@@ -0,0 +1,42 @@
+extern crate blanket;
+extern crate impls;
+
+use impls::impls;
+
+mod geometry {
+    pub mod units {
+        #[derive(Debug, PartialEq)]
+        pub struct Meters(pub u32);
+    }
+
+    pub mod shapes {
+        use blanket::blanket;
+
+        use super::units::Meters;
+
+        /// A local type shadowing `Box` in this module.
+        pub struct Box;
+
+        #[blanket(derive(Box))]
+        pub trait Shape {
+            fn perimeter(&self) -> Meters;
+        }
+
+        pub struct Square(pub u32);
+
+        impl Shape for Square {
+            fn perimeter(&self) -> Meters {
+                Meters(self.0 * 4)
+            }
+        }
+    }
+}
+
+use geometry::shapes::Shape;
+use geometry::shapes::Square;
+use geometry::units::Meters;
+
+fn main() {
+    assert!(impls!(Box<Square>: Shape));
+    assert_eq!(Box::new(Square(2)).perimeter(), Meters(8));
+}
@@ -0,0 +1,23 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), no_auto_derived)]
+pub trait Counter {
+    fn get(&self) -> u8;
+}
+
+struct Zero;
+
+impl Counter for Zero {
+    fn get(&self) -> u8 {
+        0
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Zero>: Counter));
+    assert_eq!(Box::new(Zero).get(), 0);
+}
@@ -0,0 +1,38 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), only(a, b))]
+pub trait Letters {
+    fn a(&self) -> char;
+    fn b(&self) -> char;
+    fn c(&self) -> char {
+        '?'
+    }
+}
+
+struct Lowercase;
+
+impl Letters for Lowercase {
+    fn a(&self) -> char {
+        'a'
+    }
+    fn b(&self) -> char {
+        'b'
+    }
+    fn c(&self) -> char {
+        'c'
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Lowercase>: Letters));
+
+    let boxed = Box::new(Lowercase);
+    assert_eq!(Letters::a(&boxed), 'a');
+    assert_eq!(Letters::b(&boxed), 'b');
+    assert_eq!(Letters::c(&boxed), '?');
+    assert_eq!(Letters::c(&Lowercase), 'c');
+}
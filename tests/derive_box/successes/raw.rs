@@ -0,0 +1,24 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref), raw)]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+struct User;
+
+impl Named for User {
+    fn name(&self) -> String {
+        String::from("user")
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<User>: Named));
+    assert!(impls!(&User: Named));
+    assert_eq!(Box::new(User).name(), "user");
+}
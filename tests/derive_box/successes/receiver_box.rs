@@ -0,0 +1,38 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Task {
+    fn name(&self) -> String;
+    fn run(self: Box<Self>) -> String;
+}
+
+struct Job(&'static str);
+
+impl Task for Job {
+    fn name(&self) -> String {
+        self.0.to_string()
+    }
+    fn run(self: Box<Self>) -> String {
+        format!("ran {}", self.0)
+    }
+}
+
+fn run_all(tasks: Vec<Box<dyn Task>>) -> Vec<String> {
+    tasks.into_iter().map(|task| task.run()).collect()
+}
+
+fn main() {
+    assert!(impls!(Job:      Task));
+    assert!(impls!(Box<Job>: Task));
+
+    let job: Box<Box<Job>> = Box::new(Box::new(Job("build")));
+    assert_eq!(job.name(), "build");
+    assert_eq!(job.run(), "ran build");
+
+    let tasks: Vec<Box<dyn Task>> = vec![Box::new(Job("a")), Box::new(Box::new(Job("b")))];
+    assert_eq!(run_all(tasks), ["ran a", "ran b"]);
+}
@@ -0,0 +1,36 @@
+extern crate blanket;
+extern crate impls;
+
+use std::pin::Pin;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Builder {
+    fn push(self: Pin<&mut Self>, value: u8) -> Pin<&mut Self>;
+    fn len(self: Pin<&Self>) -> usize;
+}
+
+#[derive(Default)]
+struct Bytes(Vec<u8>);
+
+impl Builder for Bytes {
+    fn push(mut self: Pin<&mut Self>, value: u8) -> Pin<&mut Self> {
+        self.0.push(value);
+        self
+    }
+    fn len(self: Pin<&Self>) -> usize {
+        self.0.len()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Bytes>: Builder));
+
+    let mut boxed = Box::new(Bytes::default());
+    let mut pinned = Pin::new(&mut boxed);
+    pinned.as_mut().push(1).push(2).push(3);
+    assert_eq!(pinned.as_ref().len(), 3);
+    assert_eq!(boxed.0, [1, 2, 3]);
+}
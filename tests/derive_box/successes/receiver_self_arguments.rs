@@ -0,0 +1,48 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Split {
+    fn into_parts(self, separator: char, limit: usize) -> (String, Vec<String>);
+    fn into_len(mut self, extra: &str) -> usize
+    where
+        Self: Sized,
+    {
+        self.push(extra);
+        self.into_parts(' ', usize::MAX).1.len()
+    }
+    fn push(&mut self, s: &str);
+}
+
+struct Line(String);
+
+impl Split for Line {
+    fn into_parts(self, separator: char, limit: usize) -> (String, Vec<String>) {
+        let parts = self
+            .0
+            .splitn(limit, separator)
+            .map(String::from)
+            .collect();
+        (self.0, parts)
+    }
+    fn push(&mut self, s: &str) {
+        self.0.push(' ');
+        self.0.push_str(s);
+    }
+}
+
+fn main() {
+    assert!(impls!(Line:      Split));
+    assert!(impls!(Box<Line>: Split));
+
+    let line = Box::new(Line(String::from("a b c")));
+    let (whole, parts) = line.into_parts(' ', 2);
+    assert_eq!(whole, "a b c");
+    assert_eq!(parts, ["a", "b c"]);
+
+    let line = Box::new(Line(String::from("a b")));
+    assert_eq!(line.into_len("c"), 3);
+}
@@ -0,0 +1,35 @@
+extern crate blanket;
+extern crate impls;
+
+use std::fmt::Display;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Builder {
+    fn build(self) -> impl Display;
+    fn build_all(self, count: usize) -> impl Iterator<Item = String>;
+}
+
+struct Greeting(String);
+
+impl Builder for Greeting {
+    fn build(self) -> impl Display {
+        format!("hello, {}", self.0)
+    }
+    fn build_all(self, count: usize) -> impl Iterator<Item = String> {
+        std::iter::repeat(self.0).take(count)
+    }
+}
+
+fn main() {
+    assert!(impls!(Greeting:      Builder));
+    assert!(impls!(Box<Greeting>: Builder));
+
+    let builder = Box::new(Greeting(String::from("world")));
+    assert_eq!(builder.build().to_string(), "hello, world");
+
+    let builder = Box::new(Greeting(String::from("world")));
+    assert_eq!(builder.build_all(2).collect::<Vec<_>>(), ["world", "world"]);
+}
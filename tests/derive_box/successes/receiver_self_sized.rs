@@ -0,0 +1,37 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Hasher {
+    fn write(&mut self, bytes: &[u8]);
+    fn finish(self) -> u64
+    where
+        Self: Sized;
+}
+
+#[derive(Default)]
+struct Sum(u64);
+
+impl Hasher for Sum {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 += bytes.iter().map(|&b| u64::from(b)).sum::<u64>();
+    }
+    fn finish(self) -> u64 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Sum:      Hasher));
+    assert!(impls!(Box<Sum>: Hasher));
+
+    let mut hasher = Box::new(Sum::default());
+    hasher.write(&[1, 2, 3]);
+    assert_eq!(hasher.finish(), 6);
+
+    let mut object: Box<dyn Hasher> = Box::new(Sum::default());
+    object.write(&[4, 5]);
+}
@@ -0,0 +1,36 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    fn get(self: &Self) -> u8;
+    fn increment(self: &mut Self);
+    fn into_count(self: Self) -> u8;
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> u8 {
+        self.0
+    }
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+    fn into_count(self) -> u8 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<SimpleCounter>: Counter));
+
+    let mut counter = Box::new(SimpleCounter::default());
+    counter.increment();
+    assert_eq!(counter.get(), 1);
+    assert_eq!(counter.into_count(), 1);
+}
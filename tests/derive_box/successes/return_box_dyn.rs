@@ -0,0 +1,41 @@
+extern crate blanket;
+extern crate impls;
+
+use impls::impls;
+
+mod shapes {
+    use std::fmt::Display;
+
+    use blanket::blanket;
+
+    /// A local type shadowing `Box` in this module.
+    pub struct Box<T: ?Sized>(pub std::boxed::Box<T>);
+
+    #[blanket(derive(Box))]
+    pub trait Shape {
+        fn name(&self) -> Box<dyn Display>;
+        fn label(&self) -> std::boxed::Box<dyn Display>;
+    }
+
+    pub struct Square;
+
+    impl Shape for Square {
+        fn name(&self) -> Box<dyn Display> {
+            Box(std::boxed::Box::new("square"))
+        }
+        fn label(&self) -> std::boxed::Box<dyn Display> {
+            std::boxed::Box::new(4)
+        }
+    }
+}
+
+use shapes::Shape;
+use shapes::Square;
+
+fn main() {
+    assert!(impls!(Box<Square>: Shape));
+
+    let boxed = Box::new(Box::new(Square));
+    assert_eq!(boxed.name().0.to_string(), "square");
+    assert_eq!(boxed.label().to_string(), "4");
+}
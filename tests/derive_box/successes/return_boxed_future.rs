@@ -0,0 +1,38 @@
+extern crate blanket;
+extern crate impls;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Fetcher {
+    fn get<'a>(&'a self, url: &'a str) -> Pin<Box<dyn Future<Output = Vec<u8>> + 'a>>;
+}
+
+struct Echo;
+
+impl Fetcher for Echo {
+    fn get<'a>(&'a self, url: &'a str) -> Pin<Box<dyn Future<Output = Vec<u8>> + 'a>> {
+        Box::pin(async move { url.as_bytes().to_vec() })
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Echo>: Fetcher));
+    assert!(impls!(&Echo:     Fetcher));
+
+    // the boxed future is returned as it is, without being awaited
+    let echo = Box::new(Echo);
+    let mut future = Fetcher::get(&echo, "url");
+    let mut context = Context::from_waker(Waker::noop());
+    match future.as_mut().poll(&mut context) {
+        Poll::Ready(bytes) => assert_eq!(bytes, b"url"),
+        Poll::Pending => unreachable!(),
+    }
+}
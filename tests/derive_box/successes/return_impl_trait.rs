@@ -0,0 +1,37 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Items<'a, T: Copy + 'a> {
+    fn items(&self) -> impl Iterator<Item = u32>;
+    fn borrowed(&self, values: &'a [T]) -> impl Iterator<Item = &'a T>;
+    fn captured(&self) -> impl Iterator<Item = u32> + '_;
+}
+
+struct Numbers(Vec<u32>);
+
+impl<'a, T: Copy + 'a> Items<'a, T> for Numbers {
+    fn items(&self) -> impl Iterator<Item = u32> {
+        self.0.clone().into_iter()
+    }
+    fn borrowed(&self, values: &'a [T]) -> impl Iterator<Item = &'a T> {
+        values.iter()
+    }
+    fn captured(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+fn sum<'a, I: Items<'a, u8>>(items: &I) -> u32 {
+    items.items().sum::<u32>() + items.captured().sum::<u32>()
+}
+
+fn main() {
+    assert!(impls!(Numbers: Items<'static, u8>));
+    let numbers = Numbers(vec![1, 2, 3]);
+    assert!(impls!(Box<Numbers>: Items<'static, u8>));
+    assert_eq!(sum(&Box::new(numbers)), 12);
+}
@@ -0,0 +1,49 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Source {
+    fn stream(&self) -> impl Iterator<Item = &u8> + '_;
+    fn windows<'s>(&'s self, size: usize) -> impl Iterator<Item = &'s [u8]> + 's;
+    fn skip(&self, count: &usize) -> impl Iterator<Item = u8> + '_;
+}
+
+struct Bytes(Vec<u8>);
+
+impl Source for Bytes {
+    fn stream(&self) -> impl Iterator<Item = &u8> + '_ {
+        self.0.iter()
+    }
+    fn windows<'s>(&'s self, size: usize) -> impl Iterator<Item = &'s [u8]> + 's {
+        self.0.windows(size)
+    }
+    fn skip(&self, count: &usize) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().copied().skip(*count)
+    }
+}
+
+// the opaque types capture the lifetime of the receiver only, so the
+// iterators outlive the other arguments
+fn last<S: Source>(source: &S) -> Option<u8> {
+    let skipped = {
+        let count = 1;
+        source.skip(&count)
+    };
+    let windows = source.windows(2).count() as u8;
+    skipped.chain(source.stream().copied()).last().map(|last| last + windows)
+}
+
+fn main() {
+    assert!(impls!(Box<Bytes>: Source));
+    assert!(impls!(&Bytes:     Source));
+
+    let boxed = Box::new(Bytes(vec![1, 2, 3]));
+    assert_eq!(last(&boxed), Some(5));
+
+    let bytes = Bytes(vec![4, 5]);
+    let by_ref = &bytes;
+    assert_eq!(last(&by_ref), Some(6));
+}
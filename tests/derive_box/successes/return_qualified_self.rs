@@ -0,0 +1,35 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Collection {
+    type Item;
+    type Iter: Iterator<Item = <Self as Collection>::Item>;
+    fn first(&self) -> Option<<Self as Collection>::Item>;
+    fn iter(&self) -> <Self as Collection>::Iter;
+}
+
+struct Numbers(Vec<u32>);
+
+impl Collection for Numbers {
+    type Item = u32;
+    type Iter = std::vec::IntoIter<u32>;
+    fn first(&self) -> Option<u32> {
+        self.0.first().copied()
+    }
+    fn iter(&self) -> <Self as Collection>::Iter {
+        self.0.clone().into_iter()
+    }
+}
+
+fn main() {
+    assert!(impls!(Numbers:      Collection));
+    assert!(impls!(Box<Numbers>: Collection));
+
+    let numbers = Box::new(Numbers(vec![1, 2, 3]));
+    assert_eq!(Collection::first(&numbers), Some(1));
+    assert_eq!(Collection::iter(&numbers).sum::<u32>(), 6);
+}
@@ -0,0 +1,62 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+pub trait Logger {
+    fn log(&self, message: &str) -> String;
+}
+
+#[blanket(derive(Box, Ref, Rc))]
+pub trait Service {
+    fn as_logger(&self) -> &dyn Logger;
+}
+
+#[blanket(derive(Box, Mut))]
+pub trait ServiceMut {
+    fn logger_mut(&mut self) -> &mut (dyn Logger + 'static);
+}
+
+struct Prefix(&'static str);
+
+impl Logger for Prefix {
+    fn log(&self, message: &str) -> String {
+        format!("{}: {}", self.0, message)
+    }
+}
+
+struct Server {
+    logger: Prefix,
+}
+
+impl Service for Server {
+    fn as_logger(&self) -> &dyn Logger {
+        &self.logger
+    }
+}
+
+impl ServiceMut for Server {
+    fn logger_mut(&mut self) -> &mut (dyn Logger + 'static) {
+        &mut self.logger
+    }
+}
+
+fn log<S: Service>(service: &S, message: &str) -> String {
+    service.as_logger().log(message)
+}
+
+fn main() {
+    assert!(impls!(Box<Server>:      Service));
+    assert!(impls!(&Server:          Service));
+    assert!(impls!(Rc<Server>:       Service));
+    assert!(impls!(Box<Server>:      ServiceMut));
+    assert!(impls!(Box<&mut Server>: ServiceMut));
+
+    let mut server = Box::new(Server { logger: Prefix("server") });
+    assert_eq!(log(&server, "started"), "server: started");
+    assert_eq!(server.logger_mut().log("running"), "server: running");
+    assert_eq!(log(&&*server, "stopped"), "server: stopped");
+}
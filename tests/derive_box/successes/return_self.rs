@@ -0,0 +1,30 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Builder {
+    fn with_flag(self) -> Self;
+    fn flags(&self) -> u8;
+}
+
+#[derive(Default)]
+struct Options(u8);
+
+impl Builder for Options {
+    fn with_flag(self) -> Self {
+        Options(self.0 + 1)
+    }
+    fn flags(&self) -> u8 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Options>: Builder));
+
+    let options = Box::new(Options::default()).with_flag().with_flag();
+    assert_eq!(options.flags(), 2);
+}
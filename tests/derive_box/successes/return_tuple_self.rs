@@ -0,0 +1,38 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Interval: Sized {
+    fn split(self) -> (Self, Self);
+    fn halve(&self) -> Option<(Self, u32)>;
+}
+
+#[derive(Debug, PartialEq)]
+struct Range(u32, u32);
+
+impl Interval for Range {
+    fn split(self) -> (Self, Self) {
+        let middle = (self.0 + self.1) / 2;
+        (Range(self.0, middle), Range(middle, self.1))
+    }
+    fn halve(&self) -> Option<(Self, u32)> {
+        let length = self.1 - self.0;
+        if length > 1 {
+            Some((Range(self.0, self.0 + length / 2), length / 2))
+        } else {
+            None
+        }
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Range>: Interval));
+
+    let (left, right) = Box::new(Range(0, 10)).split();
+    assert_eq!(left, Box::new(Range(0, 5)));
+    assert_eq!(right, Box::new(Range(5, 10)));
+    assert_eq!(Box::new(Range(0, 4)).halve(), Some((Box::new(Range(0, 2)), 2)));
+}
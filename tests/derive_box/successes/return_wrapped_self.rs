@@ -0,0 +1,43 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+
+#[blanket(derive(Box))]
+pub trait Builder {
+    fn with_flag(self) -> Result<Self, Overflow>
+    where
+        Self: Sized;
+    fn next(&self) -> Option<Self>
+    where
+        Self: Sized;
+    fn flags(&self) -> u8;
+}
+
+#[derive(Debug, Default)]
+struct Options(u8);
+
+impl Builder for Options {
+    fn with_flag(self) -> Result<Self, Overflow> {
+        self.0.checked_add(1).map(Options).ok_or(Overflow)
+    }
+    fn next(&self) -> Option<Self> {
+        self.0.checked_add(1).map(Options)
+    }
+    fn flags(&self) -> u8 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Options>: Builder));
+
+    let options = Box::new(Options::default()).with_flag().unwrap();
+    assert_eq!(options.with_flag().unwrap().flags(), 2);
+    assert_eq!(Box::new(Options(255)).with_flag().unwrap_err(), Overflow);
+    assert_eq!(Box::new(Options(1)).next().unwrap().flags(), 2);
+}
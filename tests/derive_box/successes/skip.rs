@@ -0,0 +1,43 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Register {
+    const WIDTH: u8;
+    #[blanket(skip)]
+    const NAME: &'static str = "register";
+    fn read(&self) -> u8;
+    #[blanket(skip)]
+    fn describe(&self) -> String {
+        format!("{} ({} bits)", Self::NAME, Self::WIDTH)
+    }
+}
+
+struct Status;
+
+impl Register for Status {
+    const WIDTH: u8 = 8;
+    const NAME: &'static str = "status";
+    fn read(&self) -> u8 {
+        1
+    }
+    fn describe(&self) -> String {
+        String::from("the status register")
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Status>: Register));
+    assert!(impls!(&Status: Register));
+
+    assert_eq!(<Box<Status> as Register>::WIDTH, 8);
+    assert_eq!(<Box<Status> as Register>::NAME, "register");
+    assert_eq!(<&Status as Register>::NAME, "register");
+
+    let boxed = Box::new(Status);
+    assert_eq!(boxed.read(), 1);
+    assert_eq!(Register::describe(&boxed), "register (8 bits)");
+}
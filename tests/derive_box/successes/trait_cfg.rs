@@ -0,0 +1,33 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+#[cfg(any())]
+pub trait Hidden {
+    fn hidden(&self);
+}
+
+#[blanket(derive(Box))]
+#[cfg(all())]
+pub trait Visible {
+    fn visible(&self);
+}
+
+#[blanket(derive(Box))]
+#[cfg_attr(all(), cfg(any()), must_use)]
+pub trait Gated {
+    fn gated(&self);
+}
+
+struct Value;
+
+impl Visible for Value {
+    fn visible(&self) {}
+}
+
+fn main() {
+    assert!(impls!(Box<Value>: Visible));
+}
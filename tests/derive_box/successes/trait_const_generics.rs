@@ -0,0 +1,30 @@
+extern crate blanket;
+extern crate impls;
+
+use std::convert::TryInto;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Chunks<'a, T: 'a, const N: usize> {
+    fn chunk(&self, index: usize) -> Option<&'a [T; N]>;
+}
+
+struct Slice<'a>(&'a [u8]);
+
+impl<'a> Chunks<'a, u8, 2> for Slice<'a> {
+    fn chunk(&self, index: usize) -> Option<&'a [u8; 2]> {
+        self.0.chunks_exact(2).nth(index).map(|c| c.try_into().unwrap())
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Slice<'static>>: Chunks<'static, u8, 2>));
+    assert!(impls!(Box<Slice<'static>>: !Chunks<'static, u8, 3>));
+
+    let data = [1, 2, 3, 4];
+    let slice = Box::new(Slice(&data));
+    assert_eq!(slice.chunk(1), Some(&[3, 4]));
+    assert_eq!((&slice).chunk(2), None);
+}
@@ -0,0 +1,41 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, Rc))]
+pub trait Handler<M> {
+    fn handle(&self, msg: M);
+}
+
+#[derive(Default)]
+struct Log {
+    messages: RefCell<Vec<String>>,
+}
+
+impl<M: ToString> Handler<M> for Log {
+    fn handle(&self, msg: M) {
+        self.messages.borrow_mut().push(msg.to_string());
+    }
+}
+
+fn dispatch<M, H: Handler<M>>(handler: H, msg: M) {
+    handler.handle(msg)
+}
+
+fn main() {
+    assert!(impls!(Box<Log>: Handler<String>));
+    assert!(impls!(&Log:     Handler<u8>));
+    assert!(impls!(Rc<Log>:  Handler<&'static str>));
+    assert!(!impls!(Box<Log>: Handler<Vec<u8>>));
+
+    let log = Rc::new(Log::default());
+    dispatch(log.clone(), 1u8);
+    dispatch(&*log, "two");
+    dispatch(Box::new(Log::default()), 3.0);
+    assert_eq!(*log.messages.borrow(), ["1", "two"]);
+}
@@ -0,0 +1,31 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Buf<T: Copy = u8, const N: usize = 64> {
+    fn fill(&self, value: T) -> [T; N] {
+        [value; N]
+    }
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+struct Stack;
+
+impl Buf for Stack {}
+impl Buf<u16, 2> for Stack {}
+
+fn main() {
+    assert!(impls!(Box<Stack>: Buf));
+    assert!(impls!(Box<Stack>: Buf<u16, 2>));
+    assert!(impls!(&Stack:     Buf<u8, 64>));
+    assert!(!impls!(&Stack:    Buf<u8, 2>));
+
+    let stack = Box::new(Stack);
+    assert_eq!(<Box<Stack> as Buf>::capacity(&stack), 64);
+    assert_eq!(Buf::<u16, 2>::fill(&stack, 7), [7, 7]);
+}
@@ -0,0 +1,32 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Pair<A, B: Into<A>> {
+    fn merge(&self, b: B) -> A;
+    fn convert<C: Into<B>>(&self, c: C) -> A;
+}
+
+struct Adder(u64);
+
+impl Pair<u64, u32> for Adder {
+    fn merge(&self, b: u32) -> u64 {
+        self.0 + u64::from(b)
+    }
+    fn convert<C: Into<u32>>(&self, c: C) -> u64 {
+        self.merge(c.into())
+    }
+}
+
+fn main() {
+    assert!(impls!(Adder:      Pair<u64, u32>));
+    assert!(impls!(Box<Adder>: Pair<u64, u32>));
+    assert!(impls!(&Adder:     Pair<u64, u32>));
+
+    let adder = Box::new(Adder(1));
+    assert_eq!(adder.merge(2), 3);
+    assert_eq!(adder.convert(3u8), 4);
+}
@@ -0,0 +1,36 @@
+extern crate blanket;
+extern crate impls;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Store<K: Hash + Eq, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn keys(&self) -> Vec<&K>;
+}
+
+struct Memory<K, V>(HashMap<K, V>);
+
+impl<K: Hash + Eq, V> Store<K, V> for Memory<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        self.0.get(key)
+    }
+    fn keys(&self) -> Vec<&K> {
+        self.0.keys().collect()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Memory<String, u32>>: Store<String, u32>));
+    assert!(impls!(&Memory<String, u32>: Store<String, u32>));
+
+    let mut map = HashMap::new();
+    map.insert(String::from("a"), 1);
+    let boxed = Box::new(Memory(map));
+    assert_eq!(boxed.get(&String::from("a")), Some(&1));
+    assert_eq!(Store::keys(&&*boxed), vec![&String::from("a")]);
+}
@@ -0,0 +1,34 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, Rc))]
+pub trait Holder<T: ?Sized> {
+    fn get(&self) -> &T;
+}
+
+struct Label(String);
+
+impl Holder<str> for Label {
+    fn get(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Holder<[u8]> for Label {
+    fn get(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Label>: Holder<str> & Holder<[u8]>));
+    assert!(impls!(&Label: Holder<str>));
+    assert!(impls!(std::rc::Rc<Label>: Holder<[u8]>));
+
+    let boxed = Box::new(Label(String::from("label")));
+    assert_eq!(Holder::<str>::get(&boxed), "label");
+    assert_eq!(Holder::<[u8]>::get(&boxed), b"label");
+}
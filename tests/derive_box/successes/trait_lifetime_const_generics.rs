@@ -0,0 +1,45 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Frame<'a, const N: usize> {
+    fn data(&self) -> &'a [u8; N];
+    fn get<T: From<u8>>(&self, index: usize) -> T;
+}
+
+struct Buffer<'a>(&'a [u8; 4]);
+
+impl<'a> Frame<'a, 4> for Buffer<'a> {
+    fn data(&self) -> &'a [u8; 4] {
+        self.0
+    }
+    fn get<T: From<u8>>(&self, index: usize) -> T {
+        T::from(self.0[index])
+    }
+}
+
+fn checksum<'a, F: Frame<'a, 4>>(frame: F) -> u32 {
+    frame.data().iter().map(|&x| u32::from(x)).sum()
+}
+
+static BYTES: [u8; 4] = [1, 2, 3, 4];
+
+fn main() {
+    assert!(impls!(Buffer<'static>:      Frame<'static, 4>));
+    assert!(impls!(Box<Buffer<'static>>: Frame<'static, 4>));
+    assert!(impls!(&Buffer<'static>:     Frame<'static, 4>));
+    assert!(impls!(Box<Buffer<'static>>: !Frame<'static, 8>));
+
+    let buffer = Buffer(&BYTES);
+    assert_eq!(checksum(&buffer), 10);
+    let data = {
+        let boxed = Box::new(Buffer(&BYTES));
+        assert_eq!(boxed.get::<u64>(3), 4);
+        boxed.data()
+    };
+    assert_eq!(data, &BYTES);
+    assert_eq!(checksum(Box::new(buffer)), 10);
+}
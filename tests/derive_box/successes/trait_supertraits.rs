@@ -0,0 +1,48 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Rc))]
+pub trait Handler {
+    fn handle(&self, request: u8) -> u8;
+}
+
+#[blanket(derive(Box, Rc))]
+pub trait Service: Handler + Clone {}
+
+#[derive(Clone)]
+struct Doubler;
+
+impl Handler for Doubler {
+    fn handle(&self, request: u8) -> u8 {
+        request * 2
+    }
+}
+
+impl Service for Doubler {}
+
+#[derive(Clone)]
+struct Unregistered;
+
+impl Handler for Unregistered {
+    fn handle(&self, request: u8) -> u8 {
+        request
+    }
+}
+
+fn serve<S: Service>(service: S, request: u8) -> u8 {
+    service.clone().handle(request)
+}
+
+fn main() {
+    assert!(impls!(Box<Doubler>:      Service));
+    assert!(impls!(Rc<Doubler>:       Service));
+    assert!(!impls!(Box<Unregistered>: Service));
+
+    assert_eq!(serve(Box::new(Doubler), 2), 4);
+    assert_eq!(serve(Rc::new(Doubler), 3), 6);
+}
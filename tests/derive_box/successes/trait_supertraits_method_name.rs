@@ -0,0 +1,61 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref, RcRefCell))]
+pub trait Base {
+    fn name(&self) -> String;
+}
+
+#[blanket(derive(Box, RcRefCell, BoxDyn))]
+pub trait Named: Base {
+    fn name(&self) -> String;
+    fn rename(&mut self, name: &str);
+}
+
+struct User(String);
+
+impl Base for User {
+    fn name(&self) -> String {
+        String::from("user")
+    }
+}
+
+impl Named for User {
+    fn name(&self) -> String {
+        self.0.clone()
+    }
+    fn rename(&mut self, name: &str) {
+        self.0 = name.to_string();
+    }
+}
+
+impl<'a> Base for Box<dyn Named + 'a> {
+    fn name(&self) -> String {
+        Base::name(&**self)
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<User>:             Named));
+    assert!(impls!(Rc<RefCell<User>>:     Named));
+    assert!(impls!(Box<dyn Named>:        Named));
+
+    // the forwarding methods call the method of the derived trait, and not
+    // the one of the supertrait with the same name
+    let mut user = Box::new(User(String::from("alice")));
+    Named::rename(&mut user, "bob");
+    assert_eq!(Named::name(&user), "bob");
+    assert_eq!(Base::name(&user), "user");
+
+    let shared = Rc::new(RefCell::new(User(String::from("carol"))));
+    assert_eq!(Named::name(&shared), "carol");
+
+    let object: Box<dyn Named> = Box::new(User(String::from("dave")));
+    assert_eq!(Named::name(&object), "dave");
+}
@@ -0,0 +1,33 @@
+extern crate blanket;
+extern crate impls;
+
+use std::any::Any;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), unsized)]
+pub trait Event: 'static {
+    fn id(&self) -> u32;
+}
+
+struct Click;
+
+impl Event for Click {
+    fn id(&self) -> u32 {
+        1
+    }
+}
+
+fn into_any<E: Event>(event: E) -> Box<dyn Any> {
+    Box::new(event)
+}
+
+fn main() {
+    assert!(impls!(Box<Click>: Event));
+    assert!(impls!(Box<dyn Event>: Event));
+
+    let boxed: Box<dyn Event> = Box::new(Click);
+    assert_eq!(boxed.id(), 1);
+    assert!(into_any(boxed).downcast_ref::<Box<dyn Event>>().is_some());
+}
@@ -0,0 +1,34 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+/// # Safety
+///
+/// An all-zero byte pattern must be a valid value of the implementor.
+#[blanket(derive(Box))]
+pub unsafe trait Zeroable {
+    fn zero(&mut self);
+    fn is_zero(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Default)]
+struct Counter(u32);
+
+unsafe impl Zeroable for Counter {
+    fn zero(&mut self) {
+        self.0 = 0;
+    }
+}
+
+fn main() {
+    assert!(impls!(Counter:      Zeroable));
+    assert!(impls!(Box<Counter>: Zeroable));
+
+    let mut counter = Box::new(Counter(3));
+    Zeroable::zero(&mut counter);
+    assert_eq!(counter.0, 0);
+}
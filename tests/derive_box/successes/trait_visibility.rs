@@ -0,0 +1,55 @@
+extern crate blanket;
+extern crate impls;
+
+use impls::impls;
+
+mod store {
+    use blanket::blanket;
+
+    // local types shadowing the smart pointers of the prelude
+    #[allow(dead_code)]
+    pub(crate) struct Box;
+    #[allow(dead_code)]
+    pub(crate) struct Rc;
+
+    pub(crate) struct Key(pub(crate) u8);
+
+    fn hash(key: &Key) -> u64 {
+        key.0 as u64 * 31
+    }
+
+    #[blanket(derive(Box, Rc, Ref))]
+    pub(crate) trait Store {
+        fn get(&self, key: &Key) -> Option<u8>;
+        fn hash(&self, key: &Key) -> u64 {
+            hash(key)
+        }
+    }
+
+    pub(crate) struct Single(pub(crate) u8);
+
+    impl Store for Single {
+        fn get(&self, key: &Key) -> Option<u8> {
+            if key.0 == self.0 {
+                Some(self.0)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+use store::Key;
+use store::Single;
+use store::Store;
+
+fn main() {
+    assert!(impls!(std::boxed::Box<Single>: Store));
+    assert!(impls!(std::rc::Rc<Single>:     Store));
+    assert!(impls!(&Single:                 Store));
+
+    let store = std::boxed::Box::new(Single(1));
+    assert_eq!(store.get(&Key(1)), Some(1));
+    assert_eq!(store.get(&Key(2)), None);
+    assert_eq!(store.hash(&Key(2)), 62);
+}
@@ -0,0 +1,52 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Mut), for_field(Shared = "0"), ufcs)]
+pub trait Counter {
+    fn get(&self) -> u32;
+    fn add(&mut self, n: u32);
+}
+
+#[derive(Default)]
+struct AtomicCounter(u32);
+
+// inherent methods shadowing the trait methods in method call syntax
+impl AtomicCounter {
+    fn get(&self) -> u32 {
+        u32::MAX
+    }
+    fn add(&mut self, _n: u32) {}
+}
+
+impl Counter for AtomicCounter {
+    fn get(&self) -> u32 {
+        self.0
+    }
+    fn add(&mut self, n: u32) {
+        self.0 += n;
+    }
+}
+
+struct Shared(AtomicCounter);
+
+fn main() {
+    assert!(impls!(Box<AtomicCounter>: Counter));
+    assert!(impls!(&mut AtomicCounter: Counter));
+    assert!(impls!(Shared: Counter));
+
+    let mut counter = AtomicCounter::default();
+    counter.add(1);
+    assert_eq!(counter.get(), u32::MAX);
+
+    let mut boxed = Box::new(AtomicCounter::default());
+    Counter::add(&mut boxed, 2);
+    assert_eq!(Counter::get(&boxed), 2);
+    assert_eq!(Counter::get(&&mut *boxed), 2);
+
+    let mut shared = Shared(AtomicCounter::default());
+    shared.add(3);
+    assert_eq!(shared.get(), 3);
+}
@@ -0,0 +1,46 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box), unsized)]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+}
+
+#[blanket(derive(Box))]
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+struct Square(f64);
+
+impl Named for Square {
+    fn name(&self) -> &str {
+        "square"
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Square>:    Shape));
+    assert!(impls!(Box<dyn Shape>: Shape));
+    assert!(impls!(Box<Box<dyn Shape>>: Shape));
+
+    assert!(impls!(Box<Square>:    Named));
+    assert!(!impls!(Box<dyn Named>: Named));
+
+    let mut shape: Box<dyn Shape> = Box::new(Square(2.0));
+    shape.scale(2.0);
+    assert_eq!(Shape::area(&shape), 16.0);
+}
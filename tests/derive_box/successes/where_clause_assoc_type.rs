@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Stream
+where
+    Self::Item: Default,
+{
+    type Item;
+    fn next(&mut self) -> Option<Self::Item>
+    where
+        Self::Item: Clone;
+}
+
+#[derive(Default)]
+struct Repeat {
+    count: Cell<u8>,
+}
+
+impl Stream for Repeat {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        self.count.set(self.count.get() + 1);
+        Some(self.count.get())
+    }
+}
+
+fn main() {
+    assert!(impls!(Repeat: Stream));
+    assert!(impls!(Box<Repeat>: Stream));
+
+    let mut stream = Box::new(Repeat::default());
+    assert_eq!(stream.next(), Some(1));
+    assert_eq!(Stream::next(&mut stream), Some(2));
+}
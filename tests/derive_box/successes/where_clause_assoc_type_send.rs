@@ -0,0 +1,44 @@
+extern crate blanket;
+extern crate impls;
+
+use std::thread;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Mut))]
+pub trait Producer
+where
+    Self::Item: Send,
+{
+    type Item;
+    fn produce(&mut self) -> Self::Item;
+}
+
+struct Numbers(u32);
+
+impl Producer for Numbers {
+    type Item = u32;
+    fn produce(&mut self) -> u32 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+fn spawn<P: Producer>(producer: &mut P) -> thread::JoinHandle<P::Item>
+where
+    P::Item: 'static,
+{
+    let item = producer.produce();
+    thread::spawn(move || item)
+}
+
+fn main() {
+    assert!(impls!(Numbers:          Producer));
+    assert!(impls!(Box<Numbers>:     Producer));
+    assert!(impls!(&mut Numbers:     Producer));
+
+    let mut producer = Box::new(Numbers(0));
+    assert_eq!(spawn(&mut producer).join().unwrap(), 1);
+    assert_eq!(spawn(&mut &mut *producer).join().unwrap(), 2);
+}
@@ -0,0 +1,49 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Source {
+    type Item;
+    fn source(&self) -> Self::Item;
+}
+
+#[blanket(derive(Box))]
+pub trait Stream: Source
+where
+    <Self as Stream>::Item: Default,
+{
+    type Item;
+    fn next(&mut self) -> Option<<Self as Stream>::Item>;
+}
+
+#[derive(Default)]
+struct Repeat {
+    count: u8,
+}
+
+impl Source for Repeat {
+    type Item = &'static str;
+    fn source(&self) -> &'static str {
+        "repeat"
+    }
+}
+
+impl Stream for Repeat {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        self.count += 1;
+        Some(self.count)
+    }
+}
+
+fn main() {
+    assert!(impls!(Repeat: Stream));
+    assert!(impls!(Box<Repeat>: Stream));
+
+    let mut stream = Box::new(Repeat::default());
+    assert_eq!(stream.source(), "repeat");
+    assert_eq!(stream.next(), Some(1));
+}
@@ -0,0 +1,32 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, Ref))]
+pub trait Proc<'a, T>
+where
+    T: Send,
+    T: 'static,
+    Self: Sized + 'a,
+{
+    fn run(&self, input: T) -> usize;
+}
+
+struct Len;
+
+impl<'a> Proc<'a, String> for Len {
+    fn run(&self, input: String) -> usize {
+        input.len()
+    }
+}
+
+fn main() {
+    assert!(impls!(Len:      Proc<'static, String>));
+    assert!(impls!(Box<Len>: Proc<'static, String>));
+    assert!(impls!(&Len:     Proc<'static, String>));
+
+    let proc_ = Box::new(Len);
+    assert_eq!(proc_.run(String::from("abc")), 3);
+}
@@ -0,0 +1,37 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box))]
+pub trait Visitor<F>
+where
+    F: for<'a> Fn(&'a str) -> &'a str,
+{
+    fn visit(&self, f: &F) -> String;
+}
+
+struct Greeting;
+
+impl<F> Visitor<F> for Greeting
+where
+    F: for<'a> Fn(&'a str) -> &'a str,
+{
+    fn visit(&self, f: &F) -> String {
+        f("Hello, world!").to_string()
+    }
+}
+
+fn trim(s: &str) -> &str {
+    s.trim()
+}
+
+fn main() {
+    type Trim = for<'a> fn(&'a str) -> &'a str;
+    assert!(impls!(Greeting:      Visitor<Trim>));
+    assert!(impls!(Box<Greeting>: Visitor<Trim>));
+
+    let boxed = Box::new(Greeting);
+    assert_eq!(boxed.visit(&(trim as Trim)), "Hello, world!");
+}
@@ -0,0 +1,43 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Accumulator<T>
+where
+    Self: Sized,
+    T: Into<Self>,
+{
+    fn total(&self) -> u64;
+}
+
+struct Sum(u64);
+
+impl From<u8> for Sum {
+    fn from(n: u8) -> Self {
+        Sum(n as u64)
+    }
+}
+
+impl From<u8> for Box<Sum> {
+    fn from(n: u8) -> Self {
+        Box::new(Sum::from(n))
+    }
+}
+
+impl Accumulator<u8> for Sum {
+    fn total(&self) -> u64 {
+        self.0
+    }
+}
+
+fn assert_accumulator<A: Accumulator<u8>>()
+where
+    u8: Into<A>,
+{
+}
+
+fn main() {
+    assert_accumulator::<Sum>();
+    assert_accumulator::<Box<Sum>>();
+}
@@ -0,0 +1,44 @@
+extern crate blanket;
+extern crate impls;
+
+use std::ops::Deref;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+pub struct Shared<T>(Arc<T>);
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[blanket(derive(Box), wrapper = "crate::Shared")]
+pub trait Counter {
+    fn increment(&self);
+}
+
+#[derive(Default)]
+struct AtomicCounter {
+    count: AtomicU8,
+}
+
+impl Counter for AtomicCounter {
+    fn increment(&self) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    assert!(impls!(Shared<AtomicCounter>: Counter));
+    assert!(!impls!(Box<AtomicCounter>:   Counter));
+
+    let counter = Shared(Arc::new(AtomicCounter::default()));
+    counter.increment();
+    assert_eq!(counter.count.load(Ordering::SeqCst), 1);
+}
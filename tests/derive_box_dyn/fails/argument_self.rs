@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(BoxDyn))]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn overlaps(&self, other: &Self) -> bool;
+}
+
+fn main() {}
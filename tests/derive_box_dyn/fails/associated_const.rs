@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(BoxDyn))]
+pub trait Shape {
+    const SIDES: usize;
+    fn area(&self) -> f64;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box), boxed_alias = "BoxedShape")]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn contains<P: Into<(f64, f64)>>(&self, point: P) -> bool;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(BoxDyn))]
+pub trait Visitor {
+    fn visit<T: std::fmt::Debug>(&self, value: T);
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(BoxDyn))]
+pub trait Shape {
+    const SIDES: usize;
+    fn scale<F: Into<f64>>(&self, factor: F) -> f64;
+    fn new() -> u8;
+    fn area(&self) -> f64;
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(BoxDyn))]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn split(&self) -> Option<Self>;
+    fn merge(&self) -> Result<Self, String>;
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, BoxDyn), boxed_alias = "BoxedShape")]
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+#[blanket(derive(BoxDyn), boxed_alias = "BoxedScaled")]
+pub trait Scaled<'a, T: Copy> {
+    fn scaled(&self, factor: &'a T) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+impl<'a> Scaled<'a, f64> for Square {
+    fn scaled(&self, factor: &'a f64) -> f64 {
+        self.0 * factor
+    }
+}
+
+fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+fn main() {
+    assert!(impls!(Box<Square>:              Shape));
+    assert!(impls!(BoxedShape:               Shape));
+    assert!(impls!(BoxedScaled<'static, f64>: Scaled<'static, f64>));
+
+    let shapes: Vec<BoxedShape> = vec![Box::new(Square(1.0)), Box::new(Square(3.0))];
+    assert_eq!(total_area(&shapes), 10.0);
+
+    let scaled: BoxedScaled<f64> = Box::new(Square(2.0));
+    assert_eq!(scaled.scaled(&1.5), 3.0);
+}
@@ -0,0 +1,32 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+// methods taking `self` by value do not prevent the trait from being made
+// into an object, they only cannot be called on the trait object
+#[blanket(derive(Box), boxed_alias = "BoxedShape")]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn into_area(self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn into_area(self) -> f64 {
+        self.area()
+    }
+}
+
+fn main() {
+    assert!(impls!(Box<Square>: Shape));
+
+    let shape: BoxedShape = Box::new(Square(2.0));
+    assert_eq!(shape.area(), 4.0);
+    assert_eq!(Box::new(Square(3.0)).into_area(), 9.0);
+}
@@ -0,0 +1,39 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Box, BoxDyn, BoxDynSendSync))]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+fn main() {
+    assert!(impls!(Box<Square>:                  Shape));
+    assert!(impls!(Box<dyn Shape>:               Shape));
+    assert!(impls!(Box<dyn Shape + Send + Sync>: Shape));
+
+    let mut squares = vec![Box::new(Square(1.0)), Box::new(Square(2.0))];
+    squares.iter_mut().for_each(|s| Shape::scale(s, 2.0));
+    assert_eq!(total_area(&squares), 20.0);
+
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(1.0)), Box::new(Square(3.0))];
+    assert_eq!(total_area(&shapes), 10.0);
+}
@@ -0,0 +1,55 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(BoxDyn))]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+    fn describe(&self) -> String
+    where
+        Self: Sized,
+    {
+        format!("shape of area {}", self.area())
+    }
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+struct Circle(f64);
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        3.0 * self.0 * self.0
+    }
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+fn main() {
+    assert!(impls!(Box<dyn Shape>: Shape));
+    assert!(!impls!(Box<Square>:   Shape));
+
+    let mut shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(1.0)), Box::new(Circle(1.0))];
+    for shape in shapes.iter_mut() {
+        shape.scale(2.0);
+    }
+    assert_eq!(total_area(&shapes), 16.0);
+    assert_eq!(shapes[0].describe(), "shape of area 4");
+}
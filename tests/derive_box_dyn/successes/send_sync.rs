@@ -0,0 +1,35 @@
+extern crate blanket;
+extern crate impls;
+
+use std::thread;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(BoxDyn, BoxDynSendSync))]
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+fn total_area<S: Shape + Send + 'static>(shapes: Vec<S>) -> f64 {
+    thread::spawn(move || shapes.iter().map(Shape::area).sum())
+        .join()
+        .unwrap()
+}
+
+fn main() {
+    assert!(impls!(Box<dyn Shape>:               Shape));
+    assert!(impls!(Box<dyn Shape + Send + Sync>: Shape));
+    assert!(!impls!(Box<dyn Shape + Send>:       Shape));
+
+    let shapes: Vec<Box<dyn Shape + Send + Sync>> = vec![Box::new(Square(1.0)), Box::new(Square(2.0))];
+    assert_eq!(total_area(shapes), 5.0);
+}
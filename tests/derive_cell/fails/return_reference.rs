@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Cell))]
+pub trait Named {
+    fn id(&self) -> u32;
+    fn name(&self) -> &str;
+}
+
+fn main() {}
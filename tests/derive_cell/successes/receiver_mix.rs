@@ -0,0 +1,55 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Cell))]
+pub trait Counter {
+    fn get(&self) -> u32;
+    fn set(&mut self, value: u32);
+    fn into_count(self) -> u32;
+}
+
+#[derive(Clone, Copy, Default)]
+struct SimpleCounter(u32);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> u32 {
+        self.0
+    }
+    fn set(&mut self, value: u32) {
+        self.0 = value;
+    }
+    fn into_count(self) -> u32 {
+        self.0
+    }
+}
+
+struct OwnedCounter(String);
+
+impl Counter for OwnedCounter {
+    fn get(&self) -> u32 {
+        self.0.len() as u32
+    }
+    fn set(&mut self, value: u32) {
+        self.0 = "x".repeat(value as usize);
+    }
+    fn into_count(self) -> u32 {
+        self.0.len() as u32
+    }
+}
+
+fn main() {
+    assert!(impls!(Cell<SimpleCounter>: Counter));
+    assert!(impls!(Cell<OwnedCounter>: !Counter));
+
+    let mut counter = Cell::new(SimpleCounter(1));
+    assert_eq!(Counter::get(&counter), 1);
+    Counter::set(&mut counter, 3);
+    assert_eq!(Counter::get(&counter), 3);
+    counter.replace(SimpleCounter(5));
+    assert_eq!(counter.into_count(), 5);
+}
@@ -0,0 +1,56 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Cell, Ref))]
+pub trait Counter {
+    fn count(&self) -> u32;
+    #[blanket(as = "take")]
+    fn flush(&self) -> u32;
+}
+
+#[derive(Clone, Copy, Default)]
+struct Hits(u32);
+
+impl Counter for Hits {
+    fn count(&self) -> u32 {
+        self.0
+    }
+    fn flush(&self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Fixed(u32);
+
+impl Counter for Fixed {
+    fn count(&self) -> u32 {
+        self.0
+    }
+    fn flush(&self) -> u32 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Cell<Hits>:  Counter));
+    assert!(impls!(&Hits:       Counter));
+    assert!(!impls!(Cell<Fixed>: Counter));
+    assert!(impls!(&Fixed:      Counter));
+
+    let hits = Cell::new(Hits(3));
+    assert_eq!(Counter::count(&hits), 3);
+    // the counter is reset once flushed
+    assert_eq!(Counter::flush(&hits), 3);
+    assert_eq!(Counter::count(&hits), 0);
+
+    // other derives forward the method as usual
+    let fixed = Fixed(2);
+    assert_eq!(Counter::flush(&&fixed), 2);
+    assert_eq!(Counter::count(&&fixed), 2);
+}
@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use impls::impls;
+
+mod value {
+    use blanket::blanket;
+
+    // local traits shadowing the marker traits of the prelude
+    #[allow(dead_code)]
+    pub(crate) trait Copy {}
+    #[allow(dead_code)]
+    pub(crate) trait Send {}
+    #[allow(dead_code)]
+    pub(crate) trait Sync {}
+
+    #[blanket(derive(Cell), thread_safe)]
+    pub(crate) trait Value {
+        fn get(&self) -> u32;
+    }
+
+    #[derive(Clone, std::marker::Copy)]
+    pub(crate) struct Number(pub(crate) u32);
+
+    impl Value for Number {
+        fn get(&self) -> u32 {
+            self.0
+        }
+    }
+}
+
+use std::cell::Cell;
+
+use value::Number;
+use value::Value;
+
+fn main() {
+    assert!(impls!(Cell<Number>: Value));
+    assert_eq!(Value::get(&Cell::new(Number(3))), 3);
+}
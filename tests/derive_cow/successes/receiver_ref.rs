@@ -0,0 +1,72 @@
+extern crate blanket;
+extern crate impls;
+
+use std::borrow::Cow;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Cow))]
+pub trait Words {
+    fn words(&self) -> usize;
+    fn first(&self) -> Option<&str>;
+}
+
+impl Words for str {
+    fn words(&self) -> usize {
+        self.split_whitespace().count()
+    }
+    fn first(&self) -> Option<&str> {
+        self.split_whitespace().next()
+    }
+}
+
+#[blanket(derive(Cow))]
+pub trait Total {
+    fn total(&self) -> u32;
+}
+
+impl Total for [u32] {
+    fn total(&self) -> u32 {
+        self.iter().sum()
+    }
+}
+
+// a type which cannot be cloned, and whose owned form has no
+// implementation of the trait
+#[derive(Debug)]
+pub struct Config(u32);
+
+impl ToOwned for Config {
+    type Owned = Box<Config>;
+    fn to_owned(&self) -> Box<Config> {
+        Box::new(Config(self.0))
+    }
+}
+
+impl Total for Config {
+    fn total(&self) -> u32 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Cow<'static, str>:    Words));
+    assert!(impls!(Cow<'static, [u32]>:  Total));
+    assert!(impls!(Cow<'static, Config>: Total));
+    assert!(impls!(Box<Config>:          !Total));
+
+    let borrowed: Cow<str> = Cow::Borrowed("hello blanket world");
+    assert_eq!(borrowed.words(), 3);
+    let owned: Cow<str> = Cow::Owned(String::from("owned text"));
+    assert_eq!(owned.first(), Some("owned"));
+
+    let numbers: Cow<[u32]> = Cow::Owned(vec![1, 2, 3]);
+    assert_eq!(numbers.total(), 6);
+
+    let config = Config(4);
+    let borrowed: Cow<Config> = Cow::Borrowed(&config);
+    assert_eq!(borrowed.total(), 4);
+    let owned: Cow<Config> = Cow::Owned(Box::new(Config(5)));
+    assert_eq!(owned.total(), 5);
+}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Deref, Box))]
+pub trait Counter {
+    fn increment(&self);
+}
+
+fn main() {}
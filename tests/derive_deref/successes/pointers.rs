@@ -0,0 +1,82 @@
+extern crate blanket;
+extern crate impls;
+
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+pub struct MyArc<T>(Arc<T>);
+
+impl<T> Deref for MyArc<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+mod boxes {
+    pub struct MyBox<T>(pub Box<T>);
+
+    impl<T> std::ops::Deref for MyBox<T> {
+        type Target = T;
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+}
+
+#[blanket(for(MyArc, boxes::MyBox))]
+pub trait Counter {
+    fn count(&self) -> u8;
+}
+
+struct Fixed(u8);
+
+impl Counter for Fixed {
+    fn count(&self) -> u8 {
+        self.0
+    }
+}
+
+pub struct Guard<T>(T);
+
+impl<T> Deref for Guard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Guard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[blanket(for(Guard))]
+pub trait Reset {
+    fn reset(&mut self);
+}
+
+impl Reset for Fixed {
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+fn main() {
+    assert!(impls!(MyArc<Fixed>:        Counter));
+    assert!(impls!(boxes::MyBox<Fixed>: Counter));
+    assert!(!impls!(Box<Fixed>:         Counter));
+    assert!(impls!(Guard<Fixed>:        Reset));
+
+    assert_eq!(MyArc(Arc::new(Fixed(1))).count(), 1);
+    assert_eq!(boxes::MyBox(Box::new(Fixed(2))).count(), 2);
+
+    let mut guard = Guard(Fixed(3));
+    guard.reset();
+    assert_eq!(guard.0 .0, 0);
+}
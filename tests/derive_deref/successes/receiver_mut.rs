@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Deref))]
+pub trait Counter {
+    fn count(&self) -> u8;
+    fn increment(&mut self);
+}
+
+#[derive(Default)]
+struct SimpleCounter {
+    count: u8,
+}
+
+impl Counter for SimpleCounter {
+    fn count(&self) -> u8 {
+        self.count
+    }
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+fn main() {
+    assert!(impls!(SimpleCounter:       Counter));
+    assert!(impls!(&mut SimpleCounter:  Counter));
+    assert!(impls!(Box<SimpleCounter>:  Counter));
+    assert!(!impls!(&SimpleCounter:     Counter));
+    assert!(!impls!(Rc<SimpleCounter>:  Counter));
+
+    let cell = RefCell::new(SimpleCounter::default());
+    cell.borrow_mut().increment();
+    assert_eq!(cell.borrow_mut().count(), 1);
+}
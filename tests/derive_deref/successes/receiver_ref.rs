@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Deref))]
+pub trait Counter {
+    fn increment(&self);
+}
+
+#[derive(Default)]
+struct AtomicCounter {
+    count: AtomicU8,
+}
+
+impl Counter for AtomicCounter {
+    fn increment(&self) {
+        self.count.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    assert!(impls!(AtomicCounter:         Counter));
+    assert!(impls!(&AtomicCounter:        Counter));
+    assert!(impls!(Box<AtomicCounter>:    Counter));
+    assert!(impls!(Rc<AtomicCounter>:     Counter));
+    assert!(impls!(Arc<AtomicCounter>:    Counter));
+    assert!(impls!(Box<dyn Counter>:      Counter));
+    assert!(impls!(&Rc<AtomicCounter>:    Counter));
+
+    let counter = Arc::new(AtomicCounter::default());
+    counter.increment();
+    assert_eq!(counter.count.load(Ordering::SeqCst), 1);
+}
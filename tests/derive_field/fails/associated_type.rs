@@ -0,0 +1,15 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(for_field(Wrapper = "inner"))]
+pub trait Container {
+    type Item;
+    fn first(&self) -> Option<Self::Item>;
+}
+
+struct Wrapper {
+    inner: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(for_field(Wrapper = "inner"))]
+pub trait Duplicate {
+    fn duplicate(&self) -> Self;
+}
+
+struct Wrapper {
+    inner: u8,
+}
+
+fn main() {}
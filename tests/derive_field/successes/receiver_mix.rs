@@ -0,0 +1,57 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(for_field(Named = "counter", Pair = "0"))]
+pub trait Counter {
+    fn count(&self) -> u32;
+    fn increment(&mut self);
+    fn into_count(self) -> u32;
+    fn merge(&mut self, other: &Self);
+}
+
+#[derive(Default)]
+struct Simple(u32);
+
+impl Counter for Simple {
+    fn count(&self) -> u32 {
+        self.0
+    }
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+    fn into_count(self) -> u32 {
+        self.0
+    }
+    fn merge(&mut self, other: &Self) {
+        self.0 += other.0;
+    }
+}
+
+#[derive(Default)]
+struct Named {
+    name: &'static str,
+    counter: Simple,
+}
+
+#[derive(Default)]
+struct Pair(Simple, Simple);
+
+fn main() {
+    assert!(impls!(Named: Counter));
+    assert!(impls!(Pair:  Counter));
+
+    let mut named = Named { name: "hits", ..Named::default() };
+    named.increment();
+    named.increment();
+    assert_eq!(named.count(), 2);
+    assert_eq!(named.name, "hits");
+
+    let mut pair = Pair::default();
+    pair.increment();
+    pair.merge(&Pair(Simple(3), Simple(5)));
+    assert_eq!(pair.1.count(), 0);
+    assert_eq!(pair.into_count(), 4);
+}
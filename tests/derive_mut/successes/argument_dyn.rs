@@ -0,0 +1,48 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Mut))]
+pub trait Visitor {
+    fn visit_leaf(&mut self, value: u32);
+}
+
+#[blanket(derive(Ref, Mut, Box))]
+pub trait Node {
+    fn accept(&self, visitor: &mut dyn Visitor);
+    fn accept_boxed(&self, mut visitor: Box<dyn Visitor + '_>) {
+        self.accept(&mut *visitor);
+    }
+}
+
+struct Leaf(u32);
+
+impl Node for Leaf {
+    fn accept(&self, visitor: &mut dyn Visitor) {
+        visitor.visit_leaf(self.0);
+    }
+}
+
+#[derive(Default)]
+struct Sum(u32);
+
+impl Visitor for Sum {
+    fn visit_leaf(&mut self, value: u32) {
+        self.0 += value;
+    }
+}
+
+fn main() {
+    assert!(impls!(&Leaf:     Node));
+    assert!(impls!(&mut Leaf: Node));
+    assert!(impls!(Box<Leaf>: Node));
+
+    let mut sum = Sum::default();
+    (&Leaf(1)).accept(&mut sum);
+    (&mut Leaf(2)).accept(&mut sum);
+    Box::new(Leaf(3)).accept(&mut sum);
+    Box::new(Leaf(4)).accept_boxed(Box::new(&mut sum));
+    assert_eq!(sum.0, 10);
+}
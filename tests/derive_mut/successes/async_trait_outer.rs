@@ -0,0 +1,70 @@
+extern crate async_trait;
+extern crate blanket;
+extern crate impls;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::RawWaker;
+use std::task::RawWakerVTable;
+use std::task::Waker;
+
+use async_trait::async_trait;
+use blanket::blanket;
+use impls::impls;
+
+#[async_trait]
+#[blanket(derive(Mut))]
+pub trait Store {
+    fn name(&self) -> &str;
+    async fn get(&self, key: &str) -> Option<u32>;
+    async fn put(&mut self, key: &str, value: u32);
+}
+
+#[derive(Default)]
+struct Memory(Vec<(String, u32)>);
+
+#[async_trait]
+impl Store for Memory {
+    fn name(&self) -> &str {
+        "memory"
+    }
+    async fn get(&self, key: &str) -> Option<u32> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+    }
+    async fn put(&mut self, key: &str, value: u32) {
+        self.0.push((key.to_string(), value));
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    let waker = unsafe { Waker::from_raw(raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = Pin::as_mut(&mut future).poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+fn main() {
+    assert!(impls!(Memory:      Store));
+    assert!(impls!(&mut Memory: Store));
+
+    let mut memory = Memory::default();
+    let mut store = &mut memory;
+    assert_eq!(Store::name(&store), "memory");
+    block_on(Store::put(&mut store, "answer", 42));
+    assert_eq!(block_on(Store::get(&store, "answer")), Some(42));
+    assert_eq!(block_on(Store::get(&store, "question")), None);
+}
@@ -0,0 +1,44 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Mut))]
+pub trait Writer<'a> {
+    fn buffer(&mut self) -> &'a mut [u8];
+}
+
+struct Chunks<'a> {
+    rest: &'a mut [u8],
+    size: usize,
+}
+
+impl<'a> Writer<'a> for Chunks<'a> {
+    fn buffer(&mut self) -> &'a mut [u8] {
+        let rest = std::mem::take(&mut self.rest);
+        let size = self.size.min(rest.len());
+        let (chunk, rest) = rest.split_at_mut(size);
+        self.rest = rest;
+        chunk
+    }
+}
+
+fn fill<'a, W: Writer<'a>>(mut writer: W, value: u8) -> &'a mut [u8] {
+    let chunk = writer.buffer();
+    chunk.iter_mut().for_each(|b| *b = value);
+    chunk
+}
+
+fn main() {
+    assert!(impls!(&mut Chunks<'static>: Writer<'static>));
+
+    let mut data = [0u8; 5];
+    let mut chunks = Chunks { rest: &mut data, size: 2 };
+    let first = fill(&mut chunks, 1);
+    let second = fill(&mut chunks, 2);
+    // both chunks borrow from the data rather than from the writer
+    assert_eq!((first.len(), second.len()), (2, 2));
+    assert_eq!(chunks.buffer(), &mut [0]);
+    assert_eq!(data, [1, 1, 2, 2, 0]);
+}
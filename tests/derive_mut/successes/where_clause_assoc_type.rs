@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Mut))]
+pub trait Stream
+where
+    Self::Item: Default,
+{
+    type Item;
+    fn next(&mut self) -> Option<Self::Item>
+    where
+        Self::Item: Clone;
+}
+
+#[derive(Default)]
+struct Repeat {
+    count: Cell<u8>,
+}
+
+impl Stream for Repeat {
+    type Item = u8;
+    fn next(&mut self) -> Option<u8> {
+        self.count.set(self.count.get() + 1);
+        Some(self.count.get())
+    }
+}
+
+fn main() {
+    assert!(impls!(Repeat: Stream));
+    assert!(impls!(&mut Repeat: Stream));
+
+    let mut repeat = Repeat::default();
+    assert_eq!(Stream::next(&mut &mut repeat), Some(1));
+    assert_eq!(repeat.count.get(), 1);
+}
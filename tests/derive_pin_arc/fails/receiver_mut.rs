@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(PinArc))]
+pub trait Signal {
+    fn get(&self) -> u8;
+    fn reset(&mut self);
+}
+
+fn main() {}
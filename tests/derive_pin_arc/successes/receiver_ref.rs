@@ -0,0 +1,41 @@
+extern crate blanket;
+extern crate impls;
+
+use std::pin::Pin;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(PinArc))]
+pub trait Signal {
+    fn get(&self) -> u8;
+    fn notify(self: Pin<&Self>) -> u8;
+}
+
+#[derive(Default)]
+struct Counter {
+    count: AtomicU8,
+}
+
+impl Signal for Counter {
+    fn get(&self) -> u8 {
+        self.count.load(Ordering::SeqCst)
+    }
+    fn notify(self: Pin<&Self>) -> u8 {
+        self.count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+fn main() {
+    assert!(impls!(Pin<Arc<Counter>>:    Signal));
+    assert!(impls!(Pin<Arc<dyn Signal>>: Signal));
+
+    let signal = Arc::pin(Counter::default());
+    let shared = signal.clone();
+    assert_eq!(Signal::notify(Pin::new(&shared)), 1);
+    assert_eq!(Signal::notify(Pin::new(&signal)), 2);
+    assert_eq!(Signal::get(&signal), 2);
+}
@@ -0,0 +1,15 @@
+extern crate blanket;
+
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use blanket::blanket;
+
+#[blanket(derive(PinMut))]
+pub trait Source {
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u8>>;
+    fn is_terminated(&self) -> bool;
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+extern crate blanket;
+extern crate impls;
+
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::RawWaker;
+use std::task::RawWakerVTable;
+use std::task::Waker;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(PinMut))]
+pub trait Source {
+    type Item;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+#[derive(Default)]
+struct Countdown(u8);
+
+impl Source for Countdown {
+    type Item = u8;
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u8>> {
+        let this = self.get_mut();
+        if this.0 == 0 {
+            Poll::Ready(None)
+        } else {
+            this.0 -= 1;
+            Poll::Ready(Some(this.0))
+        }
+    }
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+fn main() {
+    assert!(impls!(Pin<&mut Countdown>: Source));
+    assert!(impls!(Pin<&mut dyn Source<Item = u8>>: Source));
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut countdown = Countdown(2);
+    let mut pinned = Pin::new(&mut countdown);
+    assert_eq!(Pin::new(&mut pinned).poll_next(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(Pin::new(&mut pinned).poll_next(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(Pin::new(&mut pinned).poll_next(&mut cx), Poll::Ready(None));
+    assert_eq!(countdown.0, 0);
+}
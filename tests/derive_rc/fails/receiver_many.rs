@@ -0,0 +1,13 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Rc))]
+pub trait Counter {
+    fn get(&self) -> u8;
+    fn increment(&mut self);
+    fn reset(self);
+    fn pinned(self: std::pin::Pin<&mut Self>);
+}
+
+fn main() {}
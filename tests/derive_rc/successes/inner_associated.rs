@@ -0,0 +1,29 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc), inner = "str")]
+pub trait Counter {
+    const C: u8;
+    fn count(&self) -> u8;
+}
+
+impl Counter for str {
+    const C: u8 = 1;
+    fn count(&self) -> u8 {
+        self.len() as u8 + Self::C
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<str>:    Counter));
+    assert!(impls!(Rc<String>: !Counter));
+
+    let counter: Rc<str> = Rc::from("abc");
+    assert_eq!(counter.count(), 4);
+    assert_eq!(<Rc<str> as Counter>::C, 1);
+}
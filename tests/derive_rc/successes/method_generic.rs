@@ -0,0 +1,49 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc))]
+pub trait Visitor {
+    fn visit<T: std::fmt::Debug>(&self, value: T) -> String;
+}
+
+#[blanket(derive(Rc))]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scaled<T: Into<f64>>(&self, factor: T) -> f64
+    where
+        Self: Sized,
+    {
+        self.area() * factor.into()
+    }
+}
+
+struct Printer;
+
+impl Visitor for Printer {
+    fn visit<T: std::fmt::Debug>(&self, value: T) -> String {
+        format!("{:?}", value)
+    }
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<Printer>: Visitor));
+    assert!(impls!(Rc<Square>: Shape));
+    assert!(impls!(Rc<dyn Shape>: Shape));
+
+    assert_eq!(Rc::new(Printer).visit(1), "1");
+    let shape: Rc<dyn Shape> = Rc::new(Square(2.0));
+    assert_eq!(shape.scaled(2), 8.0);
+}
@@ -0,0 +1,54 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc))]
+pub trait Position {
+    fn value(&self) -> u8;
+    fn step(&self) -> u8
+    where
+        Self: Sized,
+    {
+        1
+    }
+    fn advance(&self) -> u8
+    where
+        Self: Sized,
+    {
+        self.value() + self.step()
+    }
+    fn describe(&self) -> String {
+        format!("at {}", self.value())
+    }
+}
+
+struct Fixed(u8);
+
+impl Position for Fixed {
+    fn value(&self) -> u8 {
+        self.0
+    }
+    fn step(&self) -> u8 {
+        10
+    }
+}
+
+fn main() {
+    assert!(impls!(Fixed:             Position));
+    assert!(impls!(Rc<Fixed>:         Position));
+    assert!(impls!(Rc<dyn Position>:  Position));
+
+    // the sized methods are inherited, so they use the default `step`
+    // rather than the one of the wrapped type
+    let shared = Rc::new(Fixed(1));
+    assert_eq!(Position::advance(&Fixed(1)), 11);
+    assert_eq!(Position::advance(&shared), 2);
+    assert_eq!(Position::describe(&shared), "at 1");
+
+    let object: Rc<dyn Position> = shared;
+    assert_eq!(Position::advance(&object), 2);
+}
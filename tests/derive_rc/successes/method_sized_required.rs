@@ -0,0 +1,31 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc))]
+pub trait Named {
+    fn name(&self) -> String;
+    fn sized_name(&self) -> String
+    where
+        Self: Sized;
+}
+
+struct Anonymous;
+
+impl Named for Anonymous {
+    fn name(&self) -> String {
+        String::new()
+    }
+    fn sized_name(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {
+    assert!(impls!(Anonymous:     Named));
+    assert!(impls!(Rc<Anonymous>: Named));
+}
@@ -0,0 +1,40 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc))]
+pub trait Task {
+    fn name(&self) -> &str;
+    fn spawn(self: Rc<Self>, times: u32) -> usize;
+}
+
+struct Job {
+    runs: Cell<u32>,
+}
+
+impl Task for Job {
+    fn name(&self) -> &str {
+        "job"
+    }
+    fn spawn(self: Rc<Self>, times: u32) -> usize {
+        self.runs.set(self.runs.get() + times);
+        Rc::strong_count(&self)
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<Job>:      Task));
+    assert!(impls!(Rc<dyn Task>: Task));
+
+    let job = Rc::new(Job { runs: Cell::new(0) });
+    let shared = Rc::new(job.clone());
+    // the wrapped `Rc` is given to the wrapped type
+    assert_eq!(Task::spawn(shared, 2), 3);
+    assert_eq!(job.runs.get(), 2);
+    assert_eq!(Rc::strong_count(&job), 1);
+}
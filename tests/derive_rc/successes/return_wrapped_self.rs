@@ -0,0 +1,35 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc))]
+pub trait Version {
+    fn next(&self) -> Option<Self>
+    where
+        Self: Sized;
+    fn number(&self) -> u8;
+}
+
+struct Release(u8);
+
+impl Version for Release {
+    fn next(&self) -> Option<Self> {
+        self.0.checked_add(1).map(Release)
+    }
+    fn number(&self) -> u8 {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<Release>: Version));
+
+    let release = Rc::new(Release(1));
+    let next: Rc<Release> = release.next().unwrap();
+    assert_eq!(next.number(), 2);
+    assert!(Rc::new(Release(255)).next().is_none());
+}
@@ -0,0 +1,39 @@
+extern crate blanket;
+extern crate impls;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc))]
+pub trait Store {
+    fn get(&self, key: &str) -> Option<u32>;
+    #[blanket(skip)]
+    fn set(&mut self, _key: &str, _value: u32) {}
+}
+
+#[derive(Default)]
+struct Memory(HashMap<String, u32>);
+
+impl Store for Memory {
+    fn get(&self, key: &str) -> Option<u32> {
+        self.0.get(key).cloned()
+    }
+    fn set(&mut self, key: &str, value: u32) {
+        self.0.insert(String::from(key), value);
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<Memory>: Store));
+
+    let mut memory = Memory::default();
+    memory.set("a", 1);
+
+    let mut shared = Rc::new(memory);
+    shared.set("b", 2);
+    assert_eq!(shared.get("a"), Some(1));
+    assert_eq!(shared.get("b"), None);
+}
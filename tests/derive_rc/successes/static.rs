@@ -0,0 +1,31 @@
+extern crate blanket;
+extern crate impls;
+
+use std::any::Any;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Rc), static)]
+pub trait Component {
+    fn as_any(&self) -> &dyn Any;
+}
+
+struct Position(u8);
+
+impl Component for Position {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<Position>:                Component));
+    assert!(impls!(Rc<dyn Component + 'static>: Component));
+
+    let component: Rc<dyn Component> = Rc::new(Position(1));
+    let shared = Rc::new(component);
+    let any = Component::as_any(&shared);
+    assert_eq!(any.downcast_ref::<Position>().map(|p| p.0), Some(1));
+}
@@ -0,0 +1,35 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+
+use impls::impls;
+
+mod buffers {
+    use blanket::blanket;
+
+    #[blanket(derive(Rc))]
+    pub trait Window<'a, T: 'a, const N: usize> {
+        fn window(&self, start: usize) -> Option<&'a [T]>;
+    }
+}
+
+pub use buffers::Window as Slices;
+
+struct Samples<'a>(&'a [i16]);
+
+impl<'a> Slices<'a, i16, 2> for Samples<'a> {
+    fn window(&self, start: usize) -> Option<&'a [i16]> {
+        self.0.get(start..start + 2)
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<Samples<'static>>: Slices<'static, i16, 2>));
+    assert!(impls!(Rc<Samples<'static>>: !Slices<'static, i16, 4>));
+
+    let data = [1, 2, 3];
+    let samples = Rc::new(Samples(&data));
+    assert_eq!(Slices::<i16, 2>::window(&samples, 1), Some(&data[1..]));
+    assert_eq!(samples.window(2), None);
+}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(RcRefCell))]
+pub trait Extract {
+    fn extract(self);
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(RcRefCell))]
+pub trait Counter {
+    fn duplicate(&self) -> Self;
+}
+
+fn main() {}
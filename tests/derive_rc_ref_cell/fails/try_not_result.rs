@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(RcRefCellTry))]
+pub trait Counter {
+    fn get(&self) -> Result<u8, std::cell::BorrowMutError>;
+    fn set(&mut self, value: u8);
+}
+
+fn main() {}
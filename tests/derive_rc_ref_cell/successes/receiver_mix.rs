@@ -0,0 +1,52 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(RcRefCell))]
+pub trait Counter {
+    fn get(&self) -> u8;
+    fn set(&mut self, value: u8);
+    fn borrow_mut(&self) {}
+}
+
+#[blanket(derive(Rc, RcRefCell))]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> u8 {
+        self.0
+    }
+    fn set(&mut self, value: u8) {
+        self.0 = value;
+    }
+}
+
+impl Named for SimpleCounter {
+    fn name(&self) -> String {
+        String::from("simple")
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<RefCell<SimpleCounter>>: Counter));
+    assert!(impls!(Rc<RefCell<dyn Counter>>:   Counter));
+    assert!(impls!(Rc<SimpleCounter>:          Named));
+    assert!(impls!(Rc<RefCell<SimpleCounter>>: Named));
+
+    let counter = Rc::new(RefCell::new(SimpleCounter::default()));
+    let mut shared = counter.clone();
+    shared.set(3);
+    assert_eq!(counter.get(), 3);
+    assert_eq!(counter.borrow().0, 3);
+    assert_eq!(Named::name(&counter), "simple");
+}
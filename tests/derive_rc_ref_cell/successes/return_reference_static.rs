@@ -0,0 +1,33 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(RcRefCell))]
+pub trait Named<'a> {
+    fn kind(&self) -> &'static str;
+    fn name(&self) -> &'a str;
+}
+
+struct User<'a>(&'a str);
+
+impl<'a> Named<'a> for User<'a> {
+    fn kind(&self) -> &'static str {
+        "user"
+    }
+    fn name(&self) -> &'a str {
+        self.0
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<RefCell<User<'static>>>: Named<'static>));
+
+    let user = Rc::new(RefCell::new(User("alice")));
+    assert_eq!(user.kind(), "user");
+    assert_eq!(user.name(), "alice");
+}
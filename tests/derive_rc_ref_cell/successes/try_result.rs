@@ -0,0 +1,55 @@
+extern crate blanket;
+extern crate impls;
+
+use std::cell::BorrowMutError;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use blanket::blanket;
+use impls::impls;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Busy,
+    Overflow,
+}
+
+impl From<BorrowMutError> for Error {
+    fn from(_: BorrowMutError) -> Self {
+        Error::Busy
+    }
+}
+
+#[blanket(derive(RcRefCellTry))]
+pub trait Counter {
+    fn get(&self) -> Result<u8, Error>;
+    fn increment(&mut self) -> Result<(), Error>;
+}
+
+#[derive(Default)]
+struct SimpleCounter(u8);
+
+impl Counter for SimpleCounter {
+    fn get(&self) -> Result<u8, Error> {
+        Ok(self.0)
+    }
+    fn increment(&mut self) -> Result<(), Error> {
+        self.0 = self.0.checked_add(1).ok_or(Error::Overflow)?;
+        Ok(())
+    }
+}
+
+fn main() {
+    assert!(impls!(Rc<RefCell<SimpleCounter>>: Counter));
+    assert!(impls!(Rc<RefCell<dyn Counter>>:   Counter));
+
+    let counter = Rc::new(RefCell::new(SimpleCounter::default()));
+    let mut shared = counter.clone();
+    assert_eq!(shared.increment(), Ok(()));
+    assert_eq!(counter.get(), Ok(1));
+
+    let guard = counter.borrow_mut();
+    assert_eq!(shared.get(), Err(Error::Busy));
+    drop(guard);
+    assert_eq!(shared.get(), Ok(1));
+}
@@ -0,0 +1,31 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Ref))]
+pub trait Cursor<'a> {
+    fn current(&self) -> &'a [u8];
+}
+
+struct Slice<'a>(&'a [u8]);
+
+impl<'a> Cursor<'a> for Slice<'a> {
+    fn current(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+fn outlive<'a, C: Cursor<'a>>(cursor: C) -> &'a [u8] {
+    cursor.current()
+}
+
+fn main() {
+    assert!(impls!(&Slice<'static>: Cursor<'static>));
+
+    let data = vec![1, 2, 3];
+    let cursor = Slice(&data);
+    let current = outlive(&cursor);
+    assert_eq!(current, [1, 2, 3]);
+}
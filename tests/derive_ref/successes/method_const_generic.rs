@@ -0,0 +1,29 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Ref, Box))]
+pub trait Snapshot {
+    fn snapshot<const N: usize>(&self) -> [u8; N];
+}
+
+struct Counter(u8);
+
+impl Snapshot for Counter {
+    fn snapshot<const N: usize>(&self) -> [u8; N] {
+        [self.0; N]
+    }
+}
+
+fn main() {
+    assert!(impls!(&Counter:      Snapshot));
+    assert!(impls!(Box<Counter>:  Snapshot));
+    assert!(impls!(&Box<Counter>: Snapshot));
+
+    let counter = Counter(7);
+    assert_eq!((&counter).snapshot::<3>(), [7; 3]);
+    assert_eq!(Box::new(Counter(1)).snapshot::<2>(), [1; 2]);
+    assert_eq!(Snapshot::snapshot::<4>(&&counter), [7; 4]);
+}
@@ -0,0 +1,27 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Ref))]
+pub trait Checksum<I: Iterator<Item = u8> + Clone> {
+    fn checksum(&self, bytes: I) -> u8;
+}
+
+struct Xor;
+
+impl<I: Iterator<Item = u8> + Clone> Checksum<I> for Xor {
+    fn checksum(&self, bytes: I) -> u8 {
+        bytes.fold(0, |acc, b| acc ^ b)
+    }
+}
+
+fn main() {
+    assert!(impls!(&Xor: Checksum<std::vec::IntoIter<u8>>));
+    assert!(impls!(&Xor: Checksum<std::iter::Once<u8>>));
+
+    let bytes = vec![1, 2, 4];
+    assert_eq!((&Xor).checksum(bytes.into_iter()), 7);
+    assert_eq!(Checksum::<std::iter::Once<u8>>::checksum(&&Xor, std::iter::once(3)), 3);
+}
@@ -0,0 +1,51 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Ref, Box))]
+pub trait T {
+    fn get(&self) -> u8;
+}
+
+#[blanket(derive(Ref, Box))]
+pub trait X<T> {
+    fn convert(&self, t: T) -> T;
+}
+
+#[blanket(derive(Ref))]
+pub trait Scale {
+    fn scale<S: Into<u8>>(&self, s: S) -> u8;
+}
+
+struct Value;
+
+impl T for Value {
+    fn get(&self) -> u8 {
+        1
+    }
+}
+
+impl X<u8> for Value {
+    fn convert(&self, t: u8) -> u8 {
+        t + 1
+    }
+}
+
+impl Scale for Value {
+    fn scale<S: Into<u8>>(&self, s: S) -> u8 {
+        s.into() * 2
+    }
+}
+
+fn main() {
+    assert!(impls!(&Value:     X<u8>));
+    assert!(impls!(Box<Value>: X<u8>));
+    assert!(impls!(&Value:     Scale));
+
+    assert_eq!(T::get(&&Value), 1);
+    assert_eq!(T::get(&Box::new(Value)), 1);
+    assert_eq!(X::convert(&Box::new(Value), 1u8), 2);
+    assert_eq!(Scale::scale(&&Value, 2u8), 4);
+}
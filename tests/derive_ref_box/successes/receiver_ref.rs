@@ -0,0 +1,32 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(RefBox, BoxDyn))]
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+fn total_area<S: Shape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(Shape::area).sum()
+}
+
+fn main() {
+    assert!(impls!(&Box<Square>:     Shape));
+    assert!(impls!(&Box<dyn Shape>:  Shape));
+    assert!(!impls!(&Square:         Shape));
+
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Square(1.0)), Box::new(Square(2.0))];
+    let borrowed = shapes.iter().collect::<Vec<&Box<dyn Shape>>>();
+    assert_eq!(total_area(&borrowed), 5.0);
+}
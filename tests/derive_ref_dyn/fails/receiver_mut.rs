@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(RefDyn))]
+pub trait Shape {
+    fn scale(&mut self, factor: f64);
+}
+
+fn main() {}
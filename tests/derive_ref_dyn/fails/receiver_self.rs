@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(RefDyn))]
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn into_area(self) -> f64;
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+extern crate blanket;
+extern crate impls;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(RefDyn))]
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+fn double_area<S: Shape>(shape: S) -> f64 {
+    2.0 * shape.area()
+}
+
+fn main() {
+    assert!(impls!(&dyn Shape: Shape));
+    assert!(!impls!(&Square:   Shape));
+
+    let square = Square(2.0);
+    let shape: &dyn Shape = &square;
+    assert_eq!(double_area(shape), 8.0);
+}
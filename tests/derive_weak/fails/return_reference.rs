@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Weak))]
+pub trait Node {
+    fn name(&self) -> Option<&str>;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Weak))]
+pub trait Node {
+    fn child(&self, index: usize) -> Option<u32>;
+    fn id(&self) -> u32;
+}
+
+fn main() {}
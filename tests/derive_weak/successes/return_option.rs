@@ -0,0 +1,42 @@
+extern crate blanket;
+extern crate impls;
+
+use std::rc::Rc;
+use std::rc::Weak;
+
+use blanket::blanket;
+use impls::impls;
+
+#[blanket(derive(Weak))]
+pub trait Node {
+    fn child(&self, index: usize) -> Option<u32>;
+    fn label(&self) -> Option<String>;
+}
+
+struct Leaf {
+    children: Vec<u32>,
+}
+
+impl Node for Leaf {
+    fn child(&self, index: usize) -> Option<u32> {
+        self.children.get(index).cloned()
+    }
+    fn label(&self) -> Option<String> {
+        Some(String::from("leaf"))
+    }
+}
+
+fn main() {
+    assert!(impls!(Weak<Leaf>:     Node));
+    assert!(impls!(Weak<dyn Node>: Node));
+
+    let leaf = Rc::new(Leaf { children: vec![1, 2] });
+    let weak = Rc::downgrade(&leaf);
+    assert_eq!(weak.child(1), Some(2));
+    assert_eq!(weak.child(2), None);
+    assert_eq!(weak.label(), Some(String::from("leaf")));
+
+    drop(leaf);
+    assert_eq!(weak.child(1), None);
+    assert_eq!(weak.label(), None);
+}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive)]
+pub trait Counter {
+    fn increment(&self);
+}
+
+fn main() {}
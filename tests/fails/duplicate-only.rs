@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box), only(get), only(set))]
+pub trait Counter {
+    fn get(&self) -> u8;
+    fn set(&mut self, _value: u8) {}
+}
+
+fn main() {}
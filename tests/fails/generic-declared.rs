@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box), generic = "T")]
+pub trait Convert<T> {
+    fn convert(&self) -> T;
+}
+
+fn main() {}
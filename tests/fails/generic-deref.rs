@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Deref), generic = "P")]
+pub trait Counter {
+    fn get(&self) -> u8;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+extern crate blanket;
+use blanket::blanket;
+
+#[blanket(derive(Box), bound = "C: +")]
+pub trait Counter {
+    fn increment(&mut self);
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box), only(std::get))]
+pub trait Counter {
+    fn get(&self) -> u8;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Ref), wrapper = "crate::Shared")]
+pub trait Counter {
+    fn increment(&self);
+}
+
+fn main() {}
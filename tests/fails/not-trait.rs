@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub struct Counter {
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box), only(a, b))]
+pub trait Letters {
+    fn a(&self) -> char;
+    fn b(&self) -> char;
+    fn c(&self) -> char;
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+pub struct MyBox<T>(Box<T>);
+
+#[blanket(derive(Deref), for(MyBox))]
+pub trait Counter {
+    fn increment(&self);
+}
+
+fn main() {}
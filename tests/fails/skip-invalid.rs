@@ -0,0 +1,13 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[blanket(hide)]
+    fn get(&self) -> u8 {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Register {
+    #[blanket(skip)]
+    const NAME: &'static str;
+    fn read(&self) -> u8;
+}
+
+fn main() {}
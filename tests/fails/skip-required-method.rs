@@ -0,0 +1,12 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[blanket(skip)]
+    fn get(&self) -> u8;
+    fn set(&mut self, value: u8);
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Parser {
+    #[blanket(skip)]
+    type Error;
+    fn parse(&self, input: &str) -> Result<u8, Self::Error>;
+}
+
+fn main() {}
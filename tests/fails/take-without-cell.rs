@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[blanket(as = "take")]
+    fn flush(&self) -> u32;
+}
+
+fn main() {}
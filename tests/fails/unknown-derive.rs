@@ -0,0 +1,10 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Boxx))]
+pub trait Counter {
+    fn increment(&self);
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box))]
+pub trait Counter {
+    #[blanket(via = "as_ref")]
+    fn into_count(self) -> u32;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate blanket;
+
+use blanket::blanket;
+
+#[blanket(derive(Box, ArcMutex))]
+pub trait Counter {
+    #[blanket(via = "as_ref")]
+    fn flush(&self) -> u32;
+}
+
+fn main() {}
@@ -0,0 +1,106 @@
+extern crate blanket;
+use blanket::blanket;
+
+use std::rc::Rc;
+
+#[blanket(derive(Ref, Mut, Rc, Box))]
+pub trait Store<K: PartialEq, V: Clone> {
+    fn get(&self, key: &K) -> Option<V>;
+    fn count(&self) -> usize;
+}
+
+#[blanket(derive(Mut, Box))]
+pub trait Source {
+    type Item;
+    fn next_item(&mut self) -> Option<Self::Item>;
+    fn peek(&self) -> Option<&Self::Item>;
+}
+
+#[blanket(derive(Box))]
+pub trait Shape: Sized {
+    fn scale(self, factor: u32) -> Self;
+    fn split(&self) -> Option<(Self, Self)>;
+}
+
+struct Pairs(Vec<(&'static str, u32)>);
+
+impl Store<&'static str, u32> for Pairs {
+    fn get(&self, key: &&'static str) -> Option<u32> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+    }
+    fn count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+struct Countdown(u32);
+
+impl Source for Countdown {
+    type Item = u32;
+    fn next_item(&mut self) -> Option<u32> {
+        self.0 = self.0.checked_sub(1)?;
+        Some(self.0)
+    }
+    fn peek(&self) -> Option<&u32> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Square(u32);
+
+impl Shape for Square {
+    fn scale(self, factor: u32) -> Self {
+        Square(self.0 * factor)
+    }
+    fn split(&self) -> Option<(Self, Self)> {
+        if self.0 > 1 {
+            Some((Square(self.0 / 2), Square(self.0 - self.0 / 2)))
+        } else {
+            None
+        }
+    }
+}
+
+fn lookup<S: Store<&'static str, u32>>(store: S, key: &'static str) -> Option<u32> {
+    store.get(&key)
+}
+
+fn drain<S: Source<Item = u32>>(mut source: S) -> Vec<u32> {
+    let mut items = Vec::new();
+    while let Some(item) = source.next_item() {
+        items.push(item);
+    }
+    items
+}
+
+#[test]
+fn generic_trait() {
+    let mut pairs = Pairs(vec![("a", 1), ("b", 2)]);
+    assert_eq!(lookup(&pairs, "a"), Some(1));
+    assert_eq!(lookup(&mut pairs, "b"), Some(2));
+    assert_eq!(lookup(Rc::new(Pairs(vec![("c", 3)])), "c"), Some(3));
+    assert_eq!(lookup(Box::new(pairs), "d"), None);
+}
+
+#[test]
+fn associated_type() {
+    let mut countdown = Countdown(3);
+    assert_eq!(Source::peek(&&mut countdown), Some(&3));
+    assert_eq!(drain(&mut countdown), vec![2, 1, 0]);
+
+    let boxed = Box::new(Countdown(2));
+    assert_eq!(boxed.peek(), Some(&2));
+    assert_eq!(drain(boxed), vec![1, 0]);
+}
+
+#[test]
+fn return_self() {
+    let boxed = Box::new(Square(2)).scale(3);
+    assert_eq!(boxed, Box::new(Square(6)));
+    assert_eq!(
+        boxed.split(),
+        Some((Box::new(Square(3)), Box::new(Square(3))))
+    );
+    assert_eq!(Box::new(Square(1)).split(), None);
+}